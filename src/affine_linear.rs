@@ -0,0 +1,136 @@
+use crate::bonding_curve_trait::Rounding;
+use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_non_negative, check_positive, fixed_parameter,
+    sqrt_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+#[derive(Clone, Debug)]
+pub struct AffineLinear<T = I64F64> {
+    pub slope: T,
+    pub base_price: T,
+    pub token_supply: T,
+    pub reserve_balance: T,
+}
+
+/*
+* P = k * S + b
+where:
+
+*   P is the price of a token,
+*   k is the slope (a constant parameter),
+*   b is the base price, the price of the first token,
+*   S is the current token supply.
+
+With b = 0 this is `Linear`. The supported supply is bounded by
+S^2 * max(1, k) <= MAX / 8 and S * max(1, b) <= MAX / 8, which keeps both terms of the
+integral and its inverse representable. See `max_supply`.
+*/
+
+impl<T: Fixed> AffineLinear<T> {
+    pub fn new(slope: f64, base_price: f64) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(
+            fixed_parameter("slope", slope)?,
+            fixed_parameter("base_price", base_price)?,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(slope: T, base_price: T) -> Result<Self, BondingCurveError> {
+        Ok(AffineLinear {
+            slope: check_positive("slope", slope)?,
+            base_price: check_non_negative("base_price", base_price)?,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
+        })
+    }
+
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        slope: T,
+        base_price: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(slope, base_price)?.with_state(token_supply, reserve_balance)
+    }
+
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(
+        slope: f64,
+        base_price: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::new(slope, base_price)?.with_initial_supply(initial_supply)
+    }
+}
+
+impl<T: Fixed> IntegralCurve for AffineLinear<T> {
+    type Value = T;
+
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        self.slope
+            .mul_checked(check_domain(supply, "price")?, "price")?
+            .add_checked(self.base_price, "price")
+    }
+
+    // Integral of the price from 0 to `supply`, k * S^2 / 2 + b * S, rounded in
+    // `rounding`'s direction by its error bound. The quadratic term is within (k + 3) ulps
+    // as in `Linear` and b * S truncates once more, so the result is within (k + 4) ulps.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let squared = supply.mul_checked(supply, "integral")?;
+        let value = self
+            .slope
+            .mul_checked(squared, "integral")?
+            .div_checked(T::from_num(2), "integral")?
+            .add_checked(self.base_price.mul_checked(supply, "integral")?, "integral")?;
+        rounding.apply(value, ulps(self.slope.saturating_add(T::from_num(4))))
+    }
+
+    // Closed-form estimate of the supply at which the integral reaches `integral`, the
+    // positive root of k * S^2 / 2 + b * S - I, written as S = 2I / (b + sqrt(b^2 + 2kI))
+    // so that it does not cancel for large b. Where b^2 does not fit, the linear term
+    // dominates and I / b estimates the root from above.
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
+        if integral <= T::ZERO {
+            return Ok(T::ZERO);
+        }
+        let closed_form = || -> Result<T, BondingCurveError> {
+            let discriminant = self
+                .base_price
+                .mul_checked(self.base_price, "integral inverse")?
+                .add_checked(
+                    self.slope
+                        .mul_checked(integral, "integral inverse")?
+                        .mul_checked(T::from_num(2), "integral inverse")?,
+                    "integral inverse",
+                )?;
+            let denominator = self
+                .base_price
+                .add_checked(sqrt_fixed(discriminant)?, "integral inverse")?;
+            integral
+                .mul_checked(T::from_num(2), "integral inverse")?
+                .div_checked(denominator, "integral inverse")
+        };
+        closed_form().or_else(|error| integral.checked_div(self.base_price).ok_or(error))
+    }
+
+    // min(sqrt(MAX / (8 * max(1, k))), MAX / (8 * max(1, b)))
+    fn supply_limit(&self) -> T {
+        let headroom = T::MAX / T::from_num(8);
+        let quadratic = sqrt_fixed(headroom / self.slope.max(T::from_num(1))).unwrap_or(T::ZERO);
+        quadratic.min(headroom / self.base_price.max(T::from_num(1)))
+    }
+
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
+    }
+
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
+    }
+}
@@ -0,0 +1,356 @@
+use crate::bonding_curve_trait::BondingCurve;
+use crate::errors::BondingCurveError;
+use crate::{
+    AffineLinear, Augmented, Bancor, ConstantProduct, Exponential, LaunchCurve, Linear,
+    Logarithmic, NaturalExponential, OffsetPower, Polynomial, Sigmoid,
+};
+use fixed::traits::FixedSigned;
+use fixed::types::I64F64;
+
+// The shape of a curve, as chosen at runtime
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CurveKind {
+    Linear,
+    Exponential,
+    Logarithmic,
+    Sigmoid,
+    Bancor,
+    NaturalExponential,
+    AffineLinear,
+    OffsetPower,
+    Polynomial,
+    ConstantProduct,
+    LaunchCurve,
+    Augmented,
+}
+
+impl CurveKind {
+    pub const ALL: [CurveKind; 12] = [
+        CurveKind::Linear,
+        CurveKind::Exponential,
+        CurveKind::Logarithmic,
+        CurveKind::Sigmoid,
+        CurveKind::Bancor,
+        CurveKind::NaturalExponential,
+        CurveKind::AffineLinear,
+        CurveKind::OffsetPower,
+        CurveKind::Polynomial,
+        CurveKind::ConstantProduct,
+        CurveKind::LaunchCurve,
+        CurveKind::Augmented,
+    ];
+
+    // Lowercase name, as used for the serialized `kind` tag
+    pub fn name(self) -> &'static str {
+        match self {
+            CurveKind::Linear => "linear",
+            CurveKind::Exponential => "exponential",
+            CurveKind::Logarithmic => "logarithmic",
+            CurveKind::Sigmoid => "sigmoid",
+            CurveKind::Bancor => "bancor",
+            CurveKind::NaturalExponential => "natural_exponential",
+            CurveKind::AffineLinear => "affine_linear",
+            CurveKind::OffsetPower => "offset_power",
+            CurveKind::Polynomial => "polynomial",
+            CurveKind::ConstantProduct => "constant_product",
+            CurveKind::LaunchCurve => "launch_curve",
+            CurveKind::Augmented => "augmented",
+        }
+    }
+}
+
+/*
+ * Any of the curve types, selected at runtime.
+ *
+ * Implements `BondingCurve` by dispatching to the wrapped curve, so a service can hold one
+ * `Clone`able type per token instead of a boxed trait object. With the `serde` feature it
+ * is encoded as the wrapped curve's fields plus a `kind` tag; with the `borsh` feature the
+ * wrapped curve's discriminator selects the variant.
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "kind", rename_all = "snake_case", bound = "T: FixedSigned")
+)]
+pub enum AnyCurve<T = I64F64> {
+    Linear(Linear<T>),
+    Exponential(Exponential<T>),
+    Logarithmic(Logarithmic<T>),
+    Sigmoid(Sigmoid<T>),
+    Bancor(Bancor<T>),
+    NaturalExponential(NaturalExponential<T>),
+    AffineLinear(AffineLinear<T>),
+    OffsetPower(OffsetPower<T>),
+    Polynomial(Polynomial<T>),
+    ConstantProduct(ConstantProduct<T>),
+    LaunchCurve(LaunchCurve<T>),
+    Augmented(Augmented<T>),
+}
+
+// Evaluates `$body` with `$curve` bound to the wrapped curve
+macro_rules! dispatch {
+    ($any:expr, $curve:ident => $body:expr) => {
+        match $any {
+            AnyCurve::Linear($curve) => $body,
+            AnyCurve::Exponential($curve) => $body,
+            AnyCurve::Logarithmic($curve) => $body,
+            AnyCurve::Sigmoid($curve) => $body,
+            AnyCurve::Bancor($curve) => $body,
+            AnyCurve::NaturalExponential($curve) => $body,
+            AnyCurve::AffineLinear($curve) => $body,
+            AnyCurve::OffsetPower($curve) => $body,
+            AnyCurve::Polynomial($curve) => $body,
+            AnyCurve::ConstantProduct($curve) => $body,
+            AnyCurve::LaunchCurve($curve) => $body,
+            AnyCurve::Augmented($curve) => $body,
+        }
+    };
+}
+
+impl<T: FixedSigned> AnyCurve<T> {
+    pub fn kind(&self) -> CurveKind {
+        match self {
+            AnyCurve::Linear(_) => CurveKind::Linear,
+            AnyCurve::Exponential(_) => CurveKind::Exponential,
+            AnyCurve::Logarithmic(_) => CurveKind::Logarithmic,
+            AnyCurve::Sigmoid(_) => CurveKind::Sigmoid,
+            AnyCurve::Bancor(_) => CurveKind::Bancor,
+            AnyCurve::NaturalExponential(_) => CurveKind::NaturalExponential,
+            AnyCurve::AffineLinear(_) => CurveKind::AffineLinear,
+            AnyCurve::OffsetPower(_) => CurveKind::OffsetPower,
+            AnyCurve::Polynomial(_) => CurveKind::Polynomial,
+            AnyCurve::ConstantProduct(_) => CurveKind::ConstantProduct,
+            AnyCurve::LaunchCurve(_) => CurveKind::LaunchCurve,
+            AnyCurve::Augmented(_) => CurveKind::Augmented,
+        }
+    }
+
+    // The wrapped curve's shape parameters by name, in constructor order. Supply and
+    // reserve are state rather than parameters and are read through `BondingCurve`.
+    pub fn parameters(&self) -> Vec<(String, T)> {
+        let parameters = match self {
+            AnyCurve::Linear(curve) => vec![("slope", curve.slope)],
+            AnyCurve::Exponential(curve) => vec![
+                ("coefficient", curve.coefficient),
+                ("exponent", curve.exponent),
+            ],
+            AnyCurve::Logarithmic(curve) => vec![
+                ("coefficient", curve.coefficient),
+                ("constant", curve.constant),
+            ],
+            AnyCurve::Sigmoid(curve) => vec![
+                ("max_price", curve.max_price),
+                ("steepness", curve.steepness),
+                ("midpoint", curve.midpoint),
+            ],
+            AnyCurve::Bancor(curve) => vec![("connector_weight", curve.connector_weight)],
+            AnyCurve::NaturalExponential(curve) => vec![
+                ("initial_price", curve.initial_price),
+                ("growth_rate", curve.growth_rate),
+            ],
+            AnyCurve::AffineLinear(curve) => {
+                vec![("slope", curve.slope), ("base_price", curve.base_price)]
+            }
+            AnyCurve::OffsetPower(curve) => vec![
+                ("coefficient", curve.coefficient),
+                ("exponent", curve.exponent),
+                ("base_price", curve.base_price),
+            ],
+            // One entry per coefficient, named as in the constructor's errors
+            AnyCurve::Polynomial(curve) => {
+                return curve
+                    .coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &value)| (format!("coefficients[{index}]"), value))
+                    .collect();
+            }
+            AnyCurve::ConstantProduct(curve) => vec![("virtual_reserve", curve.virtual_reserve)],
+            AnyCurve::LaunchCurve(curve) => vec![
+                ("virtual_reserve", curve.virtual_reserve),
+                ("total_supply", curve.total_supply),
+            ],
+            // Tribute rates in whole basis points, saturating in types too narrow for 10000
+            AnyCurve::Augmented(curve) => vec![
+                ("hatch_price", curve.hatch_price),
+                ("connector_weight", curve.connector_weight),
+                (
+                    "hatch_tribute_bps",
+                    T::saturating_from_num(curve.hatch_tribute_bps()),
+                ),
+                (
+                    "entry_tribute_bps",
+                    T::saturating_from_num(curve.entry_tribute_bps()),
+                ),
+                (
+                    "exit_tribute_bps",
+                    T::saturating_from_num(curve.exit_tribute_bps()),
+                ),
+            ],
+        };
+        parameters
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+
+    // Value of the parameter called `name`, if the wrapped curve has one
+    pub fn parameter(&self, name: &str) -> Option<T> {
+        self.parameters()
+            .into_iter()
+            .find(|(parameter, _)| parameter == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl<T: FixedSigned> BondingCurve<T> for AnyCurve<T> {
+    fn get_price(&self) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.get_price())
+    }
+
+    fn price_at(&self, supply: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.price_at(supply))
+    }
+
+    fn cost_between(&self, from: T, to: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.cost_between(from, to))
+    }
+
+    fn supply_for_reserve(&self, reserve: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.supply_for_reserve(reserve))
+    }
+
+    fn quote_buy(&self, amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.quote_buy(amount))
+    }
+
+    fn quote_sell(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.quote_sell(token_amount))
+    }
+
+    fn quote_buy_with_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.quote_buy_with_reserve(reserve_amount))
+    }
+
+    fn quote_buy_exact_tokens(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.quote_buy_exact_tokens(token_amount))
+    }
+
+    fn quote_sell_for_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.quote_sell_for_reserve(reserve_amount))
+    }
+
+    fn buy_token(&mut self, amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.buy_token(amount))
+    }
+
+    fn buy_with_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.buy_with_reserve(reserve_amount))
+    }
+
+    fn buy_exact_tokens(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.buy_exact_tokens(token_amount))
+    }
+
+    fn sell_token(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.sell_token(token_amount))
+    }
+
+    fn sell_for_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        dispatch!(self, curve => curve.sell_for_reserve(reserve_amount))
+    }
+
+    fn get_supply(&self) -> T {
+        dispatch!(self, curve => curve.get_supply())
+    }
+
+    fn get_reserve(&self) -> Option<T> {
+        dispatch!(self, curve => curve.get_reserve())
+    }
+
+    fn max_supply(&self) -> T {
+        dispatch!(self, curve => curve.max_supply())
+    }
+
+    fn buys_with_reserve(&self) -> bool {
+        dispatch!(self, curve => curve.buys_with_reserve())
+    }
+}
+
+impl<T> From<Linear<T>> for AnyCurve<T> {
+    fn from(curve: Linear<T>) -> Self {
+        AnyCurve::Linear(curve)
+    }
+}
+
+impl<T> From<Exponential<T>> for AnyCurve<T> {
+    fn from(curve: Exponential<T>) -> Self {
+        AnyCurve::Exponential(curve)
+    }
+}
+
+impl<T> From<Logarithmic<T>> for AnyCurve<T> {
+    fn from(curve: Logarithmic<T>) -> Self {
+        AnyCurve::Logarithmic(curve)
+    }
+}
+
+impl<T> From<Sigmoid<T>> for AnyCurve<T> {
+    fn from(curve: Sigmoid<T>) -> Self {
+        AnyCurve::Sigmoid(curve)
+    }
+}
+
+impl<T> From<Bancor<T>> for AnyCurve<T> {
+    fn from(curve: Bancor<T>) -> Self {
+        AnyCurve::Bancor(curve)
+    }
+}
+
+impl<T> From<NaturalExponential<T>> for AnyCurve<T> {
+    fn from(curve: NaturalExponential<T>) -> Self {
+        AnyCurve::NaturalExponential(curve)
+    }
+}
+
+impl<T> From<AffineLinear<T>> for AnyCurve<T> {
+    fn from(curve: AffineLinear<T>) -> Self {
+        AnyCurve::AffineLinear(curve)
+    }
+}
+
+impl<T> From<OffsetPower<T>> for AnyCurve<T> {
+    fn from(curve: OffsetPower<T>) -> Self {
+        AnyCurve::OffsetPower(curve)
+    }
+}
+
+impl<T> From<Polynomial<T>> for AnyCurve<T> {
+    fn from(curve: Polynomial<T>) -> Self {
+        AnyCurve::Polynomial(curve)
+    }
+}
+
+impl<T> From<ConstantProduct<T>> for AnyCurve<T> {
+    fn from(curve: ConstantProduct<T>) -> Self {
+        AnyCurve::ConstantProduct(curve)
+    }
+}
+
+impl<T> From<LaunchCurve<T>> for AnyCurve<T> {
+    fn from(curve: LaunchCurve<T>) -> Self {
+        AnyCurve::LaunchCurve(curve)
+    }
+}
+
+impl<T> From<Augmented<T>> for AnyCurve<T> {
+    fn from(curve: Augmented<T>) -> Self {
+        AnyCurve::Augmented(curve)
+    }
+}
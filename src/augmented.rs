@@ -0,0 +1,425 @@
+use crate::bancor::Bancor;
+use crate::bonding_curve_trait::{BondingCurve, Rounding};
+use crate::errors::BondingCurveError;
+use crate::fees::{BPS_DENOMINATOR, fee_for, gross_up};
+#[cfg(any(feature = "serde", feature = "borsh"))]
+use crate::helpers::check_non_negative;
+use crate::helpers::{
+    CheckedArithmetic, check_connector_weight, check_domain, check_interval, check_positive,
+    fixed_parameter,
+};
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+// Stage of an augmented bonding curve
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum AugmentedPhase {
+    // Fixed-price presale; tokens can be bought but not sold
+    Hatch,
+    // Trading on the Bancor curve, with entry and exit tributes
+    Open,
+}
+
+impl AugmentedPhase {
+    // Lowercase name, as used in `WrongPhase` errors
+    pub fn name(self) -> &'static str {
+        match self {
+            AugmentedPhase::Hatch => "hatch",
+            AugmentedPhase::Open => "open",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Augmented<T = I64F64> {
+    // Price of a token during the hatch
+    pub hatch_price: T,
+    // Bancor connector weight once the curve is open
+    pub connector_weight: T,
+    // Share of each hatch contribution sent to the funding pool. The tribute rates are
+    // private so they always pass the range checks of `new`.
+    hatch_tribute_bps: u16,
+    // Share of each buy sent to the funding pool once open
+    entry_tribute_bps: u16,
+    // Share of each sale's refund sent to the funding pool
+    exit_tribute_bps: u16,
+    phase: AugmentedPhase,
+    token_supply: T,
+    // Reserve backing the tokens, paid out on sales
+    reserve_pool: T,
+    // Tributes collected for the project, never paid out on sales
+    funding_pool: T,
+}
+
+/*
+* A Commons-style augmented bonding curve:
+*
+* Hatch:  tokens = R / p0, with R * θ to the funding pool and the rest to the reserve pool
+* Open:   Bancor over the reserve pool, P = reserve_pool / (token_supply * cw)
+*
+* where:
+* p0 is the hatch_price,
+* θ is the hatch tribute,
+* and cw is the connector_weight.
+*
+* Opening the curve starts the Bancor curve from the hatch's supply and reserve pool, at a
+* price of (1 - θ) * p0 / cw. Once open, the entry tribute is taken from the reserve paid
+* in before it reaches the curve, and the exit tribute from the reserve a sale returns. A
+* sale never pays out of the funding pool.
+*/
+impl<T: Fixed> Augmented<T> {
+    pub fn new(
+        hatch_price: f64,
+        connector_weight: f64,
+        hatch_tribute_bps: u16,
+        entry_tribute_bps: u16,
+        exit_tribute_bps: u16,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(
+            fixed_parameter("hatch_price", hatch_price)?,
+            fixed_parameter("connector_weight", connector_weight)?,
+            hatch_tribute_bps,
+            entry_tribute_bps,
+            exit_tribute_bps,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(
+        hatch_price: T,
+        connector_weight: T,
+        hatch_tribute_bps: u16,
+        entry_tribute_bps: u16,
+        exit_tribute_bps: u16,
+    ) -> Result<Self, BondingCurveError> {
+        let hatch_price = check_positive("hatch_price", hatch_price)?;
+        let connector_weight = check_connector_weight(connector_weight)?;
+        for (name, bps) in [
+            ("hatch_tribute_bps", hatch_tribute_bps),
+            ("entry_tribute_bps", entry_tribute_bps),
+            ("exit_tribute_bps", exit_tribute_bps),
+        ] {
+            if bps >= BPS_DENOMINATOR {
+                return Err(BondingCurveError::invalid_parameter(name, f64::from(bps)));
+            }
+        }
+        Ok(Augmented {
+            hatch_price,
+            connector_weight,
+            hatch_tribute_bps,
+            entry_tribute_bps,
+            exit_tribute_bps,
+            phase: AugmentedPhase::Hatch,
+            token_supply: T::ZERO,
+            reserve_pool: T::ZERO,
+            funding_pool: T::ZERO,
+        })
+    }
+
+    // Rebuilds a curve from stored state, re-running the checks of `new` and requiring
+    // non-negative balances. An open curve's supply and reserve pool must also form a
+    // valid Bancor pool, since it trades on one.
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn restore(
+        hatch_price: T,
+        connector_weight: T,
+        hatch_tribute_bps: u16,
+        entry_tribute_bps: u16,
+        exit_tribute_bps: u16,
+        phase: AugmentedPhase,
+        token_supply: T,
+        reserve_pool: T,
+        funding_pool: T,
+    ) -> Result<Self, BondingCurveError> {
+        let mut curve = Self::from_parameters(
+            hatch_price,
+            connector_weight,
+            hatch_tribute_bps,
+            entry_tribute_bps,
+            exit_tribute_bps,
+        )?;
+        curve.token_supply = check_non_negative("token_supply", token_supply)?;
+        curve.reserve_pool = check_non_negative("reserve_pool", reserve_pool)?;
+        curve.funding_pool = check_non_negative("funding_pool", funding_pool)?;
+        curve.phase = phase;
+        if phase == AugmentedPhase::Open {
+            Bancor::restore(reserve_pool, token_supply, connector_weight).map_err(|error| {
+                match error {
+                    BondingCurveError::InvalidParameter { name, value }
+                        if name == "reserve_balance" =>
+                    {
+                        BondingCurveError::invalid_parameter("reserve_pool", value)
+                    }
+                    error => error,
+                }
+            })?;
+        }
+        Ok(curve)
+    }
+
+    pub fn hatch_tribute_bps(&self) -> u16 {
+        self.hatch_tribute_bps
+    }
+
+    pub fn entry_tribute_bps(&self) -> u16 {
+        self.entry_tribute_bps
+    }
+
+    pub fn exit_tribute_bps(&self) -> u16 {
+        self.exit_tribute_bps
+    }
+
+    pub fn phase(&self) -> AugmentedPhase {
+        self.phase
+    }
+
+    // Reserve backing the tokens
+    pub fn reserve_pool(&self) -> T {
+        self.reserve_pool
+    }
+
+    // Tributes collected and not yet withdrawn
+    pub fn funding_pool(&self) -> T {
+        self.funding_pool
+    }
+
+    // Pays out and resets the funding pool
+    pub fn withdraw_funding(&mut self) -> T {
+        std::mem::take(&mut self.funding_pool)
+    }
+
+    // Ends the hatch and opens trading on the Bancor curve. Fails if the hatch sold no
+    // tokens, since the curve's price is set by the supply and reserve it raised.
+    pub fn open(&mut self) -> Result<(), BondingCurveError> {
+        self.require(AugmentedPhase::Hatch, "open")?;
+        check_positive("token_supply", self.token_supply)?;
+        self.phase = AugmentedPhase::Open;
+        Ok(())
+    }
+
+    fn require(
+        &self,
+        phase: AugmentedPhase,
+        operation: &'static str,
+    ) -> Result<(), BondingCurveError> {
+        if self.phase != phase {
+            return Err(BondingCurveError::WrongPhase {
+                operation,
+                phase: self.phase.name(),
+            });
+        }
+        Ok(())
+    }
+
+    // The Bancor curve over the reserve pool
+    fn bancor(&self) -> Bancor<T> {
+        Bancor {
+            reserve_balance: self.reserve_pool,
+            token_supply: self.token_supply,
+            connector_weight: self.connector_weight,
+        }
+    }
+
+    // Cost of `tokens` at the hatch price, rounded up
+    fn hatch_cost(&self, tokens: T) -> Result<T, BondingCurveError> {
+        let cost = tokens.mul_checked(self.hatch_price, "hatch cost")?;
+        Rounding::Up.apply(cost, T::DELTA)
+    }
+
+    // Tokens and tribute for a reserve amount paid in: the tribute is taken first and the
+    // rest buys tokens at the hatch price or on the curve
+    fn plan_buy_with_reserve(&self, reserve_amount: T) -> Result<(T, T), BondingCurveError> {
+        if reserve_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        match self.phase {
+            AugmentedPhase::Hatch => {
+                let tokens = reserve_amount.div_checked(self.hatch_price, "hatch return")?;
+                let tribute = fee_for(reserve_amount, self.hatch_tribute_bps)?;
+                Ok((tokens, tribute))
+            }
+            AugmentedPhase::Open => {
+                let tribute = fee_for(reserve_amount, self.entry_tribute_bps)?;
+                let tokens = self.bancor().quote_buy(reserve_amount - tribute)?;
+                Ok((tokens, tribute))
+            }
+        }
+    }
+
+    // Reserve to pay and tribute for `token_amount` tokens
+    fn plan_buy_exact_tokens(&self, token_amount: T) -> Result<(T, T), BondingCurveError> {
+        if token_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        match self.phase {
+            AugmentedPhase::Hatch => {
+                let cost = self.hatch_cost(token_amount)?;
+                Ok((cost, fee_for(cost, self.hatch_tribute_bps)?))
+            }
+            AugmentedPhase::Open => {
+                let cost = self.bancor().quote_buy_exact_tokens(token_amount)?;
+                let gross = gross_up(cost, self.entry_tribute_bps)?;
+                Ok((gross, gross - cost))
+            }
+        }
+    }
+
+    // Refund and tribute for selling `token_amount` tokens on the open curve
+    fn plan_sell(&self, token_amount: T) -> Result<(T, T), BondingCurveError> {
+        self.require(AugmentedPhase::Open, "sell")?;
+        let refund = self.bancor().quote_sell(token_amount)?;
+        self.check_sellable(token_amount)?;
+        Ok((refund, fee_for(refund, self.exit_tribute_bps)?))
+    }
+
+    // Refuses sales of the whole supply. Bancor prices an empty pool at its own initial
+    // price, so a sold-out curve would reopen far below the hatch price.
+    fn check_sellable(&self, token_amount: T) -> Result<(), BondingCurveError> {
+        if token_amount >= self.token_supply {
+            return Err(BondingCurveError::insufficient_supply(
+                token_amount,
+                self.token_supply - T::DELTA,
+            ));
+        }
+        Ok(())
+    }
+
+    // Adds issued tokens, the reserve that backs them and the tribute, leaving the curve
+    // unchanged on overflow
+    fn issue(&mut self, tokens: T, reserve: T, tribute: T) -> Result<(), BondingCurveError> {
+        let token_supply = self.token_supply.add_checked(tokens, "supply")?;
+        let reserve_pool = self.reserve_pool.add_checked(reserve, "reserve")?;
+        let funding_pool = self.funding_pool.add_checked(tribute, "funding")?;
+        self.token_supply = token_supply;
+        self.reserve_pool = reserve_pool;
+        self.funding_pool = funding_pool;
+        Ok(())
+    }
+
+    // Removes sold tokens and the refund they withdraw from the reserve pool, of which
+    // `tribute` moves to the funding pool
+    fn redeem(&mut self, tokens: T, refund: T, tribute: T) -> Result<(), BondingCurveError> {
+        let funding_pool = self.funding_pool.add_checked(tribute, "funding")?;
+        self.token_supply -= tokens;
+        self.reserve_pool -= refund;
+        self.funding_pool = funding_pool;
+        Ok(())
+    }
+}
+
+impl<T: Fixed> BondingCurve<T> for Augmented<T> {
+    fn get_price(&self) -> Result<T, BondingCurveError> {
+        match self.phase {
+            AugmentedPhase::Hatch => Ok(self.hatch_price),
+            AugmentedPhase::Open => self.bancor().get_price(),
+        }
+    }
+
+    // The pure curve functions describe the hatch's flat price or the open Bancor curve,
+    // without tributes
+    fn price_at(&self, supply: T) -> Result<T, BondingCurveError> {
+        match self.phase {
+            AugmentedPhase::Hatch => {
+                check_domain(supply, "price")?;
+                Ok(self.hatch_price)
+            }
+            AugmentedPhase::Open => self.bancor().price_at(supply),
+        }
+    }
+
+    fn cost_between(&self, from: T, to: T) -> Result<T, BondingCurveError> {
+        match self.phase {
+            AugmentedPhase::Hatch => {
+                check_interval(from, to)?;
+                self.hatch_cost(to - from)
+            }
+            AugmentedPhase::Open => self.bancor().cost_between(from, to),
+        }
+    }
+
+    fn supply_for_reserve(&self, reserve: T) -> Result<T, BondingCurveError> {
+        match self.phase {
+            AugmentedPhase::Hatch => check_domain(reserve, "supply_for_reserve")?
+                .div_checked(self.hatch_price, "supply_for_reserve"),
+            AugmentedPhase::Open => self.bancor().supply_for_reserve(reserve),
+        }
+    }
+
+    fn quote_buy(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.quote_buy_with_reserve(reserve_amount)
+    }
+
+    // Reserve the trader receives after the exit tribute
+    fn quote_sell(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        let (refund, tribute) = self.plan_sell(token_amount)?;
+        Ok(refund - tribute)
+    }
+
+    fn quote_buy_with_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_buy_with_reserve(reserve_amount)?.0)
+    }
+
+    fn quote_buy_exact_tokens(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_buy_exact_tokens(token_amount)?.0)
+    }
+
+    // Tokens to sell so the trader receives `reserve_amount` after the exit tribute
+    fn quote_sell_for_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.require(AugmentedPhase::Open, "sell")?;
+        if reserve_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let gross = gross_up(reserve_amount, self.exit_tribute_bps)?;
+        let tokens = self.bancor().quote_sell_for_reserve(gross)?;
+        self.check_sellable(tokens)?;
+        Ok(tokens)
+    }
+
+    fn buy_token(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.buy_with_reserve(reserve_amount)
+    }
+
+    fn buy_with_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let (tokens, tribute) = self.plan_buy_with_reserve(reserve_amount)?;
+        self.issue(tokens, reserve_amount - tribute, tribute)?;
+        Ok(tokens)
+    }
+
+    fn buy_exact_tokens(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        let (cost, tribute) = self.plan_buy_exact_tokens(token_amount)?;
+        self.issue(token_amount, cost - tribute, tribute)?;
+        Ok(cost)
+    }
+
+    fn sell_token(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        let (refund, tribute) = self.plan_sell(token_amount)?;
+        self.redeem(token_amount, refund, tribute)?;
+        Ok(refund - tribute)
+    }
+
+    fn sell_for_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let tokens = self.quote_sell_for_reserve(reserve_amount)?;
+        let gross = gross_up(reserve_amount, self.exit_tribute_bps)?;
+        self.redeem(tokens, gross, gross - reserve_amount)?;
+        Ok(tokens)
+    }
+
+    fn get_supply(&self) -> T {
+        self.token_supply
+    }
+
+    // The reserve pool only; the funding pool does not back the tokens
+    fn get_reserve(&self) -> Option<T> {
+        Some(self.reserve_pool)
+    }
+
+    fn buys_with_reserve(&self) -> bool {
+        true
+    }
+}
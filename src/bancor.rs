@@ -1,28 +1,64 @@
-use crate::bonding_curve_trait::BondingCurve;
+use crate::bonding_curve_trait::{BondingCurve, Rounding};
 use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_connector_weight, check_domain, check_interval, check_non_negative,
+    fixed_parameter, floor_supply, pow_fixed, ulps,
+};
+use fixed::traits::Fixed;
 use fixed::types::I64F64;
 
+// Price at which an empty pool, which has no reserve ratio yet, issues its first tokens
+const INITIAL_PRICE: f64 = 0.0001;
+
 #[derive(Clone, Debug)]
-pub struct Bancor {
-    pub reserve_balance: I64F64,
-    pub token_supply: I64F64,
-    pub connector_weight: I64F64,
+pub struct Bancor<T = I64F64> {
+    pub reserve_balance: T,
+    pub token_supply: T,
+    pub connector_weight: T,
 }
 
 /*
  *  P (price) = reserve_balance / (token_supply * connector_weight)
+ *
+ *  Purchase return: tokens = S * ((1 + ΔR / R)^cw - 1)
+ *  Purchase cost:   reserve = R * ((1 + ΔS / S)^(1 / cw) - 1)
+ *  Sale return:     reserve = R * (1 - (1 - ΔS / S)^(1 / cw))
+ *  Sale cost:       tokens = S * (1 - (1 - ΔR / R)^cw)
+ *
+ *  Each ratio is rounded in the pool's favor and the result is rounded by the error
+ *  bound of the power, following the `Rounding` policy.
+ *
+ *  Trades keep R proportional to S^(1 / cw), so the pure curve functions evaluate the
+ *  curve through the current state (R0, S0):
+ *    reserve at S:  R(S) = R0 * (S / S0)^(1 / cw)
+ *    price at S:    P(S) = R(S) / (S * cw) = R0 / (S0 * cw) * (S / S0)^(1 / cw - 1)
+ *    supply at R:   S(R) = S0 * (R / R0)^cw
+ *  An empty pool has no reserve ratio yet and is priced flat at INITIAL_PRICE.
 */
-impl Bancor {
+impl<T: Fixed> Bancor<T> {
     pub fn new(
         reserve_balance: i64,
         token_supply: i64,
         connector_weight: f64,
     ) -> Result<Self, BondingCurveError> {
-        if connector_weight <= 0.0 || connector_weight > 1.0 {
-            return Err(BondingCurveError::InvalidInput(
-                "Connector weight must be between 0 and 1".into(),
-            ));
-        }
+        let connector_weight = fixed_parameter("connector_weight", connector_weight)?;
+        let reserve_balance = T::checked_from_num(reserve_balance).ok_or_else(|| {
+            BondingCurveError::invalid_parameter("reserve_balance", reserve_balance as f64)
+        })?;
+        let token_supply = T::checked_from_num(token_supply).ok_or_else(|| {
+            BondingCurveError::invalid_parameter("token_supply", token_supply as f64)
+        })?;
+        Self::restore(reserve_balance, token_supply, connector_weight)
+    }
+
+    // Builds a pool from values already in the fixed-point type, applying the checks of
+    // `new`. Also used to rebuild a pool from stored state.
+    pub(crate) fn restore(
+        reserve_balance: T,
+        token_supply: T,
+        connector_weight: T,
+    ) -> Result<Self, BondingCurveError> {
+        check_connector_weight(connector_weight)?;
         /*
          * In the below checks we ensure that :-
          * No zero token supply and positive reserve balance
@@ -31,81 +67,286 @@ impl Bancor {
          * Token supply and reserve balance can be zero (eg. uninitialized pool)
          */
         if token_supply == 0 && reserve_balance != 0 {
-            return Err(BondingCurveError::InvalidInput(
-                "Cannot have reserve with zero token supply".into(),
+            return Err(BondingCurveError::invalid_parameter(
+                "reserve_balance",
+                reserve_balance.to_num(),
             ));
         }
 
         if reserve_balance == 0 && token_supply != 0 {
-            return Err(BondingCurveError::InvalidInput(
-                "Cannot have zero reserve with non-zero token supply".into(),
+            return Err(BondingCurveError::invalid_parameter(
+                "reserve_balance",
+                reserve_balance.to_num(),
             ));
         }
 
-        if reserve_balance < 0 || token_supply < 0 {
-            return Err(BondingCurveError::InvalidInput(
-                "Reserve and supply must be non-negative".into(),
-            ));
-        }
+        check_non_negative("reserve_balance", reserve_balance)?;
+        check_non_negative("token_supply", token_supply)?;
         Ok(Self {
-            reserve_balance: I64F64::from_num(reserve_balance),
-            token_supply: I64F64::from_num(token_supply),
-            connector_weight: I64F64::from_num(connector_weight),
+            reserve_balance,
+            token_supply,
+            connector_weight,
         })
     }
+
+    // Error bound of A * |g - 1| with g = b^e from `pow_fixed`. The power is within
+    // max(1, g) * (2e + 2) + 1 ulps, truncating 1 / cw moves it by at most g * b ulps, and
+    // the product with A truncates once.
+    fn power_error(scale: T, power: T, base: T, exponent: T) -> T {
+        let one = T::from_num(1);
+        let factor = exponent
+            .saturating_mul(T::from_num(2))
+            .saturating_add(T::from_num(2))
+            .saturating_add(base);
+        ulps(scale.saturating_mul(power.max(one)))
+            .saturating_mul(factor)
+            .saturating_add(ulps(scale.saturating_add(one)))
+    }
+
+    // Reserve backing `supply` on the pool's current curve, R0 * (S / S0)^(1 / cw), rounded
+    // in `rounding`'s direction: the ratio by one ulp and the result by `power_error`
+    fn reserve_at(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        if self.token_supply == T::ZERO {
+            let reserve = supply.mul_checked(T::from_num(INITIAL_PRICE), "reserve")?;
+            return Ok(rounding.apply(reserve, T::DELTA)?.max(T::ZERO));
+        }
+        let one = T::from_num(1);
+        let exponent = one / self.connector_weight;
+        let mut base = supply.div_checked(self.token_supply, "reserve")?;
+        if rounding == Rounding::Up {
+            base = base.add_checked(T::DELTA, "reserve")?;
+        }
+        let growth = pow_fixed(base, exponent)?;
+        let reserve = self.reserve_balance.mul_checked(growth, "reserve")?;
+        let error = Self::power_error(self.reserve_balance, growth, base, exponent);
+        Ok(rounding.apply(reserve, error)?.max(T::ZERO))
+    }
+
+    // Closed-form estimate of the supply backed by `reserve`, S0 * (R / R0)^cw
+    fn supply_at_reserve(&self, reserve: T) -> Result<T, BondingCurveError> {
+        if self.token_supply == T::ZERO {
+            return reserve.div_checked(T::from_num(INITIAL_PRICE), "supply");
+        }
+        let ratio = reserve.div_checked(self.reserve_balance, "supply")?;
+        let growth = pow_fixed(ratio, self.connector_weight)?;
+        self.token_supply.mul_checked(growth, "supply")
+    }
+
+    // Adds issued tokens and the reserve paid for them, leaving the pool unchanged on overflow
+    fn issue(&mut self, tokens: T, reserve: T) -> Result<(), BondingCurveError> {
+        let token_supply = self.token_supply.add_checked(tokens, "supply")?;
+        let reserve_balance = self.reserve_balance.add_checked(reserve, "reserve")?;
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
+        Ok(())
+    }
 }
 
-impl BondingCurve for Bancor {
-    fn get_price(&self) -> Result<I64F64, BondingCurveError> {
-        if self.token_supply == I64F64::from_num(0) {
-            return Ok(I64F64::from_num(0));
+impl<T: Fixed> BondingCurve<T> for Bancor<T> {
+    fn get_price(&self) -> Result<T, BondingCurveError> {
+        if self.token_supply == T::ZERO {
+            return Ok(T::ZERO);
         }
-        Ok(self.reserve_balance / (self.token_supply * self.connector_weight))
+        let weighted_supply = self
+            .token_supply
+            .mul_checked(self.connector_weight, "price")?;
+        self.reserve_balance.div_checked(weighted_supply, "price")
     }
 
-    fn buy_token(&mut self, reserve_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if reserve_amount <= I64F64::from_num(0) {
-            return Err(BondingCurveError::InvalidInput(
-                "Reserve amount must be positive".into(),
-            ));
+    fn price_at(&self, supply: T) -> Result<T, BondingCurveError> {
+        let supply = check_domain(supply, "price")?;
+        if self.token_supply == T::ZERO {
+            return Ok(T::from_num(INITIAL_PRICE));
         }
+        let one = T::from_num(1);
+        let ratio = supply.div_checked(self.token_supply, "price")?;
+        let growth = pow_fixed(ratio, one / self.connector_weight - one)?;
+        let weighted_supply = self
+            .token_supply
+            .mul_checked(self.connector_weight, "price")?;
+        self.reserve_balance
+            .div_checked(weighted_supply, "price")?
+            .mul_checked(growth, "price")
+    }
+
+    fn cost_between(&self, from: T, to: T) -> Result<T, BondingCurveError> {
+        check_interval(from, to)?;
+        let upper = self.reserve_at(to, Rounding::Up)?;
+        Ok(upper
+            .saturating_sub(self.reserve_at(from, Rounding::Down)?)
+            .max(T::ZERO))
+    }
 
-        let price = self.get_price()?;
-        if price == I64F64::from_num(0) && self.token_supply != I64F64::from_num(0) {
-            return Err(BondingCurveError::CalculationError(
-                "Invalid price calculation".into(),
+    fn supply_for_reserve(&self, reserve: T) -> Result<T, BondingCurveError> {
+        let reserve = check_domain(reserve, "supply_for_reserve")?;
+        floor_supply(
+            T::ZERO,
+            reserve,
+            || self.supply_at_reserve(reserve),
+            |supply| self.reserve_at(supply, Rounding::Up),
+        )
+    }
+
+    fn quote_buy(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        if reserve_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+
+        if self.token_supply == T::ZERO {
+            return reserve_amount.div_checked(T::from_num(INITIAL_PRICE), "purchase return");
+        }
+
+        // tokens = S * ((1 + ΔR / R)^cw - 1), with ΔR / R truncated down
+        let one = T::from_num(1);
+        let ratio = reserve_amount.div_checked(self.reserve_balance, "purchase return")?;
+        let base = one.add_checked(ratio, "purchase return")?;
+        let growth = pow_fixed(base, self.connector_weight)?;
+        let tokens = self
+            .token_supply
+            .mul_checked(growth.saturating_sub(one), "purchase return")?;
+        let error = Self::power_error(self.token_supply, growth, base, self.connector_weight);
+        let tokens = Rounding::Down.apply(tokens, error)?;
+        // A dust payment that buys nothing after rounding is refused rather than kept
+        if tokens <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        Ok(tokens)
+    }
+
+    fn quote_sell(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        if token_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        if token_amount > self.token_supply {
+            return Err(BondingCurveError::insufficient_supply(
+                token_amount,
+                self.token_supply,
             ));
         }
+        // Selling the whole supply pays out the whole reserve, so the pool returns to the
+        // empty state instead of keeping rounding dust that `restore` would reject
+        if token_amount == self.token_supply {
+            return Ok(self.reserve_balance);
+        }
 
-        let tokens_issued = if self.token_supply == I64F64::from_num(0) {
-            reserve_amount / I64F64::from_num(0.0001)
-        } else {
-            reserve_amount / price
-        };
+        // reserve = R * (1 - (1 - ΔS / S)^(1 / cw)), with ΔS / S truncated down
+        let one = T::from_num(1);
+        let exponent = one / self.connector_weight;
+        let ratio = token_amount.div_checked(self.token_supply, "sale return")?;
+        let base = one - ratio;
+        let remaining = pow_fixed(base, exponent)?;
+        let refund = self
+            .reserve_balance
+            .mul_checked(one.saturating_sub(remaining), "sale return")?;
+        let error = Self::power_error(self.reserve_balance, remaining, base, exponent);
+        Ok(Rounding::Down.apply(refund, error)?.max(T::ZERO))
+    }
 
-        self.reserve_balance += reserve_amount;
-        self.token_supply += tokens_issued;
-        Ok(tokens_issued)
+    fn quote_buy_with_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.quote_buy(reserve_amount)
     }
 
-    fn sell_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) || token_amount > self.token_supply {
-            return Err(BondingCurveError::InvalidInput(
-                "Invalid token amount".into(),
+    fn quote_buy_exact_tokens(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        if token_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+
+        if self.token_supply == T::ZERO {
+            let cost = token_amount.mul_checked(T::from_num(INITIAL_PRICE), "purchase cost")?;
+            return Rounding::Up.apply(cost, T::DELTA);
+        }
+
+        // reserve = R * ((1 + ΔS / S)^(1 / cw) - 1), with ΔS / S rounded up
+        let one = T::from_num(1);
+        let exponent = one / self.connector_weight;
+        let ratio = token_amount.div_checked(self.token_supply, "purchase cost")?;
+        let base = one
+            .add_checked(ratio, "purchase cost")?
+            .add_checked(T::DELTA, "purchase cost")?;
+        let growth = pow_fixed(base, exponent)?;
+        let cost = self
+            .reserve_balance
+            .mul_checked(growth.saturating_sub(one), "purchase cost")?;
+        let error = Self::power_error(self.reserve_balance, growth, base, exponent);
+        Rounding::Up.apply(cost, error)
+    }
+
+    fn quote_sell_for_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        if reserve_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        if reserve_amount > self.reserve_balance {
+            return Err(BondingCurveError::insufficient_reserve(
+                reserve_amount,
+                self.reserve_balance,
             ));
         }
-        let price = self.get_price()?;
-        let reserve_received = token_amount * price;
+
+        // tokens = S * (1 - (1 - ΔR / R)^cw), with ΔR / R rounded up
+        let one = T::from_num(1);
+        let ratio = reserve_amount.div_checked(self.reserve_balance, "sale cost")?;
+        let base = one
+            .saturating_sub(ratio)
+            .saturating_sub(T::DELTA)
+            .max(T::ZERO);
+        let remaining = pow_fixed(base, self.connector_weight)?;
+        let tokens = self
+            .token_supply
+            .mul_checked(one.saturating_sub(remaining), "sale cost")?;
+        let error = Self::power_error(self.token_supply, remaining, base, self.connector_weight);
+        let tokens = Rounding::Up
+            .apply(tokens, error)
+            .unwrap_or(self.token_supply);
+        // Only a request for the whole reserve may take the whole supply: any less would
+        // leave reserve dust behind a zero supply, a state `restore` rejects
+        if tokens >= self.token_supply && reserve_amount < self.reserve_balance {
+            return Err(BondingCurveError::insufficient_supply(
+                tokens,
+                self.token_supply,
+            ));
+        }
+        Ok(tokens.min(self.token_supply))
+    }
+
+    fn buy_token(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let tokens_issued = self.quote_buy(reserve_amount)?;
+        self.issue(tokens_issued, reserve_amount)?;
+        Ok(tokens_issued)
+    }
+
+    fn buy_with_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.buy_token(reserve_amount)
+    }
+
+    fn buy_exact_tokens(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        let cost = self.quote_buy_exact_tokens(token_amount)?;
+        self.issue(token_amount, cost)?;
+        Ok(cost)
+    }
+
+    fn sell_token(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        let reserve_received = self.quote_sell(token_amount)?;
         self.token_supply -= token_amount;
         self.reserve_balance -= reserve_received;
         Ok(reserve_received)
     }
 
-    fn get_supply(&self) -> I64F64 {
+    fn sell_for_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let tokens = self.quote_sell_for_reserve(reserve_amount)?;
+        self.token_supply -= tokens;
+        self.reserve_balance -= reserve_amount;
+        Ok(tokens)
+    }
+
+    fn get_supply(&self) -> T {
         self.token_supply
     }
 
-    fn get_reserve(&self) -> Option<I64F64> {
+    fn get_reserve(&self) -> Option<T> {
         Some(self.reserve_balance)
     }
+
+    fn buys_with_reserve(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,369 @@
+use crate::bonding_curve_trait::BondingCurve;
+use crate::errors::BondingCurveError;
+use crate::{
+    AffineLinear, AnyCurve, Augmented, AugmentedPhase, Bancor, ConstantProduct, Exponential,
+    LaunchCurve, Linear, Logarithmic, NaturalExponential, OffsetPower, Polynomial, Sigmoid,
+};
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use fixed::traits::{Fixed, FixedSigned};
+use std::mem::size_of;
+
+/*
+* Binary layout of a curve, as written by `BorshSerialize`:
+*
+*   byte 0      the curve's DISCRIMINATOR
+*   byte 1      LAYOUT_VERSION
+*   bytes 2..   each field as the little-endian bits of the fixed-point type, in the
+*               order of the curve's `restore` arguments
+*
+* Every field has the width of the fixed-point type, so the layout has the constant size
+* ENCODED_SIZE for a given curve and type. `Polynomial` writes its coefficients padded with
+* zeros to MAX_DEGREE + 1 fields, followed by its supply and reserve. `Augmented` writes its
+* tribute rates as little-endian u16 basis points and its phase as one byte, 0 for the
+* hatch and 1 once open. A new layout gets a new LAYOUT_VERSION rather
+* than changing the meaning of an existing one.
+*/
+
+// Version of the layout written by this release
+pub const LAYOUT_VERSION: u8 = 1;
+
+// Bytes preceding the fields: the discriminator and the layout version
+const HEADER_SIZE: usize = 2;
+
+// Widest fixed-point type supported by `fixed`, 128 bits
+const MAX_FIXED_SIZE: usize = 16;
+
+fn write_fixed<T: Fixed, W: Write>(value: T, writer: &mut W) -> Result<()> {
+    let mut buffer = [0u8; MAX_FIXED_SIZE];
+    let bytes = &mut buffer[..size_of::<T>()];
+    bytes.copy_from_slice(bytemuck::bytes_of(&value));
+    if cfg!(target_endian = "big") {
+        bytes.reverse();
+    }
+    writer.write_all(bytes)
+}
+
+fn read_fixed<T: Fixed, R: Read>(reader: &mut R) -> Result<T> {
+    let mut buffer = [0u8; MAX_FIXED_SIZE];
+    let bytes = &mut buffer[..size_of::<T>()];
+    reader.read_exact(bytes)?;
+    if cfg!(target_endian = "big") {
+        bytes.reverse();
+    }
+    Ok(bytemuck::pod_read_unaligned(bytes))
+}
+
+// Reads the header, checks that its layout version is one this release reads and returns
+// the discriminator
+fn read_header<R: Read>(reader: &mut R, curve: &str) -> Result<u8> {
+    let mut header = [0u8; HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+    if header[1] != LAYOUT_VERSION {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("unsupported {curve} layout version {}", header[1]),
+        ));
+    }
+    Ok(header[0])
+}
+
+fn invalid_state(error: BondingCurveError) -> Error {
+    Error::new(ErrorKind::InvalidData, error.to_string())
+}
+
+// Implements BorshSerialize and BorshDeserialize for a curve with the layout above and
+// adds its DISCRIMINATOR and ENCODED_SIZE constants. Deserialization rebuilds the curve
+// with `restore`, so stored state goes through the same checks as `new`.
+macro_rules! curve_borsh {
+    ($curve:ident, $discriminator:literal, $bound:path, [$($field:ident),+]) => {
+        impl<T: $bound> $curve<T> {
+            pub const DISCRIMINATOR: u8 = $discriminator;
+            pub const ENCODED_SIZE: usize =
+                HEADER_SIZE + [$(stringify!($field)),+].len() * size_of::<T>();
+
+            // Reads the fields that follow the header
+            fn read_fields<R: Read>(reader: &mut R) -> Result<Self> {
+                $(let $field = read_fixed(reader)?;)+
+                $curve::restore($($field),+).map_err(invalid_state)
+            }
+        }
+
+        impl<T: $bound> BorshSerialize for $curve<T> {
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                writer.write_all(&[$discriminator, LAYOUT_VERSION])?;
+                $(write_fixed(self.$field, writer)?;)+
+                Ok(())
+            }
+        }
+
+        impl<T: $bound> BorshDeserialize for $curve<T> {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                let discriminator = read_header(reader, stringify!($curve))?;
+                if discriminator != $discriminator {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "expected {} discriminator {}, found {discriminator}",
+                            stringify!($curve),
+                            $discriminator
+                        ),
+                    ));
+                }
+                Self::read_fields(reader)
+            }
+        }
+    };
+}
+
+curve_borsh!(Linear, 0, Fixed, [slope, token_supply, reserve_balance]);
+curve_borsh!(
+    Exponential,
+    1,
+    Fixed,
+    [coefficient, exponent, token_supply, reserve_balance]
+);
+curve_borsh!(
+    Logarithmic,
+    2,
+    FixedSigned,
+    [coefficient, constant, token_supply, reserve_balance]
+);
+curve_borsh!(
+    Sigmoid,
+    3,
+    FixedSigned,
+    [
+        max_price,
+        steepness,
+        midpoint,
+        token_supply,
+        reserve_balance
+    ]
+);
+curve_borsh!(
+    Bancor,
+    4,
+    Fixed,
+    [reserve_balance, token_supply, connector_weight]
+);
+curve_borsh!(
+    NaturalExponential,
+    5,
+    Fixed,
+    [initial_price, growth_rate, token_supply, reserve_balance]
+);
+curve_borsh!(
+    AffineLinear,
+    6,
+    Fixed,
+    [slope, base_price, token_supply, reserve_balance]
+);
+curve_borsh!(
+    OffsetPower,
+    7,
+    Fixed,
+    [
+        coefficient,
+        exponent,
+        base_price,
+        token_supply,
+        reserve_balance
+    ]
+);
+curve_borsh!(
+    ConstantProduct,
+    9,
+    Fixed,
+    [
+        token_balance,
+        virtual_reserve,
+        token_supply,
+        reserve_balance
+    ]
+);
+curve_borsh!(
+    LaunchCurve,
+    10,
+    Fixed,
+    [
+        virtual_token_reserve,
+        virtual_reserve,
+        real_token_reserve,
+        total_supply,
+        token_supply,
+        reserve_balance
+    ]
+);
+
+impl<T: Fixed> Polynomial<T> {
+    pub const DISCRIMINATOR: u8 = 8;
+    pub const ENCODED_SIZE: usize = HEADER_SIZE + (Self::MAX_DEGREE + 3) * size_of::<T>();
+
+    // Reads the fields that follow the header. `restore` drops the padding zeros.
+    fn read_fields<R: Read>(reader: &mut R) -> Result<Self> {
+        let coefficients = (0..=Self::MAX_DEGREE)
+            .map(|_| read_fixed(reader))
+            .collect::<Result<Vec<T>>>()?;
+        let token_supply = read_fixed(reader)?;
+        let reserve_balance = read_fixed(reader)?;
+        Polynomial::restore(coefficients, token_supply, reserve_balance).map_err(invalid_state)
+    }
+}
+
+impl<T: Fixed> BorshSerialize for Polynomial<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&[Self::DISCRIMINATOR, LAYOUT_VERSION])?;
+        for index in 0..=Self::MAX_DEGREE {
+            let coefficient = self.coefficients.get(index).copied().unwrap_or(T::ZERO);
+            write_fixed(coefficient, writer)?;
+        }
+        write_fixed(self.token_supply, writer)?;
+        write_fixed(self.reserve_balance, writer)
+    }
+}
+
+impl<T: Fixed> BorshDeserialize for Polynomial<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let discriminator = read_header(reader, "Polynomial")?;
+        if discriminator != Self::DISCRIMINATOR {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "expected Polynomial discriminator {}, found {discriminator}",
+                    Self::DISCRIMINATOR
+                ),
+            ));
+        }
+        Self::read_fields(reader)
+    }
+}
+
+impl<T: Fixed> Augmented<T> {
+    pub const DISCRIMINATOR: u8 = 11;
+    pub const ENCODED_SIZE: usize =
+        HEADER_SIZE + 5 * size_of::<T>() + 3 * size_of::<u16>() + size_of::<u8>();
+
+    // Reads the fields that follow the header
+    fn read_fields<R: Read>(reader: &mut R) -> Result<Self> {
+        let hatch_price = read_fixed(reader)?;
+        let connector_weight = read_fixed(reader)?;
+        let hatch_tribute_bps = u16::deserialize_reader(reader)?;
+        let entry_tribute_bps = u16::deserialize_reader(reader)?;
+        let exit_tribute_bps = u16::deserialize_reader(reader)?;
+        let phase = match u8::deserialize_reader(reader)? {
+            0 => AugmentedPhase::Hatch,
+            1 => AugmentedPhase::Open,
+            phase => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unknown Augmented phase {phase}"),
+                ));
+            }
+        };
+        let token_supply = read_fixed(reader)?;
+        let reserve_pool = read_fixed(reader)?;
+        let funding_pool = read_fixed(reader)?;
+        Augmented::restore(
+            hatch_price,
+            connector_weight,
+            hatch_tribute_bps,
+            entry_tribute_bps,
+            exit_tribute_bps,
+            phase,
+            token_supply,
+            reserve_pool,
+            funding_pool,
+        )
+        .map_err(invalid_state)
+    }
+}
+
+impl<T: Fixed> BorshSerialize for Augmented<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&[Self::DISCRIMINATOR, LAYOUT_VERSION])?;
+        write_fixed(self.hatch_price, writer)?;
+        write_fixed(self.connector_weight, writer)?;
+        self.hatch_tribute_bps().serialize(writer)?;
+        self.entry_tribute_bps().serialize(writer)?;
+        self.exit_tribute_bps().serialize(writer)?;
+        let phase: u8 = match self.phase() {
+            AugmentedPhase::Hatch => 0,
+            AugmentedPhase::Open => 1,
+        };
+        phase.serialize(writer)?;
+        write_fixed(self.get_supply(), writer)?;
+        write_fixed(self.reserve_pool(), writer)?;
+        write_fixed(self.funding_pool(), writer)
+    }
+}
+
+impl<T: Fixed> BorshDeserialize for Augmented<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let discriminator = read_header(reader, "Augmented")?;
+        if discriminator != Self::DISCRIMINATOR {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "expected Augmented discriminator {}, found {discriminator}",
+                    Self::DISCRIMINATOR
+                ),
+            ));
+        }
+        Self::read_fields(reader)
+    }
+}
+
+// An `AnyCurve` is written as the wrapped curve, whose discriminator selects the variant
+// on reading. The size depends on the variant, up to MAX_ENCODED_SIZE, the size of the
+// largest curve: `Polynomial`, unless `T` is narrower than `Augmented`'s u16 fields.
+impl<T: FixedSigned> AnyCurve<T> {
+    pub const MAX_ENCODED_SIZE: usize =
+        if Polynomial::<T>::ENCODED_SIZE >= Augmented::<T>::ENCODED_SIZE {
+            Polynomial::<T>::ENCODED_SIZE
+        } else {
+            Augmented::<T>::ENCODED_SIZE
+        };
+}
+
+impl<T: FixedSigned> BorshSerialize for AnyCurve<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        match self {
+            AnyCurve::Linear(curve) => curve.serialize(writer),
+            AnyCurve::Exponential(curve) => curve.serialize(writer),
+            AnyCurve::Logarithmic(curve) => curve.serialize(writer),
+            AnyCurve::Sigmoid(curve) => curve.serialize(writer),
+            AnyCurve::Bancor(curve) => curve.serialize(writer),
+            AnyCurve::NaturalExponential(curve) => curve.serialize(writer),
+            AnyCurve::AffineLinear(curve) => curve.serialize(writer),
+            AnyCurve::OffsetPower(curve) => curve.serialize(writer),
+            AnyCurve::Polynomial(curve) => curve.serialize(writer),
+            AnyCurve::ConstantProduct(curve) => curve.serialize(writer),
+            AnyCurve::LaunchCurve(curve) => curve.serialize(writer),
+            AnyCurve::Augmented(curve) => curve.serialize(writer),
+        }
+    }
+}
+
+impl<T: FixedSigned> BorshDeserialize for AnyCurve<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        match read_header(reader, "AnyCurve")? {
+            0 => Linear::read_fields(reader).map(AnyCurve::Linear),
+            1 => Exponential::read_fields(reader).map(AnyCurve::Exponential),
+            2 => Logarithmic::read_fields(reader).map(AnyCurve::Logarithmic),
+            3 => Sigmoid::read_fields(reader).map(AnyCurve::Sigmoid),
+            4 => Bancor::read_fields(reader).map(AnyCurve::Bancor),
+            5 => NaturalExponential::read_fields(reader).map(AnyCurve::NaturalExponential),
+            6 => AffineLinear::read_fields(reader).map(AnyCurve::AffineLinear),
+            7 => OffsetPower::read_fields(reader).map(AnyCurve::OffsetPower),
+            8 => Polynomial::read_fields(reader).map(AnyCurve::Polynomial),
+            9 => ConstantProduct::read_fields(reader).map(AnyCurve::ConstantProduct),
+            10 => LaunchCurve::read_fields(reader).map(AnyCurve::LaunchCurve),
+            11 => Augmented::read_fields(reader).map(AnyCurve::Augmented),
+            discriminator => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown curve discriminator {discriminator}"),
+            )),
+        }
+    }
+}
@@ -1,20 +1,156 @@
 use crate::errors::BondingCurveError;
+use fixed::traits::Fixed;
 use fixed::types::I64F64;
 
-// interface for all bonding curves
-pub trait BondingCurve {
+// Direction in which a curve rounds a quoted amount. Fixed-point evaluation of a curve is
+// only accurate to a few ulps, so every curve rounds by its error bound: amounts the
+// trader pays (buy costs, tokens sold for an exact reserve) round `Up` and amounts the
+// trader receives (tokens bought with a reserve, sale refunds) round `Down`. Each trade
+// is then at least as favorable to the pool as exact arithmetic, so no sequence of trades
+// can take out more reserve than it put in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Up,
+    Down,
+}
+
+impl Rounding {
+    // Moves `value` by `error` in this direction. Rounding down saturates at the type's
+    // minimum, rounding up reports an `Overflow`.
+    pub fn apply<T: Fixed>(self, value: T, error: T) -> Result<T, BondingCurveError> {
+        match self {
+            Rounding::Up => value.checked_add(error).ok_or(BondingCurveError::Overflow {
+                operation: "rounding",
+            }),
+            Rounding::Down => Ok(value.saturating_sub(error)),
+        }
+    }
+}
+
+// interface for all bonding curves, generic over the fixed-point type `T` used for
+// prices, supplies and reserves. Every quote follows the `Rounding` policy.
+pub trait BondingCurve<T: Fixed = I64F64> {
     // get the current price based on the curve's state
-    fn get_price(&self) -> Result<I64F64, BondingCurveError>;
+    fn get_price(&self) -> Result<T, BondingCurveError>;
+
+    // Price the curve would quote at `supply`, without changing its state. Fails with
+    // `DomainError` for a negative supply.
+    fn price_at(&self, supply: T) -> Result<T, BondingCurveError>;
+
+    // Reserve required to raise the supply from `from` to `to`: the integral of the price
+    // between them, rounded up like a buy. Fails with `DomainError` unless
+    // 0 <= from <= to.
+    fn cost_between(&self, from: T, to: T) -> Result<T, BondingCurveError>;
+
+    // Inverse of the integral: the largest supply whose cost from zero,
+    // `cost_between(0, supply)`, is covered by `reserve`, rounded down like a buy. Fails
+    // with `DomainError` for a negative reserve.
+    fn supply_for_reserve(&self, reserve: T) -> Result<T, BondingCurveError>;
+
+    // Quotes the result of `buy_token` without changing the curve's state
+    fn quote_buy(&self, amount: T) -> Result<T, BondingCurveError>;
+
+    // Quotes the result of `sell_token` without changing the curve's state
+    fn quote_sell(&self, token_amount: T) -> Result<T, BondingCurveError>;
+
+    // Quotes the tokens received for spending exactly `reserve_amount`
+    fn quote_buy_with_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError>;
+
+    // Quotes the reserve required to buy exactly `token_amount` tokens
+    fn quote_buy_exact_tokens(&self, token_amount: T) -> Result<T, BondingCurveError>;
+
+    // Quotes the tokens that must be sold to receive exactly `reserve_amount`
+    fn quote_sell_for_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError>;
 
     // Calculates tokens received for a given reserve amount
-    fn buy_token(&mut self, reserve_amount: I64F64) -> Result<I64F64, BondingCurveError>;
+    fn buy_token(&mut self, reserve_amount: T) -> Result<T, BondingCurveError>;
+
+    // Spends exactly `reserve_amount` and returns the tokens received
+    fn buy_with_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError>;
+
+    // Buys exactly `token_amount` tokens and returns the reserve paid
+    fn buy_exact_tokens(&mut self, token_amount: T) -> Result<T, BondingCurveError>;
 
     // Calculates reserve received for selling a given token amount
-    fn sell_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError>;
+    fn sell_token(&mut self, token_amount: T) -> Result<T, BondingCurveError>;
+
+    // Sells the tokens needed to receive exactly `reserve_amount` and returns the tokens sold
+    fn sell_for_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError>;
 
     // Return the total supply of tokens
-    fn get_supply(&self) -> I64F64;
+    fn get_supply(&self) -> T;
 
     // Return the current reserve of tokens
-    fn get_reserve(&self) -> Option<I64F64>;
+    fn get_reserve(&self) -> Option<T>;
+
+    // Largest supply up to which every quote is guaranteed to be representable in `T`.
+    // Beyond it trades may fail with `Overflow`; they never panic.
+    fn max_supply(&self) -> T {
+        T::MAX
+    }
+
+    // Whether `buy_token` takes a reserve amount (like `buy_with_reserve`) rather than
+    // a token amount (like `buy_exact_tokens`)
+    fn buys_with_reserve(&self) -> bool {
+        false
+    }
+
+    // Like `buy_with_reserve`, but fails without changing state if fewer than
+    // `min_tokens_out` tokens would be received
+    fn buy_with_reserve_min_out(
+        &mut self,
+        reserve_amount: T,
+        min_tokens_out: T,
+    ) -> Result<T, BondingCurveError> {
+        let tokens = self.quote_buy_with_reserve(reserve_amount)?;
+        if tokens < min_tokens_out {
+            return Err(BondingCurveError::slippage_exceeded(tokens, min_tokens_out));
+        }
+        self.buy_with_reserve(reserve_amount)
+    }
+
+    // Like `buy_exact_tokens`, but fails without changing state if more than
+    // `max_reserve_in` reserve would be paid
+    fn buy_exact_tokens_max_in(
+        &mut self,
+        token_amount: T,
+        max_reserve_in: T,
+    ) -> Result<T, BondingCurveError> {
+        let cost = self.quote_buy_exact_tokens(token_amount)?;
+        if cost > max_reserve_in {
+            return Err(BondingCurveError::slippage_exceeded(cost, max_reserve_in));
+        }
+        self.buy_exact_tokens(token_amount)
+    }
+
+    // Like `sell_token`, but fails without changing state if less than
+    // `min_reserve_out` reserve would be received
+    fn sell_token_min_out(
+        &mut self,
+        token_amount: T,
+        min_reserve_out: T,
+    ) -> Result<T, BondingCurveError> {
+        let refund = self.quote_sell(token_amount)?;
+        if refund < min_reserve_out {
+            return Err(BondingCurveError::slippage_exceeded(
+                refund,
+                min_reserve_out,
+            ));
+        }
+        self.sell_token(token_amount)
+    }
+
+    // Like `sell_for_reserve`, but fails without changing state if more than
+    // `max_tokens_in` tokens would be sold
+    fn sell_for_reserve_max_in(
+        &mut self,
+        reserve_amount: T,
+        max_tokens_in: T,
+    ) -> Result<T, BondingCurveError> {
+        let tokens = self.quote_sell_for_reserve(reserve_amount)?;
+        if tokens > max_tokens_in {
+            return Err(BondingCurveError::slippage_exceeded(tokens, max_tokens_in));
+        }
+        self.sell_for_reserve(reserve_amount)
+    }
 }
@@ -0,0 +1,405 @@
+use crate::any_curve::{AnyCurve, CurveKind};
+use crate::errors::BondingCurveError;
+use crate::{
+    AffineLinear, Augmented, Bancor, ConstantProduct, Exponential, LaunchCurve, Linear,
+    Logarithmic, NaturalExponential, OffsetPower, Polynomial, Sigmoid,
+};
+use fixed::traits::FixedSigned;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/*
+* A curve described by named parameters, as written in a deployment file:
+*
+*   kind = "sigmoid"
+*   max_price = 100.0
+*   steepness = 0.1
+*   midpoint = 50.0
+*   initial_supply = 40.0    # optional, defaults to 0
+*
+* `kind` selects the curve and the remaining keys are the arguments of its constructor.
+* Bancor takes `reserve_balance`, `token_supply` and `connector_weight`; the other
+* curves take the parameters of `new` plus an optional `initial_supply` for `with_supply`.
+* Polynomial takes its `coefficients` as a list of numbers, lowest power first.
+* ConstantProduct takes `token_balance` and `reserve_balance`, an optional
+* `virtual_reserve` and an optional `initial_supply` of tokens in circulation.
+* LaunchCurve takes `virtual_token_reserve`, `virtual_reserve`, `real_token_reserve` and
+* `total_supply`, plus an optional `initial_supply` of tokens already sold.
+* Augmented takes `hatch_price` and `connector_weight`, plus optional
+* `hatch_tribute_bps`, `entry_tribute_bps` and `exit_tribute_bps` in whole basis points,
+* and starts in the hatch phase.
+*
+* Every error names the offending field: missing, unknown or mistyped keys are reported
+* as `InvalidConfig` and out-of-range values as `InvalidParameter`, the same as from the
+* constructor.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum CurveConfig {
+    Linear {
+        slope: f64,
+        initial_supply: f64,
+    },
+    Exponential {
+        coefficient: f64,
+        exponent: f64,
+        initial_supply: f64,
+    },
+    Logarithmic {
+        coefficient: f64,
+        constant: f64,
+        initial_supply: f64,
+    },
+    Sigmoid {
+        max_price: f64,
+        steepness: f64,
+        midpoint: f64,
+        initial_supply: f64,
+    },
+    Bancor {
+        reserve_balance: i64,
+        token_supply: i64,
+        connector_weight: f64,
+    },
+    NaturalExponential {
+        initial_price: f64,
+        growth_rate: f64,
+        initial_supply: f64,
+    },
+    AffineLinear {
+        slope: f64,
+        base_price: f64,
+        initial_supply: f64,
+    },
+    OffsetPower {
+        coefficient: f64,
+        exponent: f64,
+        base_price: f64,
+        initial_supply: f64,
+    },
+    Polynomial {
+        coefficients: Vec<f64>,
+        initial_supply: f64,
+    },
+    ConstantProduct {
+        token_balance: f64,
+        reserve_balance: f64,
+        virtual_reserve: f64,
+        initial_supply: f64,
+    },
+    LaunchCurve {
+        virtual_token_reserve: f64,
+        virtual_reserve: f64,
+        real_token_reserve: f64,
+        total_supply: f64,
+        initial_supply: f64,
+    },
+    Augmented {
+        hatch_price: f64,
+        connector_weight: f64,
+        hatch_tribute_bps: u16,
+        entry_tribute_bps: u16,
+        exit_tribute_bps: u16,
+    },
+}
+
+// A value as written in a configuration file, before it is checked against the schema
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Integer(i64),
+    Number(f64),
+    Text(String),
+    Numbers(Vec<f64>),
+    Other(serde::de::IgnoredAny),
+}
+
+// The keys of a configuration that have not been consumed yet
+struct Fields(BTreeMap<String, Value>);
+
+impl Fields {
+    fn invalid(field: &str, message: &str) -> BondingCurveError {
+        BondingCurveError::InvalidConfig {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    fn kind(&mut self) -> Result<CurveKind, BondingCurveError> {
+        match self.0.remove("kind") {
+            Some(Value::Text(name)) => CurveKind::ALL
+                .into_iter()
+                .find(|kind| kind.name() == name)
+                .ok_or_else(|| Self::invalid("kind", &format!("unknown curve kind `{name}`"))),
+            Some(_) => Err(Self::invalid("kind", "expected a string")),
+            None => Err(Self::invalid("kind", "missing field")),
+        }
+    }
+
+    fn number(&mut self, field: &str) -> Result<Option<f64>, BondingCurveError> {
+        match self.0.remove(field) {
+            Some(Value::Integer(value)) => Ok(Some(value as f64)),
+            Some(Value::Number(value)) => Ok(Some(value)),
+            Some(_) => Err(Self::invalid(field, "expected a number")),
+            None => Ok(None),
+        }
+    }
+
+    fn required(&mut self, field: &str) -> Result<f64, BondingCurveError> {
+        self.number(field)?
+            .ok_or_else(|| Self::invalid(field, "missing field"))
+    }
+
+    fn optional(&mut self, field: &str) -> Result<f64, BondingCurveError> {
+        Ok(self.number(field)?.unwrap_or(0.0))
+    }
+
+    fn numbers(&mut self, field: &str) -> Result<Vec<f64>, BondingCurveError> {
+        match self.0.remove(field) {
+            Some(Value::Numbers(values)) => Ok(values),
+            Some(_) => Err(Self::invalid(field, "expected a list of numbers")),
+            None => Err(Self::invalid(field, "missing field")),
+        }
+    }
+
+    // A whole number within the range of i64, read without passing through a float
+    fn integer(&mut self, field: &str) -> Result<i64, BondingCurveError> {
+        match self.0.remove(field) {
+            Some(Value::Integer(value)) => Ok(value),
+            Some(_) => Err(Self::invalid(field, "expected an integer")),
+            None => Err(Self::invalid(field, "missing field")),
+        }
+    }
+
+    // A rate in basis points, defaulting to 0. Values outside u16 are reported as
+    // `InvalidParameter`, like rates the constructor rejects.
+    fn bps(&mut self, field: &str) -> Result<u16, BondingCurveError> {
+        match self.0.remove(field) {
+            Some(Value::Integer(value)) => u16::try_from(value)
+                .map_err(|_| BondingCurveError::invalid_parameter(field, value as f64)),
+            Some(_) => Err(Self::invalid(field, "expected an integer")),
+            None => Ok(0),
+        }
+    }
+
+    // Fails on the first key that the curve's schema did not consume
+    fn finish(self) -> Result<(), BondingCurveError> {
+        match self.0.into_keys().next() {
+            Some(field) => Err(Self::invalid(&field, "unknown field")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl CurveConfig {
+    fn from_fields(fields: BTreeMap<String, Value>) -> Result<Self, BondingCurveError> {
+        let mut fields = Fields(fields);
+        let config = match fields.kind()? {
+            CurveKind::Linear => CurveConfig::Linear {
+                slope: fields.required("slope")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::Exponential => CurveConfig::Exponential {
+                coefficient: fields.required("coefficient")?,
+                exponent: fields.required("exponent")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::Logarithmic => CurveConfig::Logarithmic {
+                coefficient: fields.required("coefficient")?,
+                constant: fields.required("constant")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::Sigmoid => CurveConfig::Sigmoid {
+                max_price: fields.required("max_price")?,
+                steepness: fields.required("steepness")?,
+                midpoint: fields.required("midpoint")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::Bancor => CurveConfig::Bancor {
+                reserve_balance: fields.integer("reserve_balance")?,
+                token_supply: fields.integer("token_supply")?,
+                connector_weight: fields.required("connector_weight")?,
+            },
+            CurveKind::NaturalExponential => CurveConfig::NaturalExponential {
+                initial_price: fields.required("initial_price")?,
+                growth_rate: fields.required("growth_rate")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::AffineLinear => CurveConfig::AffineLinear {
+                slope: fields.required("slope")?,
+                base_price: fields.required("base_price")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::OffsetPower => CurveConfig::OffsetPower {
+                coefficient: fields.required("coefficient")?,
+                exponent: fields.required("exponent")?,
+                base_price: fields.required("base_price")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::Polynomial => CurveConfig::Polynomial {
+                coefficients: fields.numbers("coefficients")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::ConstantProduct => CurveConfig::ConstantProduct {
+                token_balance: fields.required("token_balance")?,
+                reserve_balance: fields.required("reserve_balance")?,
+                virtual_reserve: fields.optional("virtual_reserve")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::LaunchCurve => CurveConfig::LaunchCurve {
+                virtual_token_reserve: fields.required("virtual_token_reserve")?,
+                virtual_reserve: fields.required("virtual_reserve")?,
+                real_token_reserve: fields.required("real_token_reserve")?,
+                total_supply: fields.required("total_supply")?,
+                initial_supply: fields.optional("initial_supply")?,
+            },
+            CurveKind::Augmented => CurveConfig::Augmented {
+                hatch_price: fields.required("hatch_price")?,
+                connector_weight: fields.required("connector_weight")?,
+                hatch_tribute_bps: fields.bps("hatch_tribute_bps")?,
+                entry_tribute_bps: fields.bps("entry_tribute_bps")?,
+                exit_tribute_bps: fields.bps("exit_tribute_bps")?,
+            },
+        };
+        fields.finish()?;
+        Ok(config)
+    }
+
+    // Parses a configuration from a TOML document
+    pub fn from_toml(text: &str) -> Result<Self, BondingCurveError> {
+        let fields = toml::from_str(text).map_err(|error| BondingCurveError::MalformedConfig {
+            message: error.message().to_string(),
+        })?;
+        Self::from_fields(fields)
+    }
+
+    // Parses a configuration from a JSON object
+    pub fn from_json(text: &str) -> Result<Self, BondingCurveError> {
+        let fields =
+            serde_json::from_str(text).map_err(|error| BondingCurveError::MalformedConfig {
+                message: error.to_string(),
+            })?;
+        Self::from_fields(fields)
+    }
+
+    pub fn kind(&self) -> CurveKind {
+        match self {
+            CurveConfig::Linear { .. } => CurveKind::Linear,
+            CurveConfig::Exponential { .. } => CurveKind::Exponential,
+            CurveConfig::Logarithmic { .. } => CurveKind::Logarithmic,
+            CurveConfig::Sigmoid { .. } => CurveKind::Sigmoid,
+            CurveConfig::Bancor { .. } => CurveKind::Bancor,
+            CurveConfig::NaturalExponential { .. } => CurveKind::NaturalExponential,
+            CurveConfig::AffineLinear { .. } => CurveKind::AffineLinear,
+            CurveConfig::OffsetPower { .. } => CurveKind::OffsetPower,
+            CurveConfig::Polynomial { .. } => CurveKind::Polynomial,
+            CurveConfig::ConstantProduct { .. } => CurveKind::ConstantProduct,
+            CurveConfig::LaunchCurve { .. } => CurveKind::LaunchCurve,
+            CurveConfig::Augmented { .. } => CurveKind::Augmented,
+        }
+    }
+
+    // Builds the configured curve through its constructor, so the configuration is held
+    // to the same validation as code
+    pub fn build<T: FixedSigned>(&self) -> Result<AnyCurve<T>, BondingCurveError> {
+        Ok(match *self {
+            CurveConfig::Linear {
+                slope,
+                initial_supply,
+            } => Linear::with_supply(slope, initial_supply)?.into(),
+            CurveConfig::Exponential {
+                coefficient,
+                exponent,
+                initial_supply,
+            } => Exponential::with_supply(coefficient, exponent, initial_supply)?.into(),
+            CurveConfig::Logarithmic {
+                coefficient,
+                constant,
+                initial_supply,
+            } => Logarithmic::with_supply(coefficient, constant, initial_supply)?.into(),
+            CurveConfig::Sigmoid {
+                max_price,
+                steepness,
+                midpoint,
+                initial_supply,
+            } => Sigmoid::with_supply(max_price, steepness, midpoint, initial_supply)?.into(),
+            CurveConfig::Bancor {
+                reserve_balance,
+                token_supply,
+                connector_weight,
+            } => Bancor::new(reserve_balance, token_supply, connector_weight)?.into(),
+            CurveConfig::NaturalExponential {
+                initial_price,
+                growth_rate,
+                initial_supply,
+            } => {
+                NaturalExponential::with_supply(initial_price, growth_rate, initial_supply)?.into()
+            }
+            CurveConfig::AffineLinear {
+                slope,
+                base_price,
+                initial_supply,
+            } => AffineLinear::with_supply(slope, base_price, initial_supply)?.into(),
+            CurveConfig::OffsetPower {
+                coefficient,
+                exponent,
+                base_price,
+                initial_supply,
+            } => {
+                OffsetPower::with_supply(coefficient, exponent, base_price, initial_supply)?.into()
+            }
+            CurveConfig::Polynomial {
+                ref coefficients,
+                initial_supply,
+            } => Polynomial::with_supply(coefficients, initial_supply)?.into(),
+            CurveConfig::ConstantProduct {
+                token_balance,
+                reserve_balance,
+                virtual_reserve,
+                initial_supply,
+            } => ConstantProduct::with_supply(
+                token_balance,
+                reserve_balance,
+                virtual_reserve,
+                initial_supply,
+            )?
+            .into(),
+            CurveConfig::LaunchCurve {
+                virtual_token_reserve,
+                virtual_reserve,
+                real_token_reserve,
+                total_supply,
+                initial_supply,
+            } => LaunchCurve::with_supply(
+                virtual_token_reserve,
+                virtual_reserve,
+                real_token_reserve,
+                total_supply,
+                initial_supply,
+            )?
+            .into(),
+            CurveConfig::Augmented {
+                hatch_price,
+                connector_weight,
+                hatch_tribute_bps,
+                entry_tribute_bps,
+                exit_tribute_bps,
+            } => Augmented::new(
+                hatch_price,
+                connector_weight,
+                hatch_tribute_bps,
+                entry_tribute_bps,
+                exit_tribute_bps,
+            )?
+            .into(),
+        })
+    }
+}
+
+// Reads a configuration embedded in a larger document, such as a `[curve]` table
+impl<'de> Deserialize<'de> for CurveConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = BTreeMap::deserialize(deserializer)?;
+        Self::from_fields(fields).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,217 @@
+use crate::bonding_curve_trait::Rounding;
+use crate::errors::BondingCurveError;
+#[cfg(any(feature = "serde", feature = "borsh"))]
+use crate::helpers::check_state;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_non_negative, check_positive, fixed_parameter,
+    non_negative_parameter, sqrt_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+#[derive(Clone, Debug)]
+pub struct ConstantProduct<T = I64F64> {
+    // Tokens held by the pool
+    pub token_balance: T,
+    // Reserve counted in the price but never paid out
+    pub virtual_reserve: T,
+    // Tokens held outside the pool, which may be sold into it
+    pub token_supply: T,
+    // Reserve held by the pool
+    pub reserve_balance: T,
+}
+
+/*
+* x * (y + v) = k
+* where:
+* x is the token_balance of the pool,
+* y is the reserve_balance of the pool,
+* v is the virtual reserve, an offset that prices the pool as if it held more reserve,
+* k is the invariant, preserved by every swap up to rounding in the pool's favor.
+*
+* The marginal price is (y + v) / x. Tokens leave the pool as the supply S rises, so with
+* X = S + x the tokens in circulation and in the pool together, the reserve side at
+* supply S is k / (X - S) and the price is k / (X - S)^2. Buying and selling move along
+* that curve, so swaps in either direction are its integral: taking Δx tokens out costs
+* (y + v) * Δx / (x - Δx), and putting them back pays (y + v) * Δx / (x + Δx), never
+* more than the real reserve y.
+*
+* The pool cannot run out of tokens: the supply is bounded by `max_supply`, where the
+* price reaches MAX / 8.
+*/
+
+impl<T: Fixed> ConstantProduct<T> {
+    pub fn new(
+        token_balance: f64,
+        reserve_balance: f64,
+        virtual_reserve: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(
+            fixed_parameter("token_balance", token_balance)?,
+            fixed_parameter("virtual_reserve", virtual_reserve)?,
+            T::ZERO,
+            fixed_parameter("reserve_balance", reserve_balance)?,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    pub(crate) fn from_parameters(
+        token_balance: T,
+        virtual_reserve: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        let curve = ConstantProduct {
+            token_balance: check_positive("token_balance", token_balance)?,
+            virtual_reserve: check_non_negative("virtual_reserve", virtual_reserve)?,
+            token_supply: check_non_negative("token_supply", token_supply)?,
+            reserve_balance: check_non_negative("reserve_balance", reserve_balance)?,
+        };
+        let effective_reserve = curve.effective_reserve().map_err(|_| {
+            BondingCurveError::invalid_parameter("virtual_reserve", virtual_reserve.to_num())
+        })?;
+        // A pool without any reserve, real or virtual, would price every token at zero
+        check_positive("virtual_reserve", effective_reserve)?;
+        curve.invariant().map_err(|_| {
+            BondingCurveError::invalid_parameter("token_balance", token_balance.to_num())
+        })?;
+        curve.total_tokens().map_err(|_| {
+            BondingCurveError::invalid_parameter("token_supply", token_supply.to_num())
+        })?;
+        Ok(curve)
+    }
+
+    // Rebuilds a curve from stored state, re-running the checks of `new` and requiring a
+    // non-negative supply within `max_supply` and a non-negative reserve
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        token_balance: T,
+        virtual_reserve: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        let curve = Self::from_parameters(
+            token_balance,
+            virtual_reserve,
+            token_supply,
+            reserve_balance,
+        )?;
+        check_state(curve.supply_limit(), token_supply, reserve_balance)?;
+        Ok(curve)
+    }
+
+    // Creates a pool whose `initial_supply` tokens are already in circulation and may be
+    // sold into it. Unlike the integral curves, the reserve is given rather than derived
+    // from the supply, since a pool can be seeded at any price.
+    pub fn with_supply(
+        token_balance: f64,
+        reserve_balance: f64,
+        virtual_reserve: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(
+            fixed_parameter("token_balance", token_balance)?,
+            fixed_parameter("virtual_reserve", virtual_reserve)?,
+            non_negative_parameter("initial_supply", initial_supply)?,
+            fixed_parameter("reserve_balance", reserve_balance)?,
+        )
+    }
+
+    // Reserve the price is computed from, y + v
+    pub fn effective_reserve(&self) -> Result<T, BondingCurveError> {
+        self.reserve_balance
+            .add_checked(self.virtual_reserve, "reserve")
+    }
+
+    // k = x * (y + v)
+    pub fn invariant(&self) -> Result<T, BondingCurveError> {
+        self.token_balance
+            .mul_checked(self.effective_reserve()?, "invariant")
+    }
+
+    // Tokens in circulation and in the pool together, X = S + x
+    fn total_tokens(&self) -> Result<T, BondingCurveError> {
+        self.token_supply.add_checked(self.token_balance, "supply")
+    }
+
+    // Tokens left in the pool at `supply`, X - S. The pool never gives out its last token:
+    // the reserve needed to reach X is unbounded, which is reported as an overflow.
+    fn pool_tokens_at(&self, supply: T, operation: &'static str) -> Result<T, BondingCurveError> {
+        let total = self.total_tokens()?;
+        if supply >= total {
+            return Err(BondingCurveError::Overflow { operation });
+        }
+        Ok(total - supply)
+    }
+}
+
+impl<T: Fixed> IntegralCurve for ConstantProduct<T> {
+    type Value = T;
+
+    // k / (X - S)^2
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        let pool_tokens = self.pool_tokens_at(check_domain(supply, "price")?, "price")?;
+        self.invariant()?
+            .div_checked(pool_tokens, "price")?
+            .div_checked(pool_tokens, "price")
+    }
+
+    // Reserve side of the pool at `supply`, k / (X - S), rounded in `rounding`'s direction.
+    // It plays the role of the integral of the price: the division truncates once.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let value = self
+            .invariant()?
+            .div_checked(self.pool_tokens_at(supply, "integral")?, "integral")?;
+        rounding.apply(value, ulps(T::ZERO))
+    }
+
+    // Supply at which the reserve side reaches `integral`, X - k / I
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
+        if integral <= T::ZERO {
+            return Ok(T::ZERO);
+        }
+        let pool_tokens = self.invariant()?.checked_div(integral).unwrap_or(T::MAX);
+        Ok(self
+            .total_tokens()?
+            .saturating_sub(pool_tokens)
+            .max(T::ZERO))
+    }
+
+    // X - max(r, sqrt(r)) with r = k / (MAX / 8), where both the reserve side k / (X - S)
+    // and the price k / (X - S)^2 are at most MAX / 8
+    fn supply_limit(&self) -> T {
+        let invariant = self
+            .token_balance
+            .saturating_mul(self.reserve_balance.saturating_add(self.virtual_reserve));
+        let ratio = invariant / (T::MAX / T::from_num(8));
+        let pool_tokens = ratio
+            .max(sqrt_fixed(ratio).unwrap_or(T::MAX))
+            .saturating_add(T::DELTA);
+        self.token_supply
+            .saturating_add(self.token_balance)
+            .saturating_sub(pool_tokens)
+            .max(T::ZERO)
+    }
+
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
+    }
+
+    // Tokens leave the pool as the supply rises and return as it falls, keeping X = S + x
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        if token_supply >= self.token_supply {
+            self.token_balance -= token_supply - self.token_supply;
+        } else {
+            self.token_balance += self.token_supply - token_supply;
+        }
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
+    }
+
+    // (y + v) / x
+    fn spot_price(&self) -> Result<T, BondingCurveError> {
+        self.effective_reserve()?
+            .div_checked(self.token_balance, "price")
+    }
+}
@@ -1,9 +1,83 @@
+use fixed::traits::Fixed;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+// Amounts and parameter values are reported as f64 so the error type does not depend on
+// the fixed-point type a curve is instantiated with
+#[derive(Error, Debug, Clone, PartialEq)]
 pub enum BondingCurveError {
-    #[error("Invalid input: {0}")]
-    InvalidInput(String),
-    #[error("Calculation error: {0}")]
-    CalculationError(String),
+    // A trade amount was zero or negative, or a payment too small to buy anything
+    #[error("Amount must be positive")]
+    ZeroAmount,
+    // A sale asked for more tokens than the curve has issued
+    #[error("Insufficient supply: requested {requested}, available {available}")]
+    InsufficientSupply { requested: f64, available: f64 },
+    // A trade asked for more reserve than the curve can pay out
+    #[error("Insufficient reserve: requested {requested}, available {available}")]
+    InsufficientReserve { requested: f64, available: f64 },
+    // An intermediate or final result does not fit in the fixed-point type
+    #[error("Overflow in {operation}")]
+    Overflow { operation: &'static str },
+    // A function was evaluated outside its domain, such as the logarithm of a non-positive value
+    #[error("Domain error in {operation}")]
+    DomainError { operation: &'static str },
+    #[error("Slippage exceeded: trade amount {amount} is outside the limit {limit}")]
+    SlippageExceeded { amount: f64, limit: f64 },
+    // A constructor or configuration parameter is out of range
+    #[error("Invalid parameter {name}: {value}")]
+    InvalidParameter { name: String, value: f64 },
+    // A configuration document could not be parsed
+    #[error("Malformed configuration: {message}")]
+    MalformedConfig { message: String },
+    // A configuration field is missing, unknown or of the wrong type
+    #[error("Invalid configuration field {field}: {message}")]
+    InvalidConfig { field: String, message: String },
+    // A trade was attempted on a curve that has reached its graduation target
+    #[error("Curve has graduated and no longer trades")]
+    CurveCompleted,
+    // A buy asked for more than a graduating curve fills before its target
+    #[error("Order exceeds the graduation target: requested {requested}, available {available}")]
+    TargetExceeded { requested: f64, available: f64 },
+    // An operation is not available in the curve's current phase
+    #[error("Cannot {operation} during the {phase} phase")]
+    WrongPhase {
+        operation: &'static str,
+        phase: &'static str,
+    },
+}
+
+impl BondingCurveError {
+    pub(crate) fn insufficient_supply<T: Fixed>(requested: T, available: T) -> Self {
+        BondingCurveError::InsufficientSupply {
+            requested: requested.to_num(),
+            available: available.to_num(),
+        }
+    }
+
+    pub(crate) fn insufficient_reserve<T: Fixed>(requested: T, available: T) -> Self {
+        BondingCurveError::InsufficientReserve {
+            requested: requested.to_num(),
+            available: available.to_num(),
+        }
+    }
+
+    pub(crate) fn slippage_exceeded<T: Fixed>(amount: T, limit: T) -> Self {
+        BondingCurveError::SlippageExceeded {
+            amount: amount.to_num(),
+            limit: limit.to_num(),
+        }
+    }
+
+    pub(crate) fn target_exceeded<T: Fixed>(requested: T, available: T) -> Self {
+        BondingCurveError::TargetExceeded {
+            requested: requested.to_num(),
+            available: available.to_num(),
+        }
+    }
+
+    pub(crate) fn invalid_parameter(name: &str, value: f64) -> Self {
+        BondingCurveError::InvalidParameter {
+            name: name.into(),
+            value,
+        }
+    }
 }
@@ -1,12 +1,18 @@
-use crate::bonding_curve_trait::BondingCurve;
+use crate::bonding_curve_trait::Rounding;
 use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_positive, fixed_parameter, pow_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::Fixed;
 use fixed::types::I64F64;
 
 #[derive(Clone, Debug)]
-pub struct Exponential {
-    pub coefficient: I64F64,
-    pub exponent: I64F64,
-    pub token_supply: I64F64,
+pub struct Exponential<T = I64F64> {
+    pub coefficient: T,
+    pub exponent: T,
+    pub token_supply: T,
+    pub reserve_balance: T,
 }
 
 /*
@@ -15,96 +21,113 @@ pub struct Exponential {
 * c is the coefficient (a scaling factor),
 * S is the token_supply,
 * n is the exponent (determining the curve’s steepness).
+*
+* The supported supply is bounded by S^(n+1) * max(1, c) <= MAX / 4, which keeps the
+* integral and its inverse representable. See `max_supply`.
 */
 
-impl Exponential {
+impl<T: Fixed> Exponential<T> {
     pub fn new(coefficient: f64, exponent: f64) -> Result<Self, BondingCurveError> {
-        if coefficient <= 0.0
-            || exponent <= 0.0
-            || !coefficient.is_finite()
-            || !exponent.is_finite()
-        {
-            return Err(BondingCurveError::InvalidInput(
-                "Coefficient and exponent must be positive and finite".into(),
-            ));
-        }
+        Self::from_parameters(
+            fixed_parameter("coefficient", coefficient)?,
+            fixed_parameter("exponent", exponent)?,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(coefficient: T, exponent: T) -> Result<Self, BondingCurveError> {
+        let coefficient = check_positive("coefficient", coefficient)?;
+        let exponent = check_positive("exponent", exponent)?;
+        // The integral divides by n + 1, which must itself be representable
+        exponent
+            .checked_add(T::from_num(1))
+            .ok_or_else(|| BondingCurveError::invalid_parameter("exponent", exponent.to_num()))?;
         Ok(Self {
-            coefficient: I64F64::from_num(coefficient),
-            exponent: I64F64::from_num(exponent),
-            token_supply: I64F64::from_num(0.0),
+            coefficient,
+            exponent,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
         })
     }
 
-    // Helper function to compute x^y using libm
-    fn pow_fixed(base: I64F64, exponent: I64F64) -> Result<I64F64, BondingCurveError> {
-        let base_f64: f64 = base.to_num();
-        let exp_f64: f64 = exponent.to_num();
-
-        if base_f64 < 0.0 {
-            return Err(BondingCurveError::CalculationError(
-                "Cannot raise negative number to fractional power".into(),
-            ));
-        }
-
-        let result = libm::pow(base_f64, exp_f64);
-
-        if !result.is_finite() {
-            return Err(BondingCurveError::CalculationError(
-                "Power calculation resulted in infinite or NaN value".into(),
-            ));
-        }
-
-        Ok(I64F64::from_num(result))
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        coefficient: T,
+        exponent: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(coefficient, exponent)?.with_state(token_supply, reserve_balance)
     }
-}
 
-impl BondingCurve for Exponential {
-    fn get_price(&self) -> Result<I64F64, BondingCurveError> {
-        let power_result = Self::pow_fixed(self.token_supply, self.exponent)?;
-        Ok(self.coefficient * power_result)
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(
+        coefficient: f64,
+        exponent: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::new(coefficient, exponent)?.with_initial_supply(initial_supply)
     }
+}
 
-    fn buy_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) {
-            return Err(BondingCurveError::InvalidInput(
-                "Token amount must be positive".into(),
-            ));
-        }
-
-        let n_plus_one = self.exponent + I64F64::from_num(1);
-        let new_supply_power = Self::pow_fixed(self.token_supply + token_amount, n_plus_one)?;
-        let current_supply_power = Self::pow_fixed(self.token_supply, n_plus_one)?;
-
-        let cost = (self.coefficient / n_plus_one) * new_supply_power
-            - (self.coefficient / n_plus_one) * current_supply_power;
+impl<T: Fixed> IntegralCurve for Exponential<T> {
+    type Value = T;
 
-        self.token_supply += token_amount;
-        Ok(cost)
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        let power_result = pow_fixed(check_domain(supply, "price")?, self.exponent)?;
+        self.coefficient.mul_checked(power_result, "price")
     }
 
-    fn sell_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) || token_amount > self.token_supply {
-            return Err(BondingCurveError::InvalidInput(
-                "Invalid token amount".into(),
-            ));
-        }
-
-        let n_plus_one = self.exponent + I64F64::from_num(1);
-        let current_supply_power = Self::pow_fixed(self.token_supply, n_plus_one)?;
-        let new_supply_power = Self::pow_fixed(self.token_supply - token_amount, n_plus_one)?;
+    // Integral of the price from 0 to `supply`, (c / (n + 1)) * S^(n+1), rounded in
+    // `rounding`'s direction by its error bound. c / (n + 1) truncates once and S^(n+1) is
+    // within max(1, S^(n+1)) * (2n + 4) + 1 ulps (see `pow_fixed`), so the product is within
+    // (c / (n + 1)) * (max(1, S^(n+1)) * (2n + 4) + 1) + S^(n+1) + 2 ulps.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let one = T::from_num(1);
+        let n_plus_one = self.exponent + one;
+        let supply_power = pow_fixed(supply, n_plus_one)?;
+        let scale = self.coefficient / n_plus_one;
+        let value = scale.mul_checked(supply_power, "integral")?;
+        let power_error = ulps(scale.saturating_mul(supply_power.max(one))).saturating_mul(
+            n_plus_one
+                .saturating_mul(T::from_num(2))
+                .saturating_add(T::from_num(2)),
+        );
+        let error = power_error.saturating_add(ulps(
+            scale
+                .saturating_add(supply_power)
+                .saturating_add(T::from_num(2)),
+        ));
+        rounding.apply(value, error)
+    }
 
-        let refund = (self.coefficient / n_plus_one) * current_supply_power
-            - (self.coefficient / n_plus_one) * new_supply_power;
+    // Closed-form estimate of the supply at which the integral reaches `integral`:
+    // S = (I * (n + 1) / c)^(1 / (n + 1))
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
+        let n_plus_one = self.exponent + T::from_num(1);
+        let base = integral
+            .mul_checked(n_plus_one, "integral inverse")?
+            .div_checked(self.coefficient, "integral inverse")?;
+        pow_fixed(base, T::from_num(1) / n_plus_one)
+    }
 
-        self.token_supply -= token_amount;
-        Ok(refund)
+    // (MAX / (4 * max(1, c)))^(1 / (n + 1))
+    fn supply_limit(&self) -> T {
+        let headroom = T::MAX / T::from_num(4);
+        let n_plus_one = self.exponent + T::from_num(1);
+        pow_fixed(
+            headroom / self.coefficient.max(T::from_num(1)),
+            T::from_num(1) / n_plus_one,
+        )
+        .unwrap_or(T::ZERO)
     }
 
-    fn get_supply(&self) -> I64F64 {
-        self.token_supply
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
     }
 
-    fn get_reserve(&self) -> Option<I64F64> {
-        None
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
     }
 }
@@ -0,0 +1,423 @@
+use crate::bonding_curve_trait::BondingCurve;
+use crate::errors::BondingCurveError;
+use crate::helpers::CheckedArithmetic;
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+pub(crate) const BPS_DENOMINATOR: u16 = 10_000;
+
+// Which leg of a trade the fee is taken from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeSide {
+    // Fees are deducted from what the trader pays in: reserve on buys, tokens on sells
+    Input,
+    // Fees are deducted from what the trader receives: tokens on buys, reserve on sells
+    Output,
+}
+
+// Account that collects a share of every fee
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeRecipient {
+    Protocol,
+    Creator,
+}
+
+// Fee rates are private so every config has passed the range checks in `new`
+#[derive(Clone, Debug)]
+pub struct FeeConfig {
+    buy_fee_bps: u16,
+    sell_fee_bps: u16,
+    // Share of each fee paid to the protocol; the remainder goes to the creator
+    protocol_share_bps: u16,
+    fee_side: FeeSide,
+}
+
+impl FeeConfig {
+    pub fn new(
+        buy_fee_bps: u16,
+        sell_fee_bps: u16,
+        protocol_share_bps: u16,
+        fee_side: FeeSide,
+    ) -> Result<Self, BondingCurveError> {
+        if buy_fee_bps >= BPS_DENOMINATOR {
+            return Err(BondingCurveError::invalid_parameter(
+                "buy_fee_bps",
+                f64::from(buy_fee_bps),
+            ));
+        }
+        if sell_fee_bps >= BPS_DENOMINATOR {
+            return Err(BondingCurveError::invalid_parameter(
+                "sell_fee_bps",
+                f64::from(sell_fee_bps),
+            ));
+        }
+        if protocol_share_bps > BPS_DENOMINATOR {
+            return Err(BondingCurveError::invalid_parameter(
+                "protocol_share_bps",
+                f64::from(protocol_share_bps),
+            ));
+        }
+        Ok(FeeConfig {
+            buy_fee_bps,
+            sell_fee_bps,
+            protocol_share_bps,
+            fee_side,
+        })
+    }
+
+    pub fn buy_fee_bps(&self) -> u16 {
+        self.buy_fee_bps
+    }
+
+    pub fn sell_fee_bps(&self) -> u16 {
+        self.sell_fee_bps
+    }
+
+    pub fn protocol_share_bps(&self) -> u16 {
+        self.protocol_share_bps
+    }
+
+    pub fn fee_side(&self) -> FeeSide {
+        self.fee_side
+    }
+}
+
+// Fees owed to a recipient. Fees taken from the reserve leg of a trade accrue as reserve,
+// fees taken from the token leg accrue as tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeBalance<T = I64F64> {
+    pub reserve: T,
+    pub tokens: T,
+}
+
+// `bps` in the raw integer type of `T`, so fees are scaled by exact integer multiplication
+fn bps_bits<T: Fixed>(bps: u16) -> Result<T::Bits, BondingCurveError> {
+    T::Bits::try_from(bps).map_err(|_| BondingCurveError::Overflow { operation: "fee" })
+}
+
+// amount * bps / 10000, rounded up
+pub(crate) fn fee_for<T: Fixed>(amount: T, bps: u16) -> Result<T, BondingCurveError> {
+    let denominator = bps_bits::<T>(BPS_DENOMINATOR)?;
+    let scaled = amount
+        .checked_mul_int(bps_bits::<T>(bps)?)
+        .ok_or(BondingCurveError::Overflow { operation: "fee" })?;
+    let fee = scaled / denominator;
+    if fee * denominator == scaled {
+        Ok(fee)
+    } else {
+        Ok(fee + T::DELTA)
+    }
+}
+
+// Smallest gross amount that still leaves `net` after the fee: net / (1 - bps / 10000)
+pub(crate) fn gross_up<T: Fixed>(net: T, bps: u16) -> Result<T, BondingCurveError> {
+    let remaining_bps = bps_bits::<T>(BPS_DENOMINATOR - bps)?;
+    let scaled = net
+        .checked_mul_int(bps_bits::<T>(BPS_DENOMINATOR)?)
+        .ok_or(BondingCurveError::Overflow { operation: "fee" })?;
+    let gross = scaled / remaining_bps;
+    if gross * remaining_bps == scaled {
+        Ok(gross)
+    } else {
+        gross.add_checked(T::DELTA, "fee")
+    }
+}
+
+/*
+ * Wraps any bonding curve and charges a fee on every trade.
+ *
+ * Fees are rounded up. Exact-output trades gross up the amount passed to the inner
+ * curve, so spending the quote for N tokens buys N tokens. With fee rate f on the input:
+ *   exact input:  the curve receives R * (1 - f)
+ *   exact output: the trader pays    cost / (1 - f)
+*/
+#[derive(Clone, Debug)]
+pub struct FeeCurve<C, T = I64F64> {
+    pub curve: C,
+    config: FeeConfig,
+    protocol_fees: FeeBalance<T>,
+    creator_fees: FeeBalance<T>,
+}
+
+// Outcome of a trade before it is applied to the inner curve
+struct FeeTrade<T> {
+    // Amount passed to the matching operation on the inner curve
+    curve_amount: T,
+    // Amount returned to the trader
+    amount: T,
+    fee: FeeBalance<T>,
+}
+
+impl<T: Fixed, C: BondingCurve<T>> FeeCurve<C, T> {
+    pub fn new(curve: C, config: FeeConfig) -> Self {
+        FeeCurve {
+            curve,
+            config,
+            protocol_fees: FeeBalance::default(),
+            creator_fees: FeeBalance::default(),
+        }
+    }
+
+    pub fn config(&self) -> &FeeConfig {
+        &self.config
+    }
+
+    // Fees accumulated for `recipient` and not yet withdrawn
+    pub fn fee_balance(&self, recipient: FeeRecipient) -> FeeBalance<T> {
+        match recipient {
+            FeeRecipient::Protocol => self.protocol_fees,
+            FeeRecipient::Creator => self.creator_fees,
+        }
+    }
+
+    // Pays out and resets the fees accumulated for `recipient`
+    pub fn withdraw_fees(&mut self, recipient: FeeRecipient) -> FeeBalance<T> {
+        let balance = match recipient {
+            FeeRecipient::Protocol => &mut self.protocol_fees,
+            FeeRecipient::Creator => &mut self.creator_fees,
+        };
+        std::mem::take(balance)
+    }
+
+    fn reserve_fee(fee: T) -> FeeBalance<T> {
+        FeeBalance {
+            reserve: fee,
+            tokens: T::ZERO,
+        }
+    }
+
+    fn token_fee(fee: T) -> FeeBalance<T> {
+        FeeBalance {
+            reserve: T::ZERO,
+            tokens: fee,
+        }
+    }
+
+    fn plan_buy_with_reserve(&self, reserve_amount: T) -> Result<FeeTrade<T>, BondingCurveError> {
+        let bps = self.config.buy_fee_bps;
+        match self.config.fee_side {
+            FeeSide::Input => {
+                let fee = fee_for(reserve_amount, bps)?;
+                let curve_amount = reserve_amount - fee;
+                Ok(FeeTrade {
+                    curve_amount,
+                    amount: self.curve.quote_buy_with_reserve(curve_amount)?,
+                    fee: Self::reserve_fee(fee),
+                })
+            }
+            FeeSide::Output => {
+                let tokens = self.curve.quote_buy_with_reserve(reserve_amount)?;
+                let fee = fee_for(tokens, bps)?;
+                Ok(FeeTrade {
+                    curve_amount: reserve_amount,
+                    amount: tokens - fee,
+                    fee: Self::token_fee(fee),
+                })
+            }
+        }
+    }
+
+    fn plan_buy_exact_tokens(&self, token_amount: T) -> Result<FeeTrade<T>, BondingCurveError> {
+        let bps = self.config.buy_fee_bps;
+        match self.config.fee_side {
+            FeeSide::Input => {
+                let cost = self.curve.quote_buy_exact_tokens(token_amount)?;
+                let gross = gross_up(cost, bps)?;
+                Ok(FeeTrade {
+                    curve_amount: token_amount,
+                    amount: gross,
+                    fee: Self::reserve_fee(gross - cost),
+                })
+            }
+            FeeSide::Output => {
+                let gross = gross_up(token_amount, bps)?;
+                Ok(FeeTrade {
+                    curve_amount: gross,
+                    amount: self.curve.quote_buy_exact_tokens(gross)?,
+                    fee: Self::token_fee(gross - token_amount),
+                })
+            }
+        }
+    }
+
+    fn plan_sell_token(&self, token_amount: T) -> Result<FeeTrade<T>, BondingCurveError> {
+        let bps = self.config.sell_fee_bps;
+        match self.config.fee_side {
+            FeeSide::Input => {
+                let fee = fee_for(token_amount, bps)?;
+                let curve_amount = token_amount - fee;
+                Ok(FeeTrade {
+                    curve_amount,
+                    amount: self.curve.quote_sell(curve_amount)?,
+                    fee: Self::token_fee(fee),
+                })
+            }
+            FeeSide::Output => {
+                let refund = self.curve.quote_sell(token_amount)?;
+                let fee = fee_for(refund, bps)?;
+                Ok(FeeTrade {
+                    curve_amount: token_amount,
+                    amount: refund - fee,
+                    fee: Self::reserve_fee(fee),
+                })
+            }
+        }
+    }
+
+    fn plan_sell_for_reserve(&self, reserve_amount: T) -> Result<FeeTrade<T>, BondingCurveError> {
+        let bps = self.config.sell_fee_bps;
+        match self.config.fee_side {
+            FeeSide::Input => {
+                let tokens = self.curve.quote_sell_for_reserve(reserve_amount)?;
+                let gross = gross_up(tokens, bps)?;
+                Ok(FeeTrade {
+                    curve_amount: reserve_amount,
+                    amount: gross,
+                    fee: Self::token_fee(gross - tokens),
+                })
+            }
+            FeeSide::Output => {
+                let gross = gross_up(reserve_amount, bps)?;
+                Ok(FeeTrade {
+                    curve_amount: gross,
+                    amount: self.curve.quote_sell_for_reserve(gross)?,
+                    fee: Self::reserve_fee(gross - reserve_amount),
+                })
+            }
+        }
+    }
+
+    // Balances of the protocol and the creator after splitting `fee` between them, rounding
+    // the protocol share down
+    fn credited(
+        &self,
+        fee: FeeBalance<T>,
+    ) -> Result<(FeeBalance<T>, FeeBalance<T>), BondingCurveError> {
+        let share = bps_bits::<T>(self.config.protocol_share_bps)?;
+        let denominator = bps_bits::<T>(BPS_DENOMINATOR)?;
+        let protocol_reserve = fee.reserve.saturating_mul_int(share) / denominator;
+        let protocol_tokens = fee.tokens.saturating_mul_int(share) / denominator;
+        let protocol_fees = FeeBalance {
+            reserve: self
+                .protocol_fees
+                .reserve
+                .add_checked(protocol_reserve, "fee")?,
+            tokens: self
+                .protocol_fees
+                .tokens
+                .add_checked(protocol_tokens, "fee")?,
+        };
+        let creator_fees = FeeBalance {
+            reserve: self
+                .creator_fees
+                .reserve
+                .add_checked(fee.reserve - protocol_reserve, "fee")?,
+            tokens: self
+                .creator_fees
+                .tokens
+                .add_checked(fee.tokens - protocol_tokens, "fee")?,
+        };
+        Ok((protocol_fees, creator_fees))
+    }
+
+    // Applies `trade` to the inner curve and credits its fee. The new fee balances are
+    // computed first, so a fee that overflows them leaves the inner curve untouched.
+    fn settle(
+        &mut self,
+        trade: FeeTrade<T>,
+        apply: impl FnOnce(&mut C, T) -> Result<T, BondingCurveError>,
+    ) -> Result<T, BondingCurveError> {
+        let (protocol_fees, creator_fees) = self.credited(trade.fee)?;
+        apply(&mut self.curve, trade.curve_amount)?;
+        self.protocol_fees = protocol_fees;
+        self.creator_fees = creator_fees;
+        Ok(trade.amount)
+    }
+}
+
+impl<T: Fixed, C: BondingCurve<T>> BondingCurve<T> for FeeCurve<C, T> {
+    fn get_price(&self) -> Result<T, BondingCurveError> {
+        self.curve.get_price()
+    }
+
+    // Like `get_price`, the pure curve functions describe the wrapped curve without fees
+    fn price_at(&self, supply: T) -> Result<T, BondingCurveError> {
+        self.curve.price_at(supply)
+    }
+
+    fn cost_between(&self, from: T, to: T) -> Result<T, BondingCurveError> {
+        self.curve.cost_between(from, to)
+    }
+
+    fn supply_for_reserve(&self, reserve: T) -> Result<T, BondingCurveError> {
+        self.curve.supply_for_reserve(reserve)
+    }
+
+    fn quote_buy(&self, amount: T) -> Result<T, BondingCurveError> {
+        if self.curve.buys_with_reserve() {
+            self.quote_buy_with_reserve(amount)
+        } else {
+            self.quote_buy_exact_tokens(amount)
+        }
+    }
+
+    fn quote_sell(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_sell_token(token_amount)?.amount)
+    }
+
+    fn quote_buy_with_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_buy_with_reserve(reserve_amount)?.amount)
+    }
+
+    fn quote_buy_exact_tokens(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_buy_exact_tokens(token_amount)?.amount)
+    }
+
+    fn quote_sell_for_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_sell_for_reserve(reserve_amount)?.amount)
+    }
+
+    fn buy_token(&mut self, amount: T) -> Result<T, BondingCurveError> {
+        if self.curve.buys_with_reserve() {
+            self.buy_with_reserve(amount)
+        } else {
+            self.buy_exact_tokens(amount)
+        }
+    }
+
+    fn buy_with_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let trade = self.plan_buy_with_reserve(reserve_amount)?;
+        self.settle(trade, C::buy_with_reserve)
+    }
+
+    fn buy_exact_tokens(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        let trade = self.plan_buy_exact_tokens(token_amount)?;
+        self.settle(trade, C::buy_exact_tokens)
+    }
+
+    fn sell_token(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        let trade = self.plan_sell_token(token_amount)?;
+        self.settle(trade, C::sell_token)
+    }
+
+    fn sell_for_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let trade = self.plan_sell_for_reserve(reserve_amount)?;
+        self.settle(trade, C::sell_for_reserve)
+    }
+
+    fn get_supply(&self) -> T {
+        self.curve.get_supply()
+    }
+
+    fn get_reserve(&self) -> Option<T> {
+        self.curve.get_reserve()
+    }
+
+    fn max_supply(&self) -> T {
+        self.curve.max_supply()
+    }
+
+    fn buys_with_reserve(&self) -> bool {
+        self.curve.buys_with_reserve()
+    }
+}
@@ -0,0 +1,462 @@
+use crate::bonding_curve_trait::BondingCurve;
+use crate::errors::BondingCurveError;
+use crate::helpers::{CheckedArithmetic, MAX_SOLVER_ITERATIONS, check_positive};
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+// The point at which a curve graduates
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraduationTarget<T = I64F64> {
+    // Reserve held by the curve
+    Reserve(T),
+    // Tokens issued by the curve
+    Supply(T),
+    // Current price times `total_supply`, in reserve units
+    MarketCap { market_cap: T, total_supply: T },
+}
+
+// Outcome of a buy on a graduating curve
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GraduationFill<T = I64F64> {
+    // Tokens bought
+    pub tokens: T,
+    // Reserve paid for them
+    pub reserve: T,
+    // Part of a reserve amount that was not spent because the curve graduated first
+    pub refund: T,
+    // Whether this buy completed the curve
+    pub graduated: bool,
+}
+
+/*
+ * Wraps any bonding curve and closes it once a target is reached.
+ *
+ * `fill_with_reserve` and `fill_exact_tokens` fill the buy that crosses the target only
+ * up to it: a reserve amount is spent until the target and the rest refunded, a token
+ * amount is cut to the tokens left before the target. The `BondingCurve` buys and quotes
+ * cannot report a cut, so they fail with `TargetExceeded` for such a buy instead. The buy
+ * that reaches the target completes the curve, and every later trade fails with
+ * `CurveCompleted`.
+*/
+#[derive(Clone, Debug)]
+pub struct GraduationCurve<C, T = I64F64> {
+    pub curve: C,
+    pub target: GraduationTarget<T>,
+    completed: bool,
+}
+
+// How a buy is passed to the inner curve
+#[derive(Clone, Copy)]
+enum Order<T> {
+    Reserve(T),
+    Tokens(T),
+}
+
+// A buy before it is applied to the inner curve
+struct GraduationPlan<T> {
+    order: Order<T>,
+    fill: GraduationFill<T>,
+}
+
+impl<T: Fixed, C: BondingCurve<T> + Clone> GraduationCurve<C, T> {
+    // Fails if the target is not positive or lies beyond what the curve can sell. A curve
+    // that already meets its target starts out completed.
+    pub fn new(curve: C, target: GraduationTarget<T>) -> Result<Self, BondingCurveError> {
+        let mut graduation = GraduationCurve {
+            curve,
+            target,
+            completed: false,
+        };
+        let max_supply = graduation.curve.max_supply();
+        let reachable = match target {
+            GraduationTarget::Reserve(reserve) => {
+                check_positive("target_reserve", reserve)?;
+                if graduation.curve.get_reserve().is_none() {
+                    return Err(BondingCurveError::invalid_parameter(
+                        "target_reserve",
+                        reserve.to_num(),
+                    ));
+                }
+                let supply = graduation.curve.get_supply();
+                match graduation.curve.cost_between(supply, max_supply) {
+                    Ok(cost) => graduation.reserve().saturating_add(cost) >= reserve,
+                    Err(BondingCurveError::Overflow { .. }) => true,
+                    Err(error) => return Err(error),
+                }
+            }
+            GraduationTarget::Supply(supply) => {
+                check_positive("target_supply", supply)? <= max_supply
+            }
+            GraduationTarget::MarketCap {
+                market_cap,
+                total_supply,
+            } => {
+                check_positive("target_market_cap", market_cap)?;
+                check_positive("total_supply", total_supply)?;
+                graduation.market_cap_reached(max_supply, market_cap, total_supply)?
+            }
+        };
+        if !reachable {
+            let (name, value) = match target {
+                GraduationTarget::Reserve(reserve) => ("target_reserve", reserve),
+                GraduationTarget::Supply(supply) => ("target_supply", supply),
+                GraduationTarget::MarketCap { market_cap, .. } => ("target_market_cap", market_cap),
+            };
+            return Err(BondingCurveError::invalid_parameter(name, value.to_num()));
+        }
+        graduation.completed = graduation.reached()?;
+        Ok(graduation)
+    }
+
+    // Whether the curve has graduated and stopped trading
+    pub fn is_completed(&self) -> bool {
+        self.completed
+    }
+
+    // Tokens left to sell before the curve graduates
+    pub fn tokens_to_graduation(&self) -> Result<T, BondingCurveError> {
+        self.check_open()?;
+        Ok(self.crossing()?.fill.tokens)
+    }
+
+    // Like `buy_with_reserve`, but also reports the refund and whether the curve graduated
+    pub fn fill_with_reserve(
+        &mut self,
+        reserve_amount: T,
+    ) -> Result<GraduationFill<T>, BondingCurveError> {
+        let plan = self.plan_buy_with_reserve(reserve_amount)?;
+        self.execute(plan)
+    }
+
+    // Like `buy_exact_tokens`, but also reports whether the curve graduated
+    pub fn fill_exact_tokens(
+        &mut self,
+        token_amount: T,
+    ) -> Result<GraduationFill<T>, BondingCurveError> {
+        let plan = self.plan_buy_exact_tokens(token_amount)?;
+        self.execute(plan)
+    }
+
+    fn check_open(&self) -> Result<(), BondingCurveError> {
+        if self.completed {
+            return Err(BondingCurveError::CurveCompleted);
+        }
+        Ok(())
+    }
+
+    fn reserve(&self) -> T {
+        self.curve.get_reserve().unwrap_or(T::ZERO)
+    }
+
+    // Whether the market cap at `supply` meets `market_cap`. A market cap too large to
+    // represent meets any target.
+    fn market_cap_reached(
+        &self,
+        supply: T,
+        market_cap: T,
+        total_supply: T,
+    ) -> Result<bool, BondingCurveError> {
+        match self
+            .curve
+            .price_at(supply)
+            .and_then(|price| price.mul_checked(total_supply, "market cap"))
+        {
+            Ok(cap) => Ok(cap >= market_cap),
+            Err(BondingCurveError::Overflow { .. }) => Ok(true),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn reached(&self) -> Result<bool, BondingCurveError> {
+        match self.target {
+            GraduationTarget::Reserve(reserve) => Ok(self.reserve() >= reserve),
+            GraduationTarget::Supply(supply) => Ok(self.curve.get_supply() >= supply),
+            GraduationTarget::MarketCap {
+                market_cap,
+                total_supply,
+            } => self.market_cap_reached(self.curve.get_supply(), market_cap, total_supply),
+        }
+    }
+
+    // Smallest supply at which the market cap meets `market_cap`, found by bisection
+    // between the current supply and `max_supply`
+    fn market_cap_supply(&self, market_cap: T, total_supply: T) -> Result<T, BondingCurveError> {
+        let mut low = self.curve.get_supply();
+        let mut high = self.curve.max_supply().max(low);
+        for _ in 0..MAX_SOLVER_ITERATIONS {
+            if high - low <= T::DELTA {
+                break;
+            }
+            let mid = low + (high - low) / T::from_num(2);
+            if self.market_cap_reached(mid, market_cap, total_supply)? {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Ok(high)
+    }
+
+    // Whether spending `reserve_amount` raises the reserve to `target`, found by applying
+    // the buy to a copy of the curve. A buy too large to represent is taken to reach it.
+    fn reserve_reached_by(&self, reserve_amount: T, target: T) -> Result<bool, BondingCurveError> {
+        let mut curve = self.curve.clone();
+        match curve.buy_with_reserve(reserve_amount) {
+            Ok(_) => Ok(curve.get_reserve().unwrap_or(T::ZERO) >= target),
+            Err(BondingCurveError::Overflow { .. }) => Ok(true),
+            Err(error) => Err(error),
+        }
+    }
+
+    // Smallest reserve amount whose buy raises the reserve to `target`. The curve need not
+    // keep all of it: a fee or tribute taken on the way in leaves less in the reserve, so
+    // the amount is searched for by doubling the missing reserve, then by bisection.
+    fn reserve_to_reach(&self, target: T) -> Result<T, BondingCurveError> {
+        let mut low = target - self.reserve();
+        if self.reserve_reached_by(low, target)? {
+            return Ok(low);
+        }
+        let mut high = low.add_checked(low, "graduation reserve")?;
+        while !self.reserve_reached_by(high, target)? {
+            low = high;
+            high = high.add_checked(high, "graduation reserve")?;
+        }
+        for _ in 0..MAX_SOLVER_ITERATIONS {
+            if high - low <= T::DELTA {
+                break;
+            }
+            let mid = low + (high - low) / T::from_num(2);
+            if self.reserve_reached_by(mid, target)? {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Ok(high)
+    }
+
+    // The buy that takes the curve exactly to its target. A reserve target is reached by
+    // the smallest reserve amount that raises the reserve to it, the others by buying the
+    // missing tokens.
+    fn crossing(&self) -> Result<GraduationPlan<T>, BondingCurveError> {
+        let supply = self.curve.get_supply();
+        let (order, tokens, reserve) = match self.target {
+            GraduationTarget::Reserve(target) => {
+                let reserve = self.reserve_to_reach(target)?;
+                let tokens = self.curve.quote_buy_with_reserve(reserve)?;
+                (Order::Reserve(reserve), tokens, reserve)
+            }
+            GraduationTarget::Supply(target) => {
+                let tokens = target - supply;
+                let reserve = self.curve.quote_buy_exact_tokens(tokens)?;
+                (Order::Tokens(tokens), tokens, reserve)
+            }
+            GraduationTarget::MarketCap {
+                market_cap,
+                total_supply,
+            } => {
+                let tokens = self.market_cap_supply(market_cap, total_supply)? - supply;
+                let reserve = self.curve.quote_buy_exact_tokens(tokens)?;
+                (Order::Tokens(tokens), tokens, reserve)
+            }
+        };
+        Ok(GraduationPlan {
+            order,
+            fill: GraduationFill {
+                tokens,
+                reserve,
+                refund: T::ZERO,
+                graduated: false,
+            },
+        })
+    }
+
+    fn plan_buy_with_reserve(
+        &self,
+        reserve_amount: T,
+    ) -> Result<GraduationPlan<T>, BondingCurveError> {
+        self.check_open()?;
+        if reserve_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let mut crossing = self.crossing()?;
+        if reserve_amount >= crossing.fill.reserve {
+            crossing.fill.refund = reserve_amount - crossing.fill.reserve;
+            return Ok(crossing);
+        }
+        Ok(GraduationPlan {
+            order: Order::Reserve(reserve_amount),
+            fill: GraduationFill {
+                tokens: self.curve.quote_buy_with_reserve(reserve_amount)?,
+                reserve: reserve_amount,
+                refund: T::ZERO,
+                graduated: false,
+            },
+        })
+    }
+
+    fn plan_buy_exact_tokens(
+        &self,
+        token_amount: T,
+    ) -> Result<GraduationPlan<T>, BondingCurveError> {
+        self.check_open()?;
+        if token_amount <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let crossing = self.crossing()?;
+        if token_amount >= crossing.fill.tokens {
+            return Ok(crossing);
+        }
+        Ok(GraduationPlan {
+            order: Order::Tokens(token_amount),
+            fill: GraduationFill {
+                tokens: token_amount,
+                reserve: self.curve.quote_buy_exact_tokens(token_amount)?,
+                refund: T::ZERO,
+                graduated: false,
+            },
+        })
+    }
+
+    // A reserve amount the `BondingCurve` buys can spend in full
+    fn plan_uncut_with_reserve(
+        &self,
+        reserve_amount: T,
+    ) -> Result<GraduationPlan<T>, BondingCurveError> {
+        let plan = self.plan_buy_with_reserve(reserve_amount)?;
+        if plan.fill.refund > T::ZERO {
+            return Err(BondingCurveError::target_exceeded(
+                reserve_amount,
+                plan.fill.reserve,
+            ));
+        }
+        Ok(plan)
+    }
+
+    // A token amount the `BondingCurve` buys can deliver in full
+    fn plan_uncut_exact_tokens(
+        &self,
+        token_amount: T,
+    ) -> Result<GraduationPlan<T>, BondingCurveError> {
+        let plan = self.plan_buy_exact_tokens(token_amount)?;
+        if plan.fill.tokens < token_amount {
+            return Err(BondingCurveError::target_exceeded(
+                token_amount,
+                plan.fill.tokens,
+            ));
+        }
+        Ok(plan)
+    }
+
+    // Applies a planned buy to the inner curve. Whether it graduated is read from the curve
+    // afterwards, so a buy that was not planned to cross the target but lands on it
+    // completes the curve as well.
+    fn execute(&mut self, plan: GraduationPlan<T>) -> Result<GraduationFill<T>, BondingCurveError> {
+        let mut fill = plan.fill;
+        match plan.order {
+            Order::Reserve(reserve) if reserve > T::ZERO => {
+                fill.tokens = self.curve.buy_with_reserve(reserve)?;
+            }
+            Order::Tokens(tokens) if tokens > T::ZERO => {
+                fill.reserve = self.curve.buy_exact_tokens(tokens)?;
+            }
+            _ => {}
+        }
+        fill.graduated = self.reached()?;
+        self.completed = fill.graduated;
+        Ok(fill)
+    }
+}
+
+impl<T: Fixed, C: BondingCurve<T> + Clone> BondingCurve<T> for GraduationCurve<C, T> {
+    fn get_price(&self) -> Result<T, BondingCurveError> {
+        self.curve.get_price()
+    }
+
+    // Like `get_price`, the pure curve functions describe the wrapped curve past its target
+    fn price_at(&self, supply: T) -> Result<T, BondingCurveError> {
+        self.curve.price_at(supply)
+    }
+
+    fn cost_between(&self, from: T, to: T) -> Result<T, BondingCurveError> {
+        self.curve.cost_between(from, to)
+    }
+
+    fn supply_for_reserve(&self, reserve: T) -> Result<T, BondingCurveError> {
+        self.curve.supply_for_reserve(reserve)
+    }
+
+    fn quote_buy(&self, amount: T) -> Result<T, BondingCurveError> {
+        if self.curve.buys_with_reserve() {
+            self.quote_buy_with_reserve(amount)
+        } else {
+            self.quote_buy_exact_tokens(amount)
+        }
+    }
+
+    fn quote_sell(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        self.check_open()?;
+        self.curve.quote_sell(token_amount)
+    }
+
+    // Fails with `TargetExceeded` if the target would cut the buy; `fill_with_reserve`
+    // fills it up to the target and refunds the rest
+    fn quote_buy_with_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_uncut_with_reserve(reserve_amount)?.fill.tokens)
+    }
+
+    // Fails with `TargetExceeded` if fewer than `token_amount` tokens are left before the
+    // target; `fill_exact_tokens` buys the tokens left instead
+    fn quote_buy_exact_tokens(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        Ok(self.plan_uncut_exact_tokens(token_amount)?.fill.reserve)
+    }
+
+    fn quote_sell_for_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.check_open()?;
+        self.curve.quote_sell_for_reserve(reserve_amount)
+    }
+
+    fn buy_token(&mut self, amount: T) -> Result<T, BondingCurveError> {
+        if self.curve.buys_with_reserve() {
+            self.buy_with_reserve(amount)
+        } else {
+            self.buy_exact_tokens(amount)
+        }
+    }
+
+    fn buy_with_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let plan = self.plan_uncut_with_reserve(reserve_amount)?;
+        Ok(self.execute(plan)?.tokens)
+    }
+
+    fn buy_exact_tokens(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        let plan = self.plan_uncut_exact_tokens(token_amount)?;
+        Ok(self.execute(plan)?.reserve)
+    }
+
+    fn sell_token(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        self.check_open()?;
+        self.curve.sell_token(token_amount)
+    }
+
+    fn sell_for_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.check_open()?;
+        self.curve.sell_for_reserve(reserve_amount)
+    }
+
+    fn get_supply(&self) -> T {
+        self.curve.get_supply()
+    }
+
+    fn get_reserve(&self) -> Option<T> {
+        self.curve.get_reserve()
+    }
+
+    fn max_supply(&self) -> T {
+        self.curve.max_supply()
+    }
+
+    fn buys_with_reserve(&self) -> bool {
+        self.curve.buys_with_reserve()
+    }
+}
@@ -1,92 +1,398 @@
 use crate::errors::BondingCurveError;
+use fixed::consts;
 use fixed::traits::Fixed;
+use fixed::types::I16F112;
+
+/*
+ * Deterministic fixed-point exp, ln, pow and sqrt.
+ *
+ * Only integer operations are used, so results are bit-for-bit identical on every
+ * platform. Arguments are range-reduced to a mantissa and a power of two, the series
+ * run in I16F112 (112 fractional bits), and the result is truncated back to T.
+ *
+ * Error bounds, with ε = T::DELTA and T carrying at most 112 fractional bits:
+ *   exp_fixed(x):     |error| < ε · max(1, e^x) + ε
+ *   ln_fixed(x):      |error| < 2ε
+ *   pow_fixed(b, e):  |error| < ε · max(1, b^e) · (2|e| + 2) + ε
+ *   sqrt_fixed(x):    |error| < ε · max(1, sqrt(x)), exact for perfect squares
+*/
+
+type Wide = I16F112;
+
+// Magnitude beyond which exp overflows every 128-bit fixed-point type (e^89 > 2^128)
+const MAX_EXP_ARGUMENT: i32 = 89;
+
+// Upper bound on the steps taken by the supply solvers, in both their bracketing and
+// bisection phases
+pub(crate) const MAX_SOLVER_ITERATIONS: u32 = 128;
+
+fn ln_2() -> Wide {
+    Wide::from_num(consts::LN_2)
+}
+
+// x / 2^shift as a wide value, shifting in the wide type when x fits so no bits are lost
+fn shifted_to_wide<T: Fixed>(x: T, shift: i32) -> Wide {
+    match Wide::checked_from_num(x) {
+        Some(wide) if shift >= 0 => wide >> shift.unsigned_abs(),
+        Some(wide) => wide << shift.unsigned_abs(),
+        None => Wide::from_num(x >> shift.unsigned_abs()),
+    }
+}
+
+// mantissa * 2^exponent as T, shifting in the wide type while the result still fits there
+fn scaled_from_wide<T: Fixed>(mantissa: Wide, exponent: i32) -> Option<T> {
+    if exponent <= 0 {
+        let shifted = mantissa
+            .checked_shr(exponent.unsigned_abs())
+            .unwrap_or(Wide::ZERO);
+        return T::checked_from_num(shifted);
+    }
+    if exponent <= 12 {
+        return T::checked_from_num(mantissa << exponent.unsigned_abs());
+    }
+    let power_of_two = T::checked_from_num(1u128.checked_shl(exponent.unsigned_abs())?)?;
+    T::checked_from_num(mantissa)?.checked_mul(power_of_two)
+}
+
+// e^r by Taylor series, for |r| below ln(2)
+fn exp_series(r: Wide) -> Wide {
+    let mut sum = Wide::ONE;
+    let mut term = Wide::ONE;
+    let mut n = 1;
+    loop {
+        term = term * r / n;
+        if term == Wide::ZERO {
+            return sum;
+        }
+        sum += term;
+        n += 1;
+    }
+}
+
+// ln(m) for m in [1, 2) by the series 2 * atanh((m - 1) / (m + 1))
+fn ln_series(m: Wide) -> Wide {
+    let z = (m - Wide::ONE) / (m + Wide::ONE);
+    let z_squared = z * z;
+    let mut sum = z;
+    let mut power = z;
+    let mut n = 1;
+    loop {
+        power *= z_squared;
+        n += 2;
+        let term = power / n;
+        if term == Wide::ZERO {
+            return sum * 2;
+        }
+        sum += term;
+    }
+}
+
+// e^y for a wide argument: y = k * ln(2) + r, e^y = 2^k * e^r
+fn exp_wide<T: Fixed>(y: Wide) -> Result<T, BondingCurveError> {
+    if y > MAX_EXP_ARGUMENT {
+        return Err(BondingCurveError::Overflow { operation: "exp" });
+    }
+    if y < -MAX_EXP_ARGUMENT * 2 {
+        return Ok(T::ZERO);
+    }
+    let k: i32 = (y / ln_2()).floor().to_num();
+    let r = y - ln_2() * i128::from(k);
+    scaled_from_wide(exp_series(r), k).ok_or(BondingCurveError::Overflow { operation: "exp" })
+}
+
+// ln(x) as a wide value: x = 2^k * m, ln(x) = k * ln(2) + ln(m)
+fn ln_wide<T: Fixed>(value: T) -> Result<Wide, BondingCurveError> {
+    if value <= 0 {
+        return Err(BondingCurveError::DomainError { operation: "ln" });
+    }
+    let k = value.int_log2();
+    let m = shifted_to_wide(value, k);
+    Ok(ln_2() * i128::from(k) + ln_series(m))
+}
 
 /// exponential function for fixed-point types
-fn exp_fixed<T>(value: T) -> Result<T, BondingCurveError>
+pub fn exp_fixed<T>(value: T) -> Result<T, BondingCurveError>
 where
     T: Fixed,
 {
-    let value_f64: f64 = value.to_num();
-    let result = libm::exp(value_f64);
-
-    if !result.is_finite() {
-        return Err(BondingCurveError::CalculationError(
-            "Exponential calculation resulted in infinite or NaN value".into(),
-        ));
+    match Wide::checked_from_num(value) {
+        Some(y) => exp_wide(y),
+        None if value < 0 => Ok(T::ZERO),
+        None => Err(BondingCurveError::Overflow { operation: "exp" }),
     }
-
-    Ok(T::from_num(result))
 }
 
 /// natural logarithm function for fixed-point types
-fn ln_fixed<T>(value: T) -> Result<T, BondingCurveError>
+pub fn ln_fixed<T>(value: T) -> Result<T, BondingCurveError>
 where
     T: Fixed,
 {
-    let value_f64: f64 = value.to_num();
+    T::checked_from_num(ln_wide(value)?).ok_or(BondingCurveError::Overflow { operation: "ln" })
+}
 
-    if value_f64 <= 0.0 {
-        return Err(BondingCurveError::CalculationError(
-            "Cannot take logarithm of non-positive number".into(),
-        ));
+/// power function for fixed-point types
+pub fn pow_fixed<T>(base: T, exponent: T) -> Result<T, BondingCurveError>
+where
+    T: Fixed,
+{
+    if base < 0 {
+        return Err(BondingCurveError::DomainError { operation: "pow" });
     }
-
-    let result = libm::log(value_f64);
-
-    if !result.is_finite() {
-        return Err(BondingCurveError::CalculationError(
-            "Logarithm calculation resulted in infinite or NaN value".into(),
-        ));
+    if base == 0 {
+        return match exponent.cmp(&T::ZERO) {
+            std::cmp::Ordering::Greater => Ok(T::ZERO),
+            std::cmp::Ordering::Equal => {
+                T::checked_from_num(1).ok_or(BondingCurveError::Overflow { operation: "pow" })
+            }
+            std::cmp::Ordering::Less => Err(BondingCurveError::DomainError { operation: "pow" }),
+        };
+    }
+    if exponent == 1 {
+        return Ok(base);
     }
 
-    Ok(T::from_num(result))
+    // b^e = e^(e * ln(b))
+    let overflow = || BondingCurveError::Overflow { operation: "pow" };
+    let ln_base = ln_wide(base)?;
+    let exponent_wide = Wide::checked_from_num(exponent).ok_or_else(overflow)?;
+    match ln_base.checked_mul(exponent_wide) {
+        Some(y) => exp_wide(y).map_err(|_| overflow()),
+        // A product too large to represent is either far below the smallest
+        // representable value or far above the largest one
+        None if (ln_base < 0) != (exponent < 0) => Ok(T::ZERO),
+        None => Err(overflow()),
+    }
 }
 
-/// power function for fixed-point types
-fn pow_fixed<T>(base: T, exponent: T) -> Result<T, BondingCurveError>
+/// square root function for fixed-point types
+pub fn sqrt_fixed<T>(value: T) -> Result<T, BondingCurveError>
 where
     T: Fixed,
 {
-    let base_f64: f64 = base.to_num();
-    let exp_f64: f64 = exponent.to_num();
+    if value < 0 {
+        return Err(BondingCurveError::DomainError { operation: "sqrt" });
+    }
+    if value == 0 {
+        return Ok(T::ZERO);
+    }
 
-    if base_f64 < 0.0 && exp_f64.fract() != 0.0 {
-        return Err(BondingCurveError::CalculationError(
-            "Cannot raise negative number to fractional power".into(),
-        ));
+    // x = 4^j * m with m in [1, 4), sqrt(x) = 2^j * sqrt(m)
+    let j = value.int_log2().div_euclid(2);
+    let m = shifted_to_wide(value, 2 * j);
+
+    // Newton's method from above converges to floor(sqrt(m)) in the wide type
+    let mut root = (m + Wide::ONE) / 2;
+    loop {
+        let next = (root + m / root) / 2;
+        if next >= root {
+            break;
+        }
+        root = next;
     }
 
-    let result = libm::pow(base_f64, exp_f64);
+    scaled_from_wide(root, j).ok_or(BondingCurveError::Overflow { operation: "sqrt" })
+}
+
+// Arithmetic that reports an `Overflow` naming `operation` instead of panicking or wrapping.
+// Dividing by zero is a `DomainError`.
+pub(crate) trait CheckedArithmetic: Sized {
+    fn add_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError>;
+    fn sub_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError>;
+    fn mul_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError>;
+    fn div_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError>;
+}
 
-    if !result.is_finite() {
-        return Err(BondingCurveError::CalculationError(
-            "Power calculation resulted in infinite or NaN value".into(),
-        ));
+impl<T: Fixed> CheckedArithmetic for T {
+    fn add_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError> {
+        self.checked_add(rhs)
+            .ok_or(BondingCurveError::Overflow { operation })
+    }
+
+    fn sub_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError> {
+        self.checked_sub(rhs)
+            .ok_or(BondingCurveError::Overflow { operation })
+    }
+
+    fn mul_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError> {
+        self.checked_mul(rhs)
+            .ok_or(BondingCurveError::Overflow { operation })
+    }
+
+    fn div_checked(self, rhs: Self, operation: &'static str) -> Result<Self, BondingCurveError> {
+        if rhs == 0 {
+            return Err(BondingCurveError::DomainError { operation });
+        }
+        self.checked_div(rhs)
+            .ok_or(BondingCurveError::Overflow { operation })
     }
+}
 
-    Ok(T::from_num(result))
+// Converts a constructor parameter, rejecting values that are not finite or do not fit
+// in T. Range checks are left to the curve, which applies them to the converted value.
+pub(crate) fn fixed_parameter<T: Fixed>(name: &str, value: f64) -> Result<T, BondingCurveError> {
+    match T::checked_from_num(value) {
+        Some(converted) if value.is_finite() => Ok(converted),
+        _ => Err(BondingCurveError::invalid_parameter(name, value)),
+    }
 }
 
-/// square root function for fixed-point types
-fn sqrt_fixed<T>(value: T) -> Result<T, BondingCurveError>
-where
-    T: Fixed,
-{
-    let value_f64: f64 = value.to_num();
+// Converts a constructor parameter that must be zero or positive
+pub(crate) fn non_negative_parameter<T: Fixed>(
+    name: &str,
+    value: f64,
+) -> Result<T, BondingCurveError> {
+    match T::checked_from_num(value) {
+        Some(converted) if value.is_finite() && value >= 0.0 => Ok(converted),
+        _ => Err(BondingCurveError::invalid_parameter(name, value)),
+    }
+}
 
-    if value_f64 < 0.0 {
-        return Err(BondingCurveError::CalculationError(
-            "Cannot take square root of negative number".into(),
+// Checks that a supply or reserve passed to a pure curve function is non-negative
+pub(crate) fn check_domain<T: Fixed>(
+    value: T,
+    operation: &'static str,
+) -> Result<T, BondingCurveError> {
+    if value < 0 {
+        return Err(BondingCurveError::DomainError { operation });
+    }
+    Ok(value)
+}
+
+// Checks that `from..to` is a range of non-negative supplies for `cost_between`
+pub(crate) fn check_interval<T: Fixed>(from: T, to: T) -> Result<(), BondingCurveError> {
+    if check_domain(from, "cost_between")? > to {
+        return Err(BondingCurveError::DomainError {
+            operation: "cost_between",
+        });
+    }
+    Ok(())
+}
+
+// Checks a parameter that is already in the fixed-point type and must be positive
+pub(crate) fn check_positive<T: Fixed>(name: &str, value: T) -> Result<T, BondingCurveError> {
+    if value > 0 {
+        Ok(value)
+    } else {
+        Err(BondingCurveError::invalid_parameter(name, value.to_num()))
+    }
+}
+
+// Checks a Bancor connector weight, which must lie in (0, 1]
+pub(crate) fn check_connector_weight<T: Fixed>(value: T) -> Result<T, BondingCurveError> {
+    if check_positive("connector_weight", value)? > 1 {
+        return Err(BondingCurveError::invalid_parameter(
+            "connector_weight",
+            value.to_num(),
         ));
     }
+    Ok(value)
+}
 
-    let result = libm::sqrt(value_f64);
+// Checks a value that is already in the fixed-point type and must be zero or positive
+pub(crate) fn check_non_negative<T: Fixed>(name: &str, value: T) -> Result<T, BondingCurveError> {
+    if value >= 0 {
+        Ok(value)
+    } else {
+        Err(BondingCurveError::invalid_parameter(name, value.to_num()))
+    }
+}
 
-    if !result.is_finite() {
-        return Err(BondingCurveError::CalculationError(
-            "Square root calculation resulted in infinite or NaN value".into(),
+// Checks the supply and reserve of a curve restored from stored state: both must be
+// non-negative and the supply must lie within the curve's `max_supply`
+#[cfg(any(feature = "serde", feature = "borsh"))]
+pub(crate) fn check_state<T: Fixed>(
+    max_supply: T,
+    token_supply: T,
+    reserve_balance: T,
+) -> Result<(), BondingCurveError> {
+    if check_non_negative("token_supply", token_supply)? > max_supply {
+        return Err(BondingCurveError::invalid_parameter(
+            "token_supply",
+            token_supply.to_num(),
         ));
     }
+    check_non_negative("reserve_balance", reserve_balance)?;
+    Ok(())
+}
 
-    Ok(T::from_num(result))
+// `count` multiples of `T::DELTA`, rounded up. Converts an error bound counted in ulps
+// into a value of the type.
+pub(crate) fn ulps<T: Fixed>(count: T) -> T {
+    count.saturating_mul(T::DELTA).saturating_add(T::DELTA)
+}
+
+// Whether `bound` does not exceed `target`. A bound that overflows lies above any
+// representable target.
+pub(crate) fn at_most<T: Fixed>(
+    bound: Result<T, BondingCurveError>,
+    target: T,
+) -> Result<bool, BondingCurveError> {
+    match bound {
+        Ok(value) => Ok(value <= target),
+        Err(BondingCurveError::Overflow { .. }) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+// Largest supply at or above `lower` whose rounded-up antiderivative `bound` does not
+// exceed `target`, or `lower` if none does. Closed-form inverses only estimate that
+// supply, so the root is bracketed by doubling steps away from `estimate`, upwards or
+// downwards depending on which side of the root it lies, and then narrowed by bisection,
+// both bounded by MAX_SOLVER_ITERATIONS.
+pub(crate) fn floor_supply<T: Fixed>(
+    lower: T,
+    target: T,
+    estimate: impl FnOnce() -> Result<T, BondingCurveError>,
+    bound: impl Fn(T) -> Result<T, BondingCurveError>,
+) -> Result<T, BondingCurveError> {
+    if !at_most(bound(lower), target)? {
+        return Ok(lower);
+    }
+    let estimate = estimate()?.max(lower);
+    let estimate_fits = at_most(bound(estimate), target)?;
+
+    // `lo` always satisfies the bound and `hi`, once found, never does
+    let (mut lo, mut hi) = if estimate_fits {
+        (estimate, None)
+    } else {
+        (lower, Some(estimate))
+    };
+    let mut step = T::DELTA;
+    for _ in 0..MAX_SOLVER_ITERATIONS {
+        let candidate = match hi {
+            None => lo.saturating_add(step),
+            Some(upper) => upper.saturating_sub(step).max(lower),
+        };
+        if candidate == lo || hi == Some(candidate) {
+            break;
+        }
+        if at_most(bound(candidate), target)? {
+            lo = candidate;
+            if hi.is_some() {
+                break;
+            }
+        } else {
+            hi = Some(candidate);
+            if estimate_fits {
+                break;
+            }
+        }
+        step = step.saturating_add(step);
+    }
+
+    let Some(mut hi) = hi else {
+        return Ok(lo);
+    };
+    for _ in 0..MAX_SOLVER_ITERATIONS {
+        let mid = lo + (hi - lo) / T::from_num(2);
+        if mid == lo {
+            break;
+        }
+        if at_most(bound(mid), target)? {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(lo)
 }
@@ -0,0 +1,280 @@
+use crate::bonding_curve_trait::{BondingCurve, Rounding};
+use crate::errors::BondingCurveError;
+#[cfg(any(feature = "serde", feature = "borsh"))]
+use crate::helpers::check_state;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_interval, floor_supply, non_negative_parameter,
+};
+use fixed::traits::Fixed;
+
+/*
+* Curves whose reserve is the integral of their price, I(S):
+*
+*   buying ΔS tokens costs     I(S + ΔS) - I(S), rounded up
+*   selling ΔS tokens refunds  I(S) - I(S - ΔS), rounded down
+*   a reserve R buys up to     the largest S' with I(S') <= I(S) + R
+*
+* A curve implements the math: its price, `integral` rounded by its error bound, an
+* estimate of the inverse of the integral and `max_supply`. Every `BondingCurve` quote and
+* trade is derived from them below, following the `Rounding` policy.
+*/
+// Public only so the blanket `BondingCurve` impl may name it; the module is private, so
+// no other crate can implement or call it
+pub trait IntegralCurve: Sized {
+    // Fixed-point type of the curve's prices, supplies and reserves
+    type Value: Fixed;
+
+    // Price at `supply`, failing with `DomainError` for a negative supply
+    fn price(&self, supply: Self::Value) -> Result<Self::Value, BondingCurveError>;
+
+    // Integral of the price from 0 to `supply`, rounded in `rounding`'s direction by the
+    // curve's error bound
+    fn integral(
+        &self,
+        supply: Self::Value,
+        rounding: Rounding,
+    ) -> Result<Self::Value, BondingCurveError>;
+
+    // Estimate of the supply at which the integral reaches `integral`. It only seeds the
+    // solver in `supply_covered_by`, so it may miss the root on either side; an estimate
+    // below the supply a quote starts from is raised to it.
+    fn supply_at_integral(&self, integral: Self::Value) -> Result<Self::Value, BondingCurveError>;
+
+    // Largest supply up to which every quote is representable, the curve's `max_supply`
+    fn supply_limit(&self) -> Self::Value;
+
+    // Token supply and reserve balance
+    fn state(&self) -> (Self::Value, Self::Value);
+
+    fn set_state(&mut self, token_supply: Self::Value, reserve_balance: Self::Value);
+
+    // Price at the current supply
+    fn spot_price(&self) -> Result<Self::Value, BondingCurveError> {
+        self.price(self.state().0)
+    }
+
+    // Largest supply at or above `lower` that the reserve `target` is guaranteed to cover
+    fn supply_covered_by(
+        &self,
+        target: Self::Value,
+        lower: Self::Value,
+    ) -> Result<Self::Value, BondingCurveError> {
+        floor_supply(
+            lower,
+            target,
+            || self.supply_at_integral(target),
+            |supply| self.integral(supply, Rounding::Up),
+        )
+    }
+
+    // Reserve refunded for lowering the supply from `to` to `from`, rounded down
+    fn refund_between(
+        &self,
+        from: Self::Value,
+        to: Self::Value,
+    ) -> Result<Self::Value, BondingCurveError> {
+        let upper = self.integral(to, Rounding::Down)?;
+        Ok(upper
+            .saturating_sub(self.integral(from, Rounding::Up)?)
+            .max(Self::Value::ZERO))
+    }
+
+    // Adds bought tokens and the reserve paid for them, leaving the curve unchanged on overflow
+    fn issue(
+        &mut self,
+        tokens: Self::Value,
+        reserve: Self::Value,
+    ) -> Result<(), BondingCurveError> {
+        let (token_supply, reserve_balance) = self.state();
+        let token_supply = token_supply.add_checked(tokens, "supply")?;
+        let reserve_balance = reserve_balance.add_checked(reserve, "reserve")?;
+        self.set_state(token_supply, reserve_balance);
+        Ok(())
+    }
+
+    // Removes sold tokens and the reserve paid out for them. Quotes never exceed the
+    // supply or the reserve, so this cannot fail.
+    fn redeem(
+        &mut self,
+        tokens: Self::Value,
+        reserve: Self::Value,
+    ) -> Result<(), BondingCurveError> {
+        let (token_supply, reserve_balance) = self.state();
+        self.set_state(token_supply - tokens, reserve_balance - reserve);
+        Ok(())
+    }
+
+    // Sets the state of a curve rebuilt from storage, requiring a non-negative supply
+    // within `max_supply` and a non-negative reserve
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    fn with_state(
+        mut self,
+        token_supply: Self::Value,
+        reserve_balance: Self::Value,
+    ) -> Result<Self, BondingCurveError> {
+        check_state(self.supply_limit(), token_supply, reserve_balance)?;
+        self.set_state(token_supply, reserve_balance);
+        Ok(self)
+    }
+
+    // Issues `initial_supply` tokens to a new curve, backed by a reserve equal to the
+    // integral of the price from 0 to that supply
+    fn with_initial_supply(mut self, initial_supply: f64) -> Result<Self, BondingCurveError> {
+        let supply: Self::Value = non_negative_parameter("initial_supply", initial_supply)?;
+        if supply > self.supply_limit() {
+            return Err(BondingCurveError::invalid_parameter(
+                "initial_supply",
+                initial_supply,
+            ));
+        }
+        let reserve = self.cost_between(Self::Value::ZERO, supply)?;
+        self.set_state(supply, reserve);
+        Ok(self)
+    }
+}
+
+impl<C: IntegralCurve> BondingCurve<C::Value> for C {
+    fn get_price(&self) -> Result<C::Value, BondingCurveError> {
+        self.spot_price()
+    }
+
+    fn price_at(&self, supply: C::Value) -> Result<C::Value, BondingCurveError> {
+        self.price(supply)
+    }
+
+    fn cost_between(&self, from: C::Value, to: C::Value) -> Result<C::Value, BondingCurveError> {
+        check_interval(from, to)?;
+        let upper = self.integral(to, Rounding::Up)?;
+        Ok(upper
+            .saturating_sub(self.integral(from, Rounding::Down)?)
+            .max(C::Value::ZERO))
+    }
+
+    fn supply_for_reserve(&self, reserve: C::Value) -> Result<C::Value, BondingCurveError> {
+        let target = self
+            .integral(C::Value::ZERO, Rounding::Down)?
+            .add_checked(check_domain(reserve, "supply_for_reserve")?, "integral")?;
+        self.supply_covered_by(target, C::Value::ZERO)
+    }
+
+    fn quote_buy(&self, token_amount: C::Value) -> Result<C::Value, BondingCurveError> {
+        if token_amount <= C::Value::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let token_supply = self.state().0;
+        let new_supply = token_supply.add_checked(token_amount, "supply")?;
+        self.cost_between(token_supply, new_supply)
+    }
+
+    fn quote_sell(&self, token_amount: C::Value) -> Result<C::Value, BondingCurveError> {
+        if token_amount <= C::Value::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let (token_supply, reserve_balance) = self.state();
+        if token_amount > token_supply {
+            return Err(BondingCurveError::insufficient_supply(
+                token_amount,
+                token_supply,
+            ));
+        }
+        let refund = self.refund_between(token_supply - token_amount, token_supply)?;
+        // A sale can never pay out more than the curve holds
+        Ok(refund.min(reserve_balance))
+    }
+
+    fn quote_buy_with_reserve(
+        &self,
+        reserve_amount: C::Value,
+    ) -> Result<C::Value, BondingCurveError> {
+        if reserve_amount <= C::Value::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let token_supply = self.state().0;
+        let target = self
+            .integral(token_supply, Rounding::Down)?
+            .add_checked(reserve_amount, "integral")?;
+        let new_supply = self.supply_covered_by(target, token_supply)?;
+        Ok(new_supply - token_supply)
+    }
+
+    fn quote_buy_exact_tokens(
+        &self,
+        token_amount: C::Value,
+    ) -> Result<C::Value, BondingCurveError> {
+        self.quote_buy(token_amount)
+    }
+
+    fn quote_sell_for_reserve(
+        &self,
+        reserve_amount: C::Value,
+    ) -> Result<C::Value, BondingCurveError> {
+        if reserve_amount <= C::Value::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let (token_supply, reserve_balance) = self.state();
+        let full_refund = self.refund_between(C::Value::ZERO, token_supply)?;
+        if reserve_amount > full_refund {
+            return Err(BondingCurveError::insufficient_reserve(
+                reserve_amount,
+                full_refund,
+            ));
+        }
+        if reserve_amount > reserve_balance {
+            return Err(BondingCurveError::insufficient_reserve(
+                reserve_amount,
+                reserve_balance,
+            ));
+        }
+        let target = self
+            .integral(token_supply, Rounding::Down)?
+            .saturating_sub(reserve_amount);
+        let new_supply = self.supply_covered_by(target, C::Value::ZERO)?;
+        Ok(token_supply.saturating_sub(new_supply).max(C::Value::ZERO))
+    }
+
+    fn buy_token(&mut self, token_amount: C::Value) -> Result<C::Value, BondingCurveError> {
+        let cost = self.quote_buy(token_amount)?;
+        self.issue(token_amount, cost)?;
+        Ok(cost)
+    }
+
+    fn buy_with_reserve(
+        &mut self,
+        reserve_amount: C::Value,
+    ) -> Result<C::Value, BondingCurveError> {
+        let tokens = self.quote_buy_with_reserve(reserve_amount)?;
+        self.issue(tokens, reserve_amount)?;
+        Ok(tokens)
+    }
+
+    fn buy_exact_tokens(&mut self, token_amount: C::Value) -> Result<C::Value, BondingCurveError> {
+        self.buy_token(token_amount)
+    }
+
+    fn sell_token(&mut self, token_amount: C::Value) -> Result<C::Value, BondingCurveError> {
+        let refund = self.quote_sell(token_amount)?;
+        self.redeem(token_amount, refund)?;
+        Ok(refund)
+    }
+
+    fn sell_for_reserve(
+        &mut self,
+        reserve_amount: C::Value,
+    ) -> Result<C::Value, BondingCurveError> {
+        let tokens = self.quote_sell_for_reserve(reserve_amount)?;
+        self.redeem(tokens, reserve_amount)?;
+        Ok(tokens)
+    }
+
+    fn get_supply(&self) -> C::Value {
+        self.state().0
+    }
+
+    fn get_reserve(&self) -> Option<C::Value> {
+        Some(self.state().1)
+    }
+
+    fn max_supply(&self) -> C::Value {
+        self.supply_limit()
+    }
+}
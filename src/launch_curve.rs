@@ -0,0 +1,296 @@
+use crate::bonding_curve_trait::BondingCurve;
+use crate::constant_product::ConstantProduct;
+use crate::errors::BondingCurveError;
+#[cfg(any(feature = "serde", feature = "borsh"))]
+use crate::helpers::check_state;
+use crate::helpers::{
+    CheckedArithmetic, check_non_negative, fixed_parameter, non_negative_parameter,
+};
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+#[derive(Clone, Debug)]
+pub struct LaunchCurve<T = I64F64> {
+    // Tokens the price is computed from, including the real inventory
+    pub virtual_token_reserve: T,
+    // Reserve counted in the price on top of the real reserve, never paid out
+    pub virtual_reserve: T,
+    // Tokens the curve can still sell
+    pub real_token_reserve: T,
+    // Every token of the launch, sold or not, for the market cap
+    pub total_supply: T,
+    // Tokens sold by the curve
+    pub token_supply: T,
+    // Reserve paid in for the tokens sold
+    pub reserve_balance: T,
+}
+
+/*
+* A launch curve priced from virtual balances, as popularized by pump.fun:
+*
+*   x * (y + v) = k
+*
+* where:
+* x is the virtual_token_reserve,
+* y is the real reserve_balance,
+* v is the virtual reserve,
+* and the price is (y + v) / x.
+*
+* The virtual token reserve is larger than the tokens actually for sale, which sets the
+* starting price without any liquidity. Trades move the virtual and real balances
+* together, and the curve sells at most its real_token_reserve, so `max_supply` ends at
+* the real inventory rather than where the pool would run dry. The swap math is that of
+* `ConstantProduct`.
+*/
+
+impl<T: Fixed> LaunchCurve<T> {
+    pub fn new(
+        virtual_token_reserve: f64,
+        virtual_reserve: f64,
+        real_token_reserve: f64,
+        total_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(
+            fixed_parameter("virtual_token_reserve", virtual_token_reserve)?,
+            fixed_parameter("virtual_reserve", virtual_reserve)?,
+            fixed_parameter("real_token_reserve", real_token_reserve)?,
+            fixed_parameter("total_supply", total_supply)?,
+            T::ZERO,
+            T::ZERO,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(
+        virtual_token_reserve: T,
+        virtual_reserve: T,
+        real_token_reserve: T,
+        total_supply: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        let pool = ConstantProduct::from_parameters(
+            virtual_token_reserve,
+            virtual_reserve,
+            token_supply,
+            reserve_balance,
+        )
+        .map_err(|error| match error {
+            BondingCurveError::InvalidParameter { name, value } if name == "token_balance" => {
+                BondingCurveError::invalid_parameter("virtual_token_reserve", value)
+            }
+            error => error,
+        })?;
+        // The real inventory is part of the virtual one, and the tokens sold and left
+        // for sale are part of the total supply
+        let real_token_reserve = check_non_negative("real_token_reserve", real_token_reserve)?;
+        if real_token_reserve > virtual_token_reserve {
+            return Err(BondingCurveError::invalid_parameter(
+                "real_token_reserve",
+                real_token_reserve.to_num(),
+            ));
+        }
+        if token_supply.saturating_add(real_token_reserve) > total_supply {
+            return Err(BondingCurveError::invalid_parameter(
+                "total_supply",
+                total_supply.to_num(),
+            ));
+        }
+        Ok(LaunchCurve {
+            virtual_token_reserve: pool.token_balance,
+            virtual_reserve: pool.virtual_reserve,
+            real_token_reserve,
+            total_supply,
+            token_supply: pool.token_supply,
+            reserve_balance: pool.reserve_balance,
+        })
+    }
+
+    // Rebuilds a curve from stored state, re-running the checks of `new` and requiring a
+    // non-negative supply within `max_supply` and a non-negative reserve
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        virtual_token_reserve: T,
+        virtual_reserve: T,
+        real_token_reserve: T,
+        total_supply: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        let curve = Self::from_parameters(
+            virtual_token_reserve,
+            virtual_reserve,
+            real_token_reserve,
+            total_supply,
+            token_supply,
+            reserve_balance,
+        )?;
+        check_state(curve.max_supply(), token_supply, reserve_balance)?;
+        Ok(curve)
+    }
+
+    // Creates a curve that has already sold `initial_supply` tokens, backed by a reserve
+    // equal to their cost along the curve
+    pub fn with_supply(
+        virtual_token_reserve: f64,
+        virtual_reserve: f64,
+        real_token_reserve: f64,
+        total_supply: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        let mut curve = Self::new(
+            virtual_token_reserve,
+            virtual_reserve,
+            real_token_reserve,
+            total_supply,
+        )?;
+        let supply: T = non_negative_parameter("initial_supply", initial_supply)?;
+        if supply > curve.max_supply() {
+            return Err(BondingCurveError::invalid_parameter(
+                "initial_supply",
+                initial_supply,
+            ));
+        }
+        if supply > T::ZERO {
+            curve.buy_token(supply)?;
+        }
+        Ok(curve)
+    }
+
+    // Virtual token and reserve balances the price is computed from, x and y + v
+    pub fn virtual_reserves(&self) -> Result<(T, T), BondingCurveError> {
+        Ok((self.virtual_token_reserve, self.pool().effective_reserve()?))
+    }
+
+    // Tokens left for sale and reserve actually held
+    pub fn real_reserves(&self) -> (T, T) {
+        (self.real_token_reserve, self.reserve_balance)
+    }
+
+    // Current price times the total supply, in reserve units
+    pub fn market_cap(&self) -> Result<T, BondingCurveError> {
+        self.get_price()?
+            .mul_checked(self.total_supply, "market cap")
+    }
+
+    // The constant-product pool the curve prices against
+    fn pool(&self) -> ConstantProduct<T> {
+        ConstantProduct {
+            token_balance: self.virtual_token_reserve,
+            virtual_reserve: self.virtual_reserve,
+            token_supply: self.token_supply,
+            reserve_balance: self.reserve_balance,
+        }
+    }
+
+    // Fails unless the real inventory covers `tokens`
+    fn check_inventory(&self, tokens: T) -> Result<T, BondingCurveError> {
+        if tokens > self.real_token_reserve {
+            return Err(BondingCurveError::insufficient_supply(
+                tokens,
+                self.real_token_reserve,
+            ));
+        }
+        Ok(tokens)
+    }
+
+    // Runs `trade` against the pool and moves the real inventory by the tokens it sold or
+    // bought back, leaving the curve unchanged if it fails
+    fn trade(
+        &mut self,
+        trade: impl FnOnce(&mut ConstantProduct<T>) -> Result<T, BondingCurveError>,
+    ) -> Result<T, BondingCurveError> {
+        let mut pool = self.pool();
+        let result = trade(&mut pool)?;
+        let real_token_reserve = if pool.token_supply >= self.token_supply {
+            self.real_token_reserve - (pool.token_supply - self.token_supply)
+        } else {
+            self.real_token_reserve
+                .add_checked(self.token_supply - pool.token_supply, "supply")?
+        };
+        self.virtual_token_reserve = pool.token_balance;
+        self.token_supply = pool.token_supply;
+        self.reserve_balance = pool.reserve_balance;
+        self.real_token_reserve = real_token_reserve;
+        Ok(result)
+    }
+}
+
+impl<T: Fixed> BondingCurve<T> for LaunchCurve<T> {
+    fn get_price(&self) -> Result<T, BondingCurveError> {
+        self.pool().get_price()
+    }
+
+    fn price_at(&self, supply: T) -> Result<T, BondingCurveError> {
+        self.pool().price_at(supply)
+    }
+
+    fn cost_between(&self, from: T, to: T) -> Result<T, BondingCurveError> {
+        self.pool().cost_between(from, to)
+    }
+
+    fn supply_for_reserve(&self, reserve: T) -> Result<T, BondingCurveError> {
+        self.pool().supply_for_reserve(reserve)
+    }
+
+    fn quote_buy(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        let cost = self.pool().quote_buy(token_amount)?;
+        self.check_inventory(token_amount)?;
+        Ok(cost)
+    }
+
+    fn quote_sell(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        self.pool().quote_sell(token_amount)
+    }
+
+    fn quote_buy_with_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        let tokens = self.pool().quote_buy_with_reserve(reserve_amount)?;
+        self.check_inventory(tokens)
+    }
+
+    fn quote_buy_exact_tokens(&self, token_amount: T) -> Result<T, BondingCurveError> {
+        self.quote_buy(token_amount)
+    }
+
+    fn quote_sell_for_reserve(&self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.pool().quote_sell_for_reserve(reserve_amount)
+    }
+
+    fn buy_token(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        self.quote_buy(token_amount)?;
+        self.trade(|pool| pool.buy_token(token_amount))
+    }
+
+    fn buy_with_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.quote_buy_with_reserve(reserve_amount)?;
+        self.trade(|pool| pool.buy_with_reserve(reserve_amount))
+    }
+
+    fn buy_exact_tokens(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        self.buy_token(token_amount)
+    }
+
+    fn sell_token(&mut self, token_amount: T) -> Result<T, BondingCurveError> {
+        self.trade(|pool| pool.sell_token(token_amount))
+    }
+
+    fn sell_for_reserve(&mut self, reserve_amount: T) -> Result<T, BondingCurveError> {
+        self.trade(|pool| pool.sell_for_reserve(reserve_amount))
+    }
+
+    fn get_supply(&self) -> T {
+        self.token_supply
+    }
+
+    fn get_reserve(&self) -> Option<T> {
+        Some(self.reserve_balance)
+    }
+
+    // The tokens sold so far plus the real inventory, or less if the pool's price would
+    // exceed the fixed-point range first
+    fn max_supply(&self) -> T {
+        self.token_supply
+            .saturating_add(self.real_token_reserve)
+            .min(self.pool().max_supply())
+    }
+}
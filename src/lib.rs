@@ -1,15 +1,50 @@
+mod affine_linear;
+mod any_curve;
+mod augmented;
 mod bancor;
+#[cfg(feature = "borsh")]
+mod binary;
 mod bonding_curve_trait;
+#[cfg(feature = "config")]
+mod config;
+mod constant_product;
 mod errors;
 mod exponential;
+mod fees;
+mod graduation;
+mod helpers;
+mod integral_curve;
+mod launch_curve;
 mod linear;
 mod logarithmic;
+mod migration;
+mod natural_exponential;
+mod offset_power;
+mod polynomial;
+#[cfg(feature = "serde")]
+mod serialization;
 mod sigmoid;
 
+pub use affine_linear::AffineLinear;
+pub use any_curve::{AnyCurve, CurveKind};
+pub use augmented::{Augmented, AugmentedPhase};
 pub use bancor::Bancor;
-pub use bonding_curve_trait::BondingCurve;
+#[cfg(feature = "borsh")]
+pub use binary::LAYOUT_VERSION;
+pub use bonding_curve_trait::{BondingCurve, Rounding};
+#[cfg(feature = "config")]
+pub use config::CurveConfig;
+pub use constant_product::ConstantProduct;
 pub use errors::BondingCurveError;
 pub use exponential::Exponential;
+pub use fees::{FeeBalance, FeeConfig, FeeCurve, FeeRecipient, FeeSide};
+pub use graduation::{GraduationCurve, GraduationFill, GraduationTarget};
+pub use helpers::{exp_fixed, ln_fixed, pow_fixed, sqrt_fixed};
+pub use launch_curve::LaunchCurve;
 pub use linear::Linear;
 pub use logarithmic::Logarithmic;
+pub use migration::{LeftoverTokens, Migration, MigrationConfig};
+pub use natural_exponential::NaturalExponential;
+pub use offset_power::OffsetPower;
+pub use polynomial::Polynomial;
 pub use sigmoid::Sigmoid;
@@ -1,11 +1,17 @@
-use crate::bonding_curve_trait::BondingCurve;
+use crate::bonding_curve_trait::Rounding;
 use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_positive, fixed_parameter, sqrt_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::Fixed;
 use fixed::types::I64F64;
 
 #[derive(Clone, Debug)]
-pub struct Linear {
-    pub slope: I64F64,
-    pub token_supply: I64F64,
+pub struct Linear<T = I64F64> {
+    pub slope: T,
+    pub token_supply: T,
+    pub reserve_balance: T,
 }
 
 /*
@@ -15,59 +21,79 @@ where:
 *   P is the price of a token,
 *   k is the slope (a constant parameter),
 *   S is the current token supply.
+
+The supported supply is bounded by S^2 * max(1, k) <= MAX / 4, which keeps S^2, the
+integral and its inverse representable. See `max_supply`.
 */
 
-impl Linear {
+impl<T: Fixed> Linear<T> {
     pub fn new(slope: f64) -> Result<Self, BondingCurveError> {
-        if slope <= 0.0 {
-            return Err(BondingCurveError::InvalidInput(
-                "Slope must be positive".into(),
-            ));
-        }
+        Self::from_parameters(fixed_parameter("slope", slope)?)
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(slope: T) -> Result<Self, BondingCurveError> {
         Ok(Linear {
-            slope: I64F64::from_num(slope),
-            token_supply: I64F64::from_num(0),
+            slope: check_positive("slope", slope)?,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
         })
     }
+
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        slope: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(slope)?.with_state(token_supply, reserve_balance)
+    }
+
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(slope: f64, initial_supply: f64) -> Result<Self, BondingCurveError> {
+        Self::new(slope)?.with_initial_supply(initial_supply)
+    }
 }
 
-impl BondingCurve for Linear {
-    fn get_price(&self) -> Result<I64F64, BondingCurveError> {
-        Ok(self.slope * self.token_supply)
+impl<T: Fixed> IntegralCurve for Linear<T> {
+    type Value = T;
+
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        self.slope
+            .mul_checked(check_domain(supply, "price")?, "price")
     }
-    fn buy_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) {
-            return Err(BondingCurveError::InvalidInput(
-                "Token amount must be positive".into(),
-            ));
-        }
-        // Cost = ∫(k*S)dS from S to S+ΔS = k * (S+ΔS)^2 / 2 - k * S^2 / 2
-        let new_supply = self.token_supply + token_amount;
-        let cost = self.slope * (new_supply * new_supply) / I64F64::from_num(2)
-            - self.slope * (self.token_supply * self.token_supply) / I64F64::from_num(2);
-        self.token_supply = new_supply;
-        Ok(cost)
+
+    // k * S^2 / 2. S^2, the product with k and the halving each truncate once, so the
+    // result is within (k + 3) ulps.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let squared = supply.mul_checked(supply, "integral")?;
+        let value = self
+            .slope
+            .mul_checked(squared, "integral")?
+            .div_checked(T::from_num(2), "integral")?;
+        rounding.apply(value, ulps(self.slope.saturating_add(T::from_num(3))))
+    }
+
+    // S = sqrt(2 * I / k)
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
+        let squared = integral
+            .mul_checked(T::from_num(2), "integral inverse")?
+            .div_checked(self.slope, "integral inverse")?;
+        sqrt_fixed(squared)
     }
 
-    fn sell_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) || token_amount > self.token_supply {
-            return Err(BondingCurveError::InvalidInput(
-                "Invalid token amount".into(),
-            ));
-        }
-        // Refund = ∫(k*S)dS from S-ΔS to S = k * S^2 / 2 - k * (S-ΔS)^2 / 2
-        let new_supply = self.token_supply - token_amount;
-        let refund = self.slope * (self.token_supply * self.token_supply) / I64F64::from_num(2)
-            - self.slope * (new_supply * new_supply) / I64F64::from_num(2);
-        self.token_supply = new_supply;
-        Ok(refund)
+    // sqrt(MAX / (4 * max(1, k)))
+    fn supply_limit(&self) -> T {
+        let headroom = T::MAX / T::from_num(4);
+        sqrt_fixed(headroom / self.slope.max(T::from_num(1))).unwrap_or(T::ZERO)
     }
 
-    fn get_supply(&self) -> I64F64 {
-        self.token_supply
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
     }
 
-    fn get_reserve(&self) -> Option<I64F64> {
-        None
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
     }
 }
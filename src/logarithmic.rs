@@ -1,12 +1,18 @@
-use crate::bonding_curve_trait::BondingCurve;
+use crate::bonding_curve_trait::Rounding;
 use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_positive, fixed_parameter, ln_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::FixedSigned;
 use fixed::types::I64F64;
 
 #[derive(Clone, Debug)]
-pub struct Logarithmic {
-    pub coefficient: I64F64,
-    pub constant: I64F64,
-    pub token_supply: I64F64,
+pub struct Logarithmic<T = I64F64> {
+    pub coefficient: T,
+    pub constant: T,
+    pub token_supply: T,
+    pub reserve_balance: T,
 }
 
 /*
@@ -16,111 +22,100 @@ pub struct Logarithmic {
 *  S  is the token supply,
 *  k  is a constant (often 1, to avoid issues with ln(0)),
 *  ln  is the natural logarithm.
+*
+* The supported supply is bounded by (S + k) * max(1, c) * max(1, ln(MAX / 4)) <= MAX / 4,
+* which keeps x * ln(x) and the integral representable. See `max_supply`.
 */
 
-impl Logarithmic {
+impl<T: FixedSigned> Logarithmic<T> {
     pub fn new(coefficient: f64, constant: f64) -> Result<Self, BondingCurveError> {
-        if coefficient <= 0.0
-            || constant <= 0.0
-            || !coefficient.is_finite()
-            || !constant.is_finite()
-        {
-            return Err(BondingCurveError::InvalidInput(
-                "Coefficient and constant must be positive and finite".into(),
-            ));
-        }
+        Self::from_parameters(
+            fixed_parameter("coefficient", coefficient)?,
+            fixed_parameter("constant", constant)?,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(coefficient: T, constant: T) -> Result<Self, BondingCurveError> {
         Ok(Logarithmic {
-            coefficient: I64F64::from_num(coefficient),
-            constant: I64F64::from_num(constant),
-            token_supply: I64F64::from_num(0),
+            coefficient: check_positive("coefficient", coefficient)?,
+            constant: check_positive("constant", constant)?,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
         })
     }
 
-    // Helper function to compute natural logarithm using libm
-    fn ln_fixed(value: I64F64) -> Result<I64F64, BondingCurveError> {
-        let value_f64: f64 = value.to_num();
-
-        if value_f64 <= 0.0 {
-            return Err(BondingCurveError::CalculationError(
-                "Cannot take logarithm of non-positive number".into(),
-            ));
-        }
-
-        let result = libm::log(value_f64);
-
-        if !result.is_finite() {
-            return Err(BondingCurveError::CalculationError(
-                "Logarithm calculation resulted in infinite or NaN value".into(),
-            ));
-        }
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        coefficient: T,
+        constant: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(coefficient, constant)?.with_state(token_supply, reserve_balance)
+    }
 
-        Ok(I64F64::from_num(result))
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(
+        coefficient: f64,
+        constant: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::new(coefficient, constant)?.with_initial_supply(initial_supply)
     }
 }
 
-impl BondingCurve for Logarithmic {
-    fn get_price(&self) -> Result<I64F64, BondingCurveError> {
-        let supply_plus_const = self.token_supply + self.constant;
-        if supply_plus_const <= I64F64::from_num(0) {
-            return Err(BondingCurveError::CalculationError(
-                "Invalid supply for logarithm".into(),
-            ));
-        }
-        let ln_result = Self::ln_fixed(supply_plus_const)?;
-        Ok(self.coefficient * ln_result)
-    }
+impl<T: FixedSigned> IntegralCurve for Logarithmic<T> {
+    type Value = T;
 
-    fn buy_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) {
-            return Err(BondingCurveError::InvalidInput(
-                "Token amount must be positive".into(),
-            ));
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        let supply_plus_const =
+            check_domain(supply, "price")?.add_checked(self.constant, "price")?;
+        if supply_plus_const <= T::ZERO {
+            return Err(BondingCurveError::DomainError { operation: "ln" });
         }
-
-        let s_new = self.token_supply + token_amount + self.constant;
-        let s_old = self.token_supply + self.constant;
-
-        let ln_s_new = Self::ln_fixed(s_new)?;
-        let ln_s_old = Self::ln_fixed(s_old)?;
-
-        let cost = self.coefficient * (s_new * ln_s_new - s_new)
-            - self.coefficient * (s_old * ln_s_old - s_old);
-
-        self.token_supply += token_amount;
-        Ok(cost)
+        let ln_result = ln_fixed(supply_plus_const)?;
+        self.coefficient.mul_checked(ln_result, "price")
     }
 
-    fn sell_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) || token_amount > self.token_supply {
-            return Err(BondingCurveError::InvalidInput(
-                "Invalid token amount".into(),
-            ));
-        }
-
-        let s_old = self.token_supply + self.constant;
-        let s_new = self.token_supply - token_amount + self.constant;
-
-        if s_new <= I64F64::from_num(0) {
-            return Err(BondingCurveError::CalculationError(
-                "Cannot sell tokens: would result in invalid supply for logarithm".into(),
-            ));
-        }
-
-        let ln_s_old = Self::ln_fixed(s_old)?;
-        let ln_s_new = Self::ln_fixed(s_new)?;
+    // Antiderivative of the price at `supply`, c * (x * ln(x) - x) with x = S + k, rounded in
+    // `rounding`'s direction by its error bound. ln(x) is within 2 ulps, so x * ln(x) is
+    // within 2x + 1 ulps and the product with c within c * (2x + 1) + 1 ulps.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let x = supply.add_checked(self.constant, "integral")?;
+        let ln_x = ln_fixed(x)?;
+        let x_ln_x = x.mul_checked(ln_x, "integral")?;
+        let value = self
+            .coefficient
+            .mul_checked(x_ln_x.sub_checked(x, "integral")?, "integral")?;
+        let error_ulps = self
+            .coefficient
+            .saturating_mul(x.saturating_add(x).saturating_add(T::from_num(1)));
+        rounding.apply(value, ulps(error_ulps.saturating_add(T::from_num(1))))
+    }
 
-        let refund = self.coefficient * (s_old * ln_s_old - s_old)
-            - self.coefficient * (s_new * ln_s_new - s_new);
+    // x * ln(x) has no closed-form inverse, so there is no estimate to offer and the
+    // solver brackets the root by doubling steps from the supply it starts at
+    fn supply_at_integral(&self, _integral: T) -> Result<T, BondingCurveError> {
+        Ok(T::ZERO)
+    }
 
-        self.token_supply -= token_amount;
-        Ok(refund)
+    // MAX / (4 * max(1, c) * max(1, ln(MAX / 4))) - k
+    fn supply_limit(&self) -> T {
+        let headroom = T::MAX / T::from_num(4);
+        let one = T::from_num(1);
+        let ln_headroom = ln_fixed(headroom).unwrap_or(one).max(one);
+        (headroom / self.coefficient.max(one) / ln_headroom)
+            .saturating_sub(self.constant)
+            .max(T::ZERO)
     }
 
-    fn get_supply(&self) -> I64F64 {
-        self.token_supply
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
     }
 
-    fn get_reserve(&self) -> Option<I64F64> {
-        None
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
     }
 }
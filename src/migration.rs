@@ -0,0 +1,120 @@
+use crate::bonding_curve_trait::BondingCurve;
+use crate::constant_product::ConstantProduct;
+use crate::errors::BondingCurveError;
+use crate::fees::{BPS_DENOMINATOR, fee_for};
+use crate::helpers::{CheckedArithmetic, check_non_negative};
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+// What happens to tokens set aside for the pool but not needed to match the price
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeftoverTokens {
+    Burn,
+    // Returned to the creator or launchpad
+    Return,
+}
+
+#[derive(Clone, Debug)]
+pub struct MigrationConfig {
+    // Share of the curve's reserve kept as a fee instead of seeding the pool
+    pub fee_bps: u16,
+    pub leftover: LeftoverTokens,
+}
+
+// Amounts that seed a constant-product pool from a graduated curve
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Migration<T = I64F64> {
+    // Reserve deposited into the pool
+    pub reserve: T,
+    // Tokens deposited into the pool
+    pub tokens: T,
+    // Reserve kept as the migration fee
+    pub fee: T,
+    // Tokens issued by the curve, which holders may sell into the pool
+    pub circulating_supply: T,
+    // Tokens of the inventory left over after seeding the pool
+    pub leftover_tokens: T,
+    pub leftover: LeftoverTokens,
+}
+
+/*
+ * Seeds a constant-product pool at the final marginal price of a curve:
+ *
+ *   R = reserve - fee
+ *   x = R / P
+ *
+ * where P is the curve's `get_price` and fee = reserve * fee_bps / 10000, rounded up.
+ * The pool then prices at R / x = P. x is rounded down, so the pool never starts below
+ * the curve's last price. Tokens of the inventory beyond x are burned or returned.
+*/
+impl MigrationConfig {
+    pub fn new(fee_bps: u16, leftover: LeftoverTokens) -> Result<Self, BondingCurveError> {
+        if fee_bps >= BPS_DENOMINATOR {
+            return Err(BondingCurveError::invalid_parameter(
+                "fee_bps",
+                f64::from(fee_bps),
+            ));
+        }
+        Ok(MigrationConfig { fee_bps, leftover })
+    }
+
+    // Computes the pool amounts for `curve`, with `token_inventory` tokens available to
+    // pair with its reserve. Fails with `InsufficientSupply` if the inventory cannot
+    // match the curve's price, and with `ZeroAmount` if no reserve is left after the fee.
+    pub fn migrate<T: Fixed, C: BondingCurve<T>>(
+        &self,
+        curve: &C,
+        token_inventory: T,
+    ) -> Result<Migration<T>, BondingCurveError> {
+        let token_inventory = check_non_negative("token_inventory", token_inventory)?;
+        let balance = curve.get_reserve().unwrap_or(T::ZERO);
+        let fee = fee_for(balance, self.fee_bps)?;
+        let reserve = balance - fee;
+        if reserve <= T::ZERO {
+            return Err(BondingCurveError::ZeroAmount);
+        }
+        let tokens = reserve.div_checked(curve.get_price()?, "migration")?;
+        if tokens > token_inventory {
+            return Err(BondingCurveError::insufficient_supply(
+                tokens,
+                token_inventory,
+            ));
+        }
+        Ok(Migration {
+            reserve,
+            tokens,
+            fee,
+            circulating_supply: curve.get_supply(),
+            leftover_tokens: token_inventory - tokens,
+            leftover: self.leftover,
+        })
+    }
+}
+
+impl<T: Fixed> Migration<T> {
+    // Tokens burned by the migration
+    pub fn burned(&self) -> T {
+        match self.leftover {
+            LeftoverTokens::Burn => self.leftover_tokens,
+            LeftoverTokens::Return => T::ZERO,
+        }
+    }
+
+    // Tokens returned by the migration
+    pub fn returned(&self) -> T {
+        match self.leftover {
+            LeftoverTokens::Burn => T::ZERO,
+            LeftoverTokens::Return => self.leftover_tokens,
+        }
+    }
+
+    // The seeded pool, with the curve's circulating supply free to trade into it
+    pub fn pool(&self) -> Result<ConstantProduct<T>, BondingCurveError> {
+        ConstantProduct::from_parameters(
+            self.tokens,
+            T::ZERO,
+            self.circulating_supply,
+            self.reserve,
+        )
+    }
+}
@@ -0,0 +1,146 @@
+use crate::bonding_curve_trait::Rounding;
+use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_positive, exp_fixed, fixed_parameter, ln_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+#[derive(Clone, Debug)]
+pub struct NaturalExponential<T = I64F64> {
+    pub initial_price: T,
+    pub growth_rate: T,
+    pub token_supply: T,
+    pub reserve_balance: T,
+}
+
+/*
+* P = a * e^(b * S)
+* where:
+* a is the initial price, the price at S = 0,
+* b is the growth rate (the price grows by a factor e every 1 / b tokens),
+* S is the token supply.
+*
+* The integral from 0 to S is (a / b) * (e^(bS) - 1) and its inverse is
+* S = ln(1 + I * b / a) / b. The supported supply is bounded by
+* e^(bS) * max(1, a, a / b) <= MAX / 4, which keeps the price, the integral and its inverse
+* representable. See `max_supply`.
+*/
+
+impl<T: Fixed> NaturalExponential<T> {
+    pub fn new(initial_price: f64, growth_rate: f64) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(
+            fixed_parameter("initial_price", initial_price)?,
+            fixed_parameter("growth_rate", growth_rate)?,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(initial_price: T, growth_rate: T) -> Result<Self, BondingCurveError> {
+        let initial_price = check_positive("initial_price", initial_price)?;
+        let growth_rate = check_positive("growth_rate", growth_rate)?;
+        // The integral scales by a / b, which must itself be representable
+        initial_price.checked_div(growth_rate).ok_or_else(|| {
+            BondingCurveError::invalid_parameter("growth_rate", growth_rate.to_num())
+        })?;
+        Ok(Self {
+            initial_price,
+            growth_rate,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
+        })
+    }
+
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        initial_price: T,
+        growth_rate: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(initial_price, growth_rate)?.with_state(token_supply, reserve_balance)
+    }
+
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(
+        initial_price: f64,
+        growth_rate: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::new(initial_price, growth_rate)?.with_initial_supply(initial_supply)
+    }
+
+    // e^(b * S), the factor by which the price has grown at `supply`
+    fn growth(&self, supply: T, operation: &'static str) -> Result<T, BondingCurveError> {
+        let exponent = self.growth_rate.mul_checked(supply, operation)?;
+        exp_fixed(exponent).map_err(|_| BondingCurveError::Overflow { operation })
+    }
+}
+
+impl<T: Fixed> IntegralCurve for NaturalExponential<T> {
+    type Value = T;
+
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        let growth = self.growth(check_domain(supply, "price")?, "price")?;
+        self.initial_price.mul_checked(growth, "price")
+    }
+
+    // Integral of the price from 0 to `supply`, (a / b) * (e^(bS) - 1), rounded in
+    // `rounding`'s direction by its error bound. b * S truncates once, which moves e^(bS)
+    // by at most e^(bS) ulps, and exp adds max(1, e^(bS)) + 1 more (see `exp_fixed`).
+    // a / b and the product each truncate once, so the result is within
+    // (a / b) * (2 * max(1, e^(bS)) + 1) + e^(bS) + 2 ulps.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let one = T::from_num(1);
+        let growth = self.growth(supply, "integral")?;
+        let scale = self.initial_price / self.growth_rate;
+        let value = scale.mul_checked(growth.saturating_sub(one), "integral")?;
+        let error = scale
+            .saturating_mul(
+                growth
+                    .max(one)
+                    .saturating_mul(T::from_num(2))
+                    .saturating_add(one),
+            )
+            .saturating_add(growth)
+            .saturating_add(T::from_num(2));
+        rounding.apply(value, ulps(error))
+    }
+
+    // Closed-form estimate of the supply at which the integral reaches `integral`:
+    // S = ln(1 + I * b / a) / b
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
+        let growth = integral
+            .max(T::ZERO)
+            .mul_checked(self.growth_rate, "integral inverse")?
+            .div_checked(self.initial_price, "integral inverse")?
+            .add_checked(T::from_num(1), "integral inverse")?;
+        ln_fixed(growth)?.div_checked(self.growth_rate, "integral inverse")
+    }
+
+    // ln(MAX / (4 * max(1, a, a / b))) / b
+    fn supply_limit(&self) -> T {
+        let headroom = T::MAX / T::from_num(4);
+        let scale = (self.initial_price / self.growth_rate)
+            .max(self.initial_price)
+            .max(T::from_num(1));
+        ln_fixed(headroom / scale)
+            .map(|exponent| {
+                exponent
+                    .checked_div(self.growth_rate)
+                    .unwrap_or(T::MAX)
+                    .max(T::ZERO)
+            })
+            .unwrap_or(T::ZERO)
+    }
+
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
+    }
+
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
+    }
+}
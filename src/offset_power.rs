@@ -0,0 +1,165 @@
+use crate::bonding_curve_trait::Rounding;
+use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_non_negative, check_positive, fixed_parameter,
+    pow_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+#[derive(Clone, Debug)]
+pub struct OffsetPower<T = I64F64> {
+    pub coefficient: T,
+    pub exponent: T,
+    pub base_price: T,
+    pub token_supply: T,
+    pub reserve_balance: T,
+}
+
+/*
+* P = c * S^n + p0
+* where:
+* c is the coefficient (a scaling factor),
+* S is the token_supply,
+* n is the exponent (determining the curve’s steepness),
+* p0 is the base price, the price of the first token.
+*
+* With p0 = 0 this is `Exponential`. The supported supply is bounded by
+* S^(n+1) * max(1, c) <= MAX / 8 and S * max(1, p0) <= MAX / 8, which keeps both terms of
+* the integral representable. See `max_supply`.
+*/
+
+impl<T: Fixed> OffsetPower<T> {
+    pub fn new(
+        coefficient: f64,
+        exponent: f64,
+        base_price: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(
+            fixed_parameter("coefficient", coefficient)?,
+            fixed_parameter("exponent", exponent)?,
+            fixed_parameter("base_price", base_price)?,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(
+        coefficient: T,
+        exponent: T,
+        base_price: T,
+    ) -> Result<Self, BondingCurveError> {
+        let coefficient = check_positive("coefficient", coefficient)?;
+        let exponent = check_positive("exponent", exponent)?;
+        // The integral divides by n + 1, which must itself be representable
+        exponent
+            .checked_add(T::from_num(1))
+            .ok_or_else(|| BondingCurveError::invalid_parameter("exponent", exponent.to_num()))?;
+        Ok(Self {
+            coefficient,
+            exponent,
+            base_price: check_non_negative("base_price", base_price)?,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
+        })
+    }
+
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        coefficient: T,
+        exponent: T,
+        base_price: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(coefficient, exponent, base_price)?
+            .with_state(token_supply, reserve_balance)
+    }
+
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(
+        coefficient: f64,
+        exponent: f64,
+        base_price: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::new(coefficient, exponent, base_price)?.with_initial_supply(initial_supply)
+    }
+}
+
+impl<T: Fixed> IntegralCurve for OffsetPower<T> {
+    type Value = T;
+
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        let power_result = pow_fixed(check_domain(supply, "price")?, self.exponent)?;
+        self.coefficient
+            .mul_checked(power_result, "price")?
+            .add_checked(self.base_price, "price")
+    }
+
+    // Integral of the price from 0 to `supply`, (c / (n + 1)) * S^(n+1) + p0 * S, rounded in
+    // `rounding`'s direction by its error bound. The power term is bounded as in
+    // `Exponential` and p0 * S truncates once more, so the result is within
+    // (c / (n + 1)) * (max(1, S^(n+1)) * (2n + 4) + 1) + S^(n+1) + 3 ulps.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let one = T::from_num(1);
+        let n_plus_one = self.exponent + one;
+        let supply_power = pow_fixed(supply, n_plus_one)?;
+        let scale = self.coefficient / n_plus_one;
+        let value = scale
+            .mul_checked(supply_power, "integral")?
+            .add_checked(self.base_price.mul_checked(supply, "integral")?, "integral")?;
+        let power_error = ulps(scale.saturating_mul(supply_power.max(one))).saturating_mul(
+            n_plus_one
+                .saturating_mul(T::from_num(2))
+                .saturating_add(T::from_num(2)),
+        );
+        let error = power_error.saturating_add(ulps(
+            scale
+                .saturating_add(supply_power)
+                .saturating_add(T::from_num(3)),
+        ));
+        rounding.apply(value, error)
+    }
+
+    // Estimate of the supply at which the integral reaches `integral`. Each term alone
+    // would reach it at (I * (n + 1) / c)^(1 / (n+1)) or I / p0, and the sum reaches it no
+    // later than the earlier of the two.
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
+        let n_plus_one = self.exponent + T::from_num(1);
+        let base = integral
+            .max(T::ZERO)
+            .mul_checked(n_plus_one, "integral inverse")?
+            .div_checked(self.coefficient, "integral inverse")?;
+        let power_root = pow_fixed(base, T::from_num(1) / n_plus_one)?;
+        if self.base_price <= T::ZERO {
+            return Ok(power_root);
+        }
+        let linear_root = integral
+            .max(T::ZERO)
+            .checked_div(self.base_price)
+            .unwrap_or(T::MAX);
+        Ok(power_root.min(linear_root))
+    }
+
+    // min((MAX / (8 * max(1, c)))^(1 / (n + 1)), MAX / (8 * max(1, p0)))
+    fn supply_limit(&self) -> T {
+        let headroom = T::MAX / T::from_num(8);
+        let n_plus_one = self.exponent + T::from_num(1);
+        let power = pow_fixed(
+            headroom / self.coefficient.max(T::from_num(1)),
+            T::from_num(1) / n_plus_one,
+        )
+        .unwrap_or(T::ZERO);
+        power.min(headroom / self.base_price.max(T::from_num(1)))
+    }
+
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
+    }
+
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
+    }
+}
@@ -0,0 +1,291 @@
+use crate::bonding_curve_trait::Rounding;
+use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, MAX_SOLVER_ITERATIONS, check_domain, check_non_negative, fixed_parameter,
+    pow_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::Fixed;
+use fixed::types::I64F64;
+
+#[derive(Clone, Debug)]
+pub struct Polynomial<T = I64F64> {
+    // a0, a1, ..., an, lowest power first, without trailing zeros
+    pub coefficients: Vec<T>,
+    pub token_supply: T,
+    pub reserve_balance: T,
+}
+
+/*
+* P = a0 + a1 * S + a2 * S^2 + ... + an * S^n
+* where:
+* a0..an are the coefficients, a0 being the price of the first token,
+* S is the token_supply,
+* n is the degree, at most MAX_DEGREE.
+*
+* Coefficients may be negative as long as the price stays non-negative and non-decreasing
+* from 0 to `max_supply`, which `new` checks. The supported supply is bounded by
+* max(1, S)^(n+1) * max(1, |a0| + ... + |an|) <= MAX / 8, which keeps the price, the
+* integral and every partial sum of their Horner evaluation representable. See
+* `max_supply`.
+*/
+
+// Evaluates the polynomial with `coefficients`, lowest power first, at `supply` by
+// Horner's method. Each step truncates once.
+fn horner<T: Fixed>(
+    coefficients: impl DoubleEndedIterator<Item = T>,
+    supply: T,
+    operation: &'static str,
+) -> Result<T, BondingCurveError> {
+    coefficients.rev().try_fold(T::ZERO, |value, coefficient| {
+        value
+            .mul_checked(supply, operation)?
+            .add_checked(coefficient, operation)
+    })
+}
+
+// Lower bound of the polynomial with `coefficients` within `radius` of `center`, from its
+// Taylor expansion q(center) + q'(center) * d + q''(center) / 2 * d^2 + ... with every term
+// past the first taken at its most negative, -|q^(k)(center) / k!| * radius^k. Unlike
+// bounding each power separately, this stays tight next to a double root.
+fn lower_bound<T: Fixed>(coefficients: &[T], center: T, radius: T) -> T {
+    // Taylor shift: afterwards `shifted[k]` is q^(k)(center) / k!
+    let mut shifted = coefficients.to_vec();
+    for start in 0..shifted.len() {
+        for index in (start..shifted.len() - 1).rev() {
+            shifted[index] =
+                shifted[index].saturating_add(center.saturating_mul(shifted[index + 1]));
+        }
+    }
+    let mut bound = shifted[0];
+    let mut power = T::from_num(1);
+    for &term in &shifted[1..] {
+        power = power.saturating_mul(radius);
+        let magnitude = if term < 0 {
+            T::ZERO.saturating_sub(term)
+        } else {
+            term
+        };
+        bound = bound.saturating_sub(magnitude.saturating_mul(power));
+    }
+    bound
+}
+
+impl<T: Fixed> Polynomial<T> {
+    // Highest power of S a curve may have
+    pub const MAX_DEGREE: usize = 8;
+
+    // `coefficients` are a0, a1, ..., an, lowest power first
+    pub fn new(coefficients: &[f64]) -> Result<Self, BondingCurveError> {
+        let coefficients = coefficients
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| fixed_parameter(&format!("coefficients[{index}]"), value))
+            .collect::<Result<Vec<T>, _>>()?;
+        Self::from_parameters(coefficients)
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(mut coefficients: Vec<T>) -> Result<Self, BondingCurveError> {
+        // Trailing zeros do not change the price but would raise the degree
+        while coefficients.last() == Some(&T::ZERO) {
+            coefficients.pop();
+        }
+        // An all-zero or too long vector is reported with its length
+        if coefficients.is_empty() || coefficients.len() > Self::MAX_DEGREE + 1 {
+            return Err(BondingCurveError::invalid_parameter(
+                "coefficients",
+                coefficients.len() as f64,
+            ));
+        }
+        check_non_negative("coefficients[0]", coefficients[0])?;
+        let curve = Polynomial {
+            coefficients,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
+        };
+        curve.check_non_decreasing()?;
+        Ok(curve)
+    }
+
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        coefficients: Vec<T>,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(coefficients)?.with_state(token_supply, reserve_balance)
+    }
+
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(
+        coefficients: &[f64],
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::new(coefficients)?.with_initial_supply(initial_supply)
+    }
+
+    // Highest power of S in the price
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    // Checks that the price does not fall anywhere on 0..=max_supply. With a0 >= 0 this
+    // also keeps it non-negative. The derivative a1 + 2 * a2 * S + ... is bounded from below
+    // on intervals, which are halved until the bound is non-negative on each; an interval
+    // too narrow to halve passes if the derivative is non-negative at both ends. A failure
+    // is reported with the supply at which the price falls, or could not be shown not to.
+    fn check_non_decreasing(&self) -> Result<(), BondingCurveError> {
+        let slopes = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(index, &coefficient)| {
+                T::checked_from_num(index)
+                    .and_then(|power| coefficient.checked_mul(power))
+                    .ok_or_else(|| {
+                        BondingCurveError::invalid_parameter(
+                            &format!("coefficients[{index}]"),
+                            coefficient.to_num(),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+        if slopes.iter().all(|&slope| slope >= 0) {
+            return Ok(());
+        }
+
+        let falls_at =
+            |supply: T| BondingCurveError::invalid_parameter("coefficients", supply.to_num());
+        let mut pending = vec![(T::ZERO, self.supply_limit())];
+        let mut splits = MAX_SOLVER_ITERATIONS * 32;
+        while let Some((low, high)) = pending.pop() {
+            let mid = low + (high - low) / T::from_num(2);
+            if lower_bound(&slopes, mid, high - mid) >= 0 {
+                continue;
+            }
+            for end in [low, high] {
+                if horner(slopes.iter().copied(), end, "price")? < 0 {
+                    return Err(falls_at(end));
+                }
+            }
+            if mid == low {
+                continue;
+            }
+            if splits == 0 {
+                return Err(falls_at(low));
+            }
+            splits -= 1;
+            pending.push((mid, high));
+            pending.push((low, mid));
+        }
+        Ok(())
+    }
+
+    // Integral of the price from 0 to `supply`, S * (a0 + S * (a1 / 2 + S * (a2 / 3 + ...))),
+    // as computed. Each of the n + 1 divisions and n + 2 products truncates once, and the
+    // error of the k-th is scaled by at most max(1, S)^(n+1) on the way out.
+    fn antiderivative(&self, supply: T) -> Result<T, BondingCurveError> {
+        let scaled = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .map(|(index, &coefficient)| coefficient / T::from_num(index + 1));
+        horner(scaled, supply, "integral")?.mul_checked(supply, "integral")
+    }
+}
+
+impl<T: Fixed> IntegralCurve for Polynomial<T> {
+    type Value = T;
+
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        horner(
+            self.coefficients.iter().copied(),
+            check_domain(supply, "price")?,
+            "price",
+        )
+    }
+
+    // `antiderivative` rounded in `rounding`'s direction by its error bound, which is
+    // (2n + 2) * max(1, S)^(n+1) + 1 ulps
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let value = self.antiderivative(supply)?;
+        let base = supply.max(T::from_num(1));
+        let power = (0..=self.degree()).fold(T::from_num(1), |power, _| power.saturating_mul(base));
+        let terms = T::from_num(2 * self.degree() + 2);
+        rounding.apply(
+            value,
+            ulps(power.saturating_mul(terms).saturating_add(T::from_num(1))),
+        )
+    }
+
+    // Estimate of the supply at which the integral reaches `integral`, found by Newton's
+    // method on I(S) - integral, whose derivative is the price. The root stays bracketed
+    // and a step that would leave the bracket, or a zero price, falls back to bisection.
+    // The integral is convex, so from above the root the steps approach it monotonically.
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
+        if integral <= T::ZERO {
+            return Ok(T::ZERO);
+        }
+        let (mut low, mut high) = (T::ZERO, self.supply_limit());
+        if self.antiderivative(high)? <= integral {
+            return Ok(high);
+        }
+
+        let mut supply = high;
+        for _ in 0..MAX_SOLVER_ITERATIONS {
+            let value = self.antiderivative(supply)?;
+            let price = self.price(supply)?;
+            let newton = if value < integral {
+                low = supply;
+                (integral - value)
+                    .checked_div(price)
+                    .and_then(|step| supply.checked_add(step))
+            } else if value > integral {
+                high = supply;
+                (value - integral)
+                    .checked_div(price)
+                    .and_then(|step| supply.checked_sub(step))
+            } else {
+                return Ok(supply);
+            };
+            let next = match newton {
+                Some(next) if next > low && next < high => next,
+                _ => low + (high - low) / T::from_num(2),
+            };
+            if next == supply {
+                break;
+            }
+            supply = next;
+        }
+        Ok(supply)
+    }
+
+    // (MAX / (8 * max(1, |a0| + ... + |an|)))^(1 / (n + 1))
+    fn supply_limit(&self) -> T {
+        let magnitude = self.coefficients.iter().fold(T::ZERO, |sum, &coefficient| {
+            let absolute = if coefficient < 0 {
+                T::ZERO.saturating_sub(coefficient)
+            } else {
+                coefficient
+            };
+            sum.saturating_add(absolute)
+        });
+        let headroom = T::MAX / T::from_num(8);
+        pow_fixed(
+            headroom / magnitude.max(T::from_num(1)),
+            T::from_num(1) / T::from_num(self.degree() + 1),
+        )
+        .unwrap_or(T::ZERO)
+    }
+
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
+    }
+
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
+    }
+}
@@ -0,0 +1,228 @@
+use crate::bonding_curve_trait::BondingCurve;
+use crate::{
+    AffineLinear, Augmented, AugmentedPhase, Bancor, ConstantProduct, Exponential, LaunchCurve,
+    Linear, Logarithmic, NaturalExponential, OffsetPower, Polynomial, Sigmoid,
+};
+use fixed::traits::{Fixed, FixedSigned};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// A fixed-point value encoded as a decimal string such as "2500.0000000000000000002".
+// `fixed` prints as many digits as needed to parse back to the same bits, so the encoding
+// is lossless, unlike a float.
+struct Decimal<T>(T);
+
+impl<T: Fixed> Serialize for Decimal<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de, T: Fixed> Deserialize<'de> for Decimal<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map(Decimal).map_err(D::Error::custom)
+    }
+}
+
+// Implements Serialize and Deserialize for a curve through a struct of its fields as
+// decimal strings. Deserialization rebuilds the curve with `restore`, so stored state
+// goes through the same checks as `new`.
+macro_rules! curve_serde {
+    ($curve:ident, $state:ident, $bound:path, [$($field:ident),+]) => {
+        #[derive(Serialize, Deserialize)]
+        #[serde(bound = "T: Fixed", deny_unknown_fields)]
+        struct $state<T> {
+            $($field: Decimal<T>),+
+        }
+
+        impl<T: $bound> Serialize for $curve<T> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $state {
+                    $($field: Decimal(self.$field)),+
+                }
+                .serialize(serializer)
+            }
+        }
+
+        impl<'de, T: $bound> Deserialize<'de> for $curve<T> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let state = $state::<T>::deserialize(deserializer)?;
+                $curve::restore($(state.$field.0),+).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+curve_serde!(
+    Linear,
+    LinearState,
+    Fixed,
+    [slope, token_supply, reserve_balance]
+);
+curve_serde!(
+    Exponential,
+    ExponentialState,
+    Fixed,
+    [coefficient, exponent, token_supply, reserve_balance]
+);
+curve_serde!(
+    Logarithmic,
+    LogarithmicState,
+    FixedSigned,
+    [coefficient, constant, token_supply, reserve_balance]
+);
+curve_serde!(
+    Sigmoid,
+    SigmoidState,
+    FixedSigned,
+    [
+        max_price,
+        steepness,
+        midpoint,
+        token_supply,
+        reserve_balance
+    ]
+);
+curve_serde!(
+    Bancor,
+    BancorState,
+    Fixed,
+    [reserve_balance, token_supply, connector_weight]
+);
+curve_serde!(
+    NaturalExponential,
+    NaturalExponentialState,
+    Fixed,
+    [initial_price, growth_rate, token_supply, reserve_balance]
+);
+curve_serde!(
+    AffineLinear,
+    AffineLinearState,
+    Fixed,
+    [slope, base_price, token_supply, reserve_balance]
+);
+curve_serde!(
+    OffsetPower,
+    OffsetPowerState,
+    Fixed,
+    [
+        coefficient,
+        exponent,
+        base_price,
+        token_supply,
+        reserve_balance
+    ]
+);
+curve_serde!(
+    ConstantProduct,
+    ConstantProductState,
+    Fixed,
+    [
+        token_balance,
+        virtual_reserve,
+        token_supply,
+        reserve_balance
+    ]
+);
+curve_serde!(
+    LaunchCurve,
+    LaunchCurveState,
+    Fixed,
+    [
+        virtual_token_reserve,
+        virtual_reserve,
+        real_token_reserve,
+        total_supply,
+        token_supply,
+        reserve_balance
+    ]
+);
+
+// The coefficient vector does not fit `curve_serde`, so `Polynomial` is written by hand with
+// the same decimal encoding and checks
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "T: Fixed", deny_unknown_fields)]
+struct PolynomialState<T> {
+    coefficients: Vec<Decimal<T>>,
+    token_supply: Decimal<T>,
+    reserve_balance: Decimal<T>,
+}
+
+impl<T: Fixed> Serialize for Polynomial<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PolynomialState {
+            coefficients: self.coefficients.iter().copied().map(Decimal).collect(),
+            token_supply: Decimal(self.token_supply),
+            reserve_balance: Decimal(self.reserve_balance),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Fixed> Deserialize<'de> for Polynomial<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = PolynomialState::<T>::deserialize(deserializer)?;
+        Polynomial::restore(
+            state
+                .coefficients
+                .into_iter()
+                .map(|value| value.0)
+                .collect(),
+            state.token_supply.0,
+            state.reserve_balance.0,
+        )
+        .map_err(D::Error::custom)
+    }
+}
+
+// The tribute rates and the phase are not fixed-point values, so `Augmented` is written by
+// hand too. Rates stay whole basis points and the phase is its lowercase name.
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "T: Fixed", deny_unknown_fields)]
+struct AugmentedState<T> {
+    hatch_price: Decimal<T>,
+    connector_weight: Decimal<T>,
+    hatch_tribute_bps: u16,
+    entry_tribute_bps: u16,
+    exit_tribute_bps: u16,
+    phase: AugmentedPhase,
+    token_supply: Decimal<T>,
+    reserve_pool: Decimal<T>,
+    funding_pool: Decimal<T>,
+}
+
+impl<T: Fixed> Serialize for Augmented<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AugmentedState {
+            hatch_price: Decimal(self.hatch_price),
+            connector_weight: Decimal(self.connector_weight),
+            hatch_tribute_bps: self.hatch_tribute_bps(),
+            entry_tribute_bps: self.entry_tribute_bps(),
+            exit_tribute_bps: self.exit_tribute_bps(),
+            phase: self.phase(),
+            token_supply: Decimal(self.get_supply()),
+            reserve_pool: Decimal(self.reserve_pool()),
+            funding_pool: Decimal(self.funding_pool()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Fixed> Deserialize<'de> for Augmented<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = AugmentedState::<T>::deserialize(deserializer)?;
+        Augmented::restore(
+            state.hatch_price.0,
+            state.connector_weight.0,
+            state.hatch_tribute_bps,
+            state.entry_tribute_bps,
+            state.exit_tribute_bps,
+            state.phase,
+            state.token_supply.0,
+            state.reserve_pool.0,
+            state.funding_pool.0,
+        )
+        .map_err(D::Error::custom)
+    }
+}
@@ -1,13 +1,20 @@
-use crate::bonding_curve_trait::BondingCurve;
+use crate::bonding_curve_trait::Rounding;
 use crate::errors::BondingCurveError;
+use crate::helpers::{
+    CheckedArithmetic, check_domain, check_non_negative, check_positive, exp_fixed,
+    fixed_parameter, floor_supply, ln_fixed, ulps,
+};
+use crate::integral_curve::IntegralCurve;
+use fixed::traits::FixedSigned;
 use fixed::types::I64F64;
 
 #[derive(Clone, Debug)]
-pub struct Sigmoid {
-    pub max_price: I64F64,
-    pub steepness: I64F64,
-    pub midpoint: I64F64,
-    pub token_supply: I64F64,
+pub struct Sigmoid<T = I64F64> {
+    pub max_price: T,
+    pub steepness: T,
+    pub midpoint: T,
+    pub token_supply: T,
+    pub reserve_balance: T,
 }
 
 /*
@@ -17,122 +24,158 @@ pub struct Sigmoid {
 * k is steepness,
 * m is midpoint,
 * S is token_supply.
+*
+* The supported supply is bounded by (S + M / k) * max(1, M, k) <= MAX / 4, which keeps
+* k(S - m) and the integral representable. See `max_supply`.
 */
 
-impl Sigmoid {
+impl<T: FixedSigned> Sigmoid<T> {
     pub fn new(max_price: f64, steepness: f64, midpoint: f64) -> Result<Self, BondingCurveError> {
-        if max_price <= 0.0
-            || steepness <= 0.0
-            || midpoint < 0.0
-            || !max_price.is_finite()
-            || !steepness.is_finite()
-            || !midpoint.is_finite()
-        {
-            return Err(BondingCurveError::InvalidInput("Invalid parameters".into()));
-        }
+        Self::from_parameters(
+            fixed_parameter("max_price", max_price)?,
+            fixed_parameter("steepness", steepness)?,
+            fixed_parameter("midpoint", midpoint)?,
+        )
+    }
+
+    // Applies the parameter checks of `new` to values already in the fixed-point type
+    fn from_parameters(max_price: T, steepness: T, midpoint: T) -> Result<Self, BondingCurveError> {
+        let max_price = check_positive("max_price", max_price)?;
+        let steepness = check_positive("steepness", steepness)?;
+        // The integral scales by M / k, which must itself be representable
+        max_price
+            .checked_div(steepness)
+            .filter(|scale| *scale > T::ZERO)
+            .ok_or_else(|| BondingCurveError::invalid_parameter("steepness", steepness.to_num()))?;
         Ok(Sigmoid {
-            max_price: I64F64::from_num(max_price),
-            steepness: I64F64::from_num(steepness),
-            midpoint: I64F64::from_num(midpoint),
-            token_supply: I64F64::from_num(0),
+            max_price,
+            steepness,
+            midpoint: check_non_negative("midpoint", midpoint)?,
+            token_supply: T::ZERO,
+            reserve_balance: T::ZERO,
         })
     }
 
-    // Helper function to compute exponential using libm
-    fn exp_fixed(value: I64F64) -> Result<I64F64, BondingCurveError> {
-        let value_f64: f64 = value.to_num();
-        let result = libm::exp(value_f64);
-
-        if !result.is_finite() {
-            return Err(BondingCurveError::CalculationError(
-                "Exponential calculation resulted in infinite or NaN value".into(),
-            ));
-        }
-
-        Ok(I64F64::from_num(result))
+    #[cfg(any(feature = "serde", feature = "borsh"))]
+    pub(crate) fn restore(
+        max_price: T,
+        steepness: T,
+        midpoint: T,
+        token_supply: T,
+        reserve_balance: T,
+    ) -> Result<Self, BondingCurveError> {
+        Self::from_parameters(max_price, steepness, midpoint)?
+            .with_state(token_supply, reserve_balance)
     }
 
-    // Helper function to compute natural logarithm using libm
-    fn ln_fixed(value: I64F64) -> Result<I64F64, BondingCurveError> {
-        let value_f64: f64 = value.to_num();
-
-        if value_f64 <= 0.0 {
-            return Err(BondingCurveError::CalculationError(
-                "Cannot take logarithm of non-positive number".into(),
-            ));
-        }
-
-        let result = libm::log(value_f64);
+    // Creates a curve that has already issued `initial_supply` tokens
+    pub fn with_supply(
+        max_price: f64,
+        steepness: f64,
+        midpoint: f64,
+        initial_supply: f64,
+    ) -> Result<Self, BondingCurveError> {
+        Self::new(max_price, steepness, midpoint)?.with_initial_supply(initial_supply)
+    }
 
-        if !result.is_finite() {
-            return Err(BondingCurveError::CalculationError(
-                "Logarithm calculation resulted in infinite or NaN value".into(),
-            ));
+    // k(S - m), or None when it lies far enough below zero to overflow
+    fn scaled_offset(&self, supply: T) -> Result<Option<T>, BondingCurveError> {
+        let offset = supply.sub_checked(self.midpoint, "integral")?;
+        match self.steepness.checked_mul(offset) {
+            Some(z) => Ok(Some(z)),
+            None if offset < T::ZERO => Ok(None),
+            None => Err(BondingCurveError::Overflow {
+                operation: "integral",
+            }),
         }
-
-        Ok(I64F64::from_num(result))
     }
 }
 
-impl BondingCurve for Sigmoid {
-    fn get_price(&self) -> Result<I64F64, BondingCurveError> {
-        let exponent = -self.steepness * (self.token_supply - self.midpoint);
-        let exp_result = Self::exp_fixed(exponent)?;
-        let denominator = I64F64::from_num(1) + exp_result;
-        Ok(self.max_price / denominator)
-    }
-
-    fn buy_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) {
-            return Err(BondingCurveError::InvalidInput(
-                "Token amount must be positive".into(),
-            ));
+impl<T: FixedSigned> IntegralCurve for Sigmoid<T> {
+    type Value = T;
+
+    fn price(&self, supply: T) -> Result<T, BondingCurveError> {
+        let one = T::from_num(1);
+        let offset = check_domain(supply, "price")? - self.midpoint;
+        // Evaluated on the side where e^-|z| cannot overflow; a z too large to represent
+        // saturates the price at 0 or M
+        match self.steepness.checked_mul(offset) {
+            None if offset < T::ZERO => Ok(T::ZERO),
+            None => Ok(self.max_price),
+            Some(z) if z >= T::ZERO => Ok(self.max_price / (one + exp_fixed(-z)?)),
+            Some(z) => {
+                let exp_z = exp_fixed(z)?;
+                Ok(self.max_price.mul_checked(exp_z, "price")? / (one + exp_z))
+            }
         }
-
-        let k = self.steepness;
-        let s_new = self.token_supply + token_amount - self.midpoint;
-        let s_old = self.token_supply - self.midpoint;
-
-        let exp_k_s_new = Self::exp_fixed(k * s_new)?;
-        let exp_k_s_old = Self::exp_fixed(k * s_old)?;
-
-        let ln_term_new = Self::ln_fixed(I64F64::from_num(1) + exp_k_s_new)?;
-        let ln_term_old = Self::ln_fixed(I64F64::from_num(1) + exp_k_s_old)?;
-
-        let cost = (self.max_price / k) * ln_term_new - (self.max_price / k) * ln_term_old;
-
-        self.token_supply += token_amount;
-        Ok(cost)
     }
 
-    fn sell_token(&mut self, token_amount: I64F64) -> Result<I64F64, BondingCurveError> {
-        if token_amount <= I64F64::from_num(0) || token_amount > self.token_supply {
-            return Err(BondingCurveError::InvalidInput(
-                "Invalid token amount".into(),
-            ));
-        }
+    // Antiderivative of the price at `supply`, (M / k) * ln(1 + e^(k(S - m))), rounded in
+    // `rounding`'s direction by its error bound. For z > 0 the softplus is evaluated as
+    // z + ln(1 + e^-z) so e^z never overflows. The softplus is within 6 ulps and at most
+    // kS + 1, so with M / k truncated once the product is within 6M / k + kS + 2 ulps.
+    fn integral(&self, supply: T, rounding: Rounding) -> Result<T, BondingCurveError> {
+        let one = T::from_num(1);
+        let softplus = match self.scaled_offset(supply)? {
+            None => T::ZERO,
+            Some(z) if z > T::ZERO => {
+                let tail = ln_fixed(one + exp_fixed(-z)?)?;
+                z.add_checked(tail, "integral")?
+            }
+            Some(z) => ln_fixed(one + exp_fixed(z)?)?,
+        };
+        let scale = self.max_price / self.steepness;
+        let value = scale.mul_checked(softplus, "integral")?;
+        let error_ulps = scale
+            .saturating_mul(T::from_num(6))
+            .saturating_add(self.steepness.saturating_mul(supply))
+            .saturating_add(T::from_num(2));
+        rounding.apply(value, ulps(error_ulps))
+    }
 
+    // Closed-form estimate of the supply at which the antiderivative reaches `integral`,
+    // using the softplus inverse ln(e^u - 1) = u + ln(1 - e^-u) with u = I * k / M
+    fn supply_at_integral(&self, integral: T) -> Result<T, BondingCurveError> {
         let k = self.steepness;
-        let s_old = self.token_supply - self.midpoint;
-        let s_new = self.token_supply - token_amount - self.midpoint;
-
-        let exp_k_s_old = Self::exp_fixed(k * s_old)?;
-        let exp_k_s_new = Self::exp_fixed(k * s_new)?;
-
-        let ln_term_old = Self::ln_fixed(I64F64::from_num(1) + exp_k_s_old)?;
-        let ln_term_new = Self::ln_fixed(I64F64::from_num(1) + exp_k_s_new)?;
+        let u = (integral / self.max_price).mul_checked(k, "integral inverse")?;
+        if u <= T::ZERO {
+            return Err(BondingCurveError::DomainError {
+                operation: "softplus inverse",
+            });
+        }
+        let exp_neg_u = exp_fixed(-u)?;
+        let softplus_inverse = u + ln_fixed(T::from_num(1) - exp_neg_u)?;
+        self.midpoint
+            .add_checked(softplus_inverse / k, "integral inverse")
+    }
 
-        let refund = (self.max_price / k) * ln_term_old - (self.max_price / k) * ln_term_new;
+    // Targets too close to the antiderivative at `lower` for the softplus inverse to
+    // resolve cover no further tokens
+    fn supply_covered_by(&self, target: T, lower: T) -> Result<T, BondingCurveError> {
+        floor_supply(
+            lower,
+            target,
+            || match self.supply_at_integral(target) {
+                Err(BondingCurveError::DomainError { .. }) => Ok(lower),
+                estimate => estimate,
+            },
+            |supply| self.integral(supply, Rounding::Up),
+        )
+    }
 
-        self.token_supply -= token_amount;
-        Ok(refund)
+    // (MAX / 4 - M / k) / max(1, M, k)
+    fn supply_limit(&self) -> T {
+        let headroom = T::MAX / T::from_num(4);
+        let scale = self.max_price.max(self.steepness).max(T::from_num(1));
+        headroom.saturating_sub(self.max_price / self.steepness) / scale
     }
 
-    fn get_supply(&self) -> I64F64 {
-        self.token_supply
+    fn state(&self) -> (T, T) {
+        (self.token_supply, self.reserve_balance)
     }
 
-    fn get_reserve(&self) -> Option<I64F64> {
-        None
+    fn set_state(&mut self, token_supply: T, reserve_balance: T) {
+        self.token_supply = token_supply;
+        self.reserve_balance = reserve_balance;
     }
 }
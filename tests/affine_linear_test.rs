@@ -0,0 +1,164 @@
+use bonding_curves::{AffineLinear, BondingCurve, BondingCurveError, Linear};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+// Integral of 0.01 * S + 1 from 0 to `supply`
+fn integral(supply: f64) -> f64 {
+    0.005 * supply * supply + supply
+}
+
+#[test]
+fn test_affine_linear_new_valid() {
+    let curve = AffineLinear::new(0.01, 1.0).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    // The first token costs the base price rather than nothing
+    assert_eq!(curve.get_price().unwrap(), I64F64::from_num(1));
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+}
+
+#[test]
+fn test_affine_linear_new_invalid() {
+    let result = AffineLinear::<I64F64>::new(0.0, 1.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "slope"
+    ));
+
+    let result = AffineLinear::<I64F64>::new(0.01, -1.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "base_price"
+    ));
+
+    let result = AffineLinear::<I64F64>::new(0.01, f64::INFINITY);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "base_price"
+    ));
+}
+
+#[test]
+fn test_affine_linear_buy_and_sell() {
+    let mut curve = AffineLinear::new(0.01, 1.0).unwrap();
+
+    // Cost = 0.005 * 100^2 + 1 * 100
+    let cost = curve.buy_token(I64F64::from_num(100)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(150),
+        I64F64::from_num(0.000001),
+        "Cost for 100 tokens",
+    );
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        I64F64::from_num(2),
+        I64F64::from_num(0.000001),
+        "Price after 100 tokens",
+    );
+
+    let refund = curve.sell_token(I64F64::from_num(50)).unwrap();
+    assert_approx_eq(
+        refund,
+        I64F64::from_num(integral(100.0) - integral(50.0)),
+        I64F64::from_num(0.000001),
+        "Refund for 50 tokens",
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(50));
+
+    assert!(matches!(
+        curve.sell_token(I64F64::from_num(51)),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+    assert!(matches!(
+        curve.buy_token(I64F64::from_num(0)),
+        Err(BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_affine_linear_buy_with_reserve() {
+    let mut curve = AffineLinear::new(0.01, 1.0).unwrap();
+    curve.buy_token(I64F64::from_num(20)).unwrap();
+
+    // 0.005 * S'^2 + S' = I(20) + 40
+    let tokens = curve.buy_with_reserve(I64F64::from_num(40)).unwrap();
+    let target = integral(20.0) + 40.0;
+    let expected_supply = (-1.0 + (1.0 + 0.02 * target).sqrt()) / 0.01;
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(expected_supply - 20.0),
+        I64F64::from_num(0.000001),
+        "Tokens for 40 reserve",
+    );
+
+    // Selling for the refund of 10 tokens sells 10 tokens
+    let refund = curve.quote_sell(I64F64::from_num(10)).unwrap();
+    assert_approx_eq(
+        curve.sell_for_reserve(refund).unwrap(),
+        I64F64::from_num(10),
+        I64F64::from_num(0.000001),
+        "Tokens sold",
+    );
+}
+
+#[test]
+fn test_affine_linear_large_base_price() {
+    // A base price whose square does not fit still solves for the supply a reserve buys
+    let mut curve = AffineLinear::<I64F64>::new(1.0, 1e10).unwrap();
+    let tokens = curve.buy_with_reserve(I64F64::from_num(5e10)).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(5),
+        I64F64::from_num(0.000001),
+        "Tokens at a high base price",
+    );
+}
+
+#[test]
+fn test_affine_linear_without_base_price_matches_linear() {
+    let mut affine = AffineLinear::new(0.01, 0.0).unwrap();
+    let mut linear = Linear::new(0.01).unwrap();
+    let amount = I64F64::from_num(75);
+    assert_approx_eq(
+        affine.buy_token(amount).unwrap(),
+        linear.buy_token(amount).unwrap(),
+        I64F64::from_num(0.000001),
+        "Cost",
+    );
+    let reserve = I64F64::from_num(10);
+    assert_approx_eq(
+        affine.buy_with_reserve(reserve).unwrap(),
+        linear.buy_with_reserve(reserve).unwrap(),
+        I64F64::from_num(0.000001),
+        "Tokens",
+    );
+}
+
+#[test]
+fn test_affine_linear_with_supply() {
+    let seeded = AffineLinear::<I64F64>::with_supply(0.01, 1.0, 100.0).unwrap();
+    assert_eq!(seeded.get_supply(), I64F64::from_num(100));
+    assert_approx_eq(
+        seeded.get_reserve().unwrap(),
+        I64F64::from_num(150),
+        I64F64::from_num(0.000001),
+        "Seeded reserve",
+    );
+
+    let max_supply = seeded.max_supply().to_num::<f64>();
+    let result = AffineLinear::<I64F64>::with_supply(0.01, 1.0, max_supply * 2.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+}
@@ -0,0 +1,177 @@
+use bonding_curves::{
+    AffineLinear, AnyCurve, Augmented, Bancor, BondingCurve, ConstantProduct, CurveKind,
+    Exponential, LaunchCurve, Linear, Logarithmic, NaturalExponential, OffsetPower, Polynomial,
+    Sigmoid,
+};
+use fixed::types::I64F64;
+
+// An augmented curve past its hatch, so that it can be sold into
+fn open_augmented() -> Augmented {
+    let mut curve = Augmented::new(0.01, 0.5, 2000, 500, 1000).unwrap();
+    curve.buy_with_reserve(I64F64::from_num(1000)).unwrap();
+    curve.open().unwrap();
+    curve
+}
+
+fn all_curves() -> Vec<AnyCurve> {
+    vec![
+        Linear::with_supply(0.01, 100.0).unwrap().into(),
+        Exponential::with_supply(0.001, 2.0, 10.0).unwrap().into(),
+        Logarithmic::with_supply(10.0, 1.0, 100.0).unwrap().into(),
+        Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap().into(),
+        Bancor::new(1000, 10000, 0.5).unwrap().into(),
+        NaturalExponential::with_supply(0.5, 0.01, 100.0)
+            .unwrap()
+            .into(),
+        AffineLinear::with_supply(0.01, 1.0, 100.0).unwrap().into(),
+        OffsetPower::with_supply(0.001, 2.0, 0.5, 10.0)
+            .unwrap()
+            .into(),
+        Polynomial::with_supply(&[1.0, 0.02, 0.0003], 100.0)
+            .unwrap()
+            .into(),
+        ConstantProduct::with_supply(10000.0, 100.0, 50.0, 100.0)
+            .unwrap()
+            .into(),
+        LaunchCurve::with_supply(1073.0, 30.0, 793.1, 1000.0, 100.0)
+            .unwrap()
+            .into(),
+        open_augmented().into(),
+    ]
+}
+
+// Runs the same trades on `curve` and on `any`, which must wrap an identical curve
+fn check_dispatch<C: BondingCurve + Clone>(mut curve: C, mut any: AnyCurve) {
+    let amount = I64F64::from_num(3);
+    let name = any.kind().name();
+    assert_eq!(any.get_price(), curve.get_price(), "{name}: price");
+    assert_eq!(any.quote_buy(amount), curve.quote_buy(amount), "{name}");
+    assert_eq!(any.quote_sell(amount), curve.quote_sell(amount), "{name}");
+    assert_eq!(any.max_supply(), curve.max_supply(), "{name}");
+    assert_eq!(any.buys_with_reserve(), curve.buys_with_reserve(), "{name}");
+
+    assert_eq!(any.buy_token(amount), curve.buy_token(amount), "{name}");
+    assert_eq!(
+        any.buy_with_reserve(amount),
+        curve.buy_with_reserve(amount),
+        "{name}"
+    );
+    assert_eq!(any.sell_token(amount), curve.sell_token(amount), "{name}");
+    assert_eq!(
+        any.sell_for_reserve(amount),
+        curve.sell_for_reserve(amount),
+        "{name}"
+    );
+    assert_eq!(any.get_supply(), curve.get_supply(), "{name}: supply");
+    assert_eq!(any.get_reserve(), curve.get_reserve(), "{name}: reserve");
+}
+
+#[test]
+fn test_any_curve_dispatches_to_wrapped_curve() {
+    let linear = Linear::with_supply(0.01, 100.0).unwrap();
+    check_dispatch(linear.clone(), linear.into());
+    let exponential = Exponential::with_supply(0.001, 2.0, 10.0).unwrap();
+    check_dispatch(exponential.clone(), exponential.into());
+    let logarithmic = Logarithmic::with_supply(10.0, 1.0, 100.0).unwrap();
+    check_dispatch(logarithmic.clone(), logarithmic.into());
+    let sigmoid = Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    check_dispatch(sigmoid.clone(), sigmoid.into());
+    let bancor = Bancor::new(1000, 10000, 0.5).unwrap();
+    check_dispatch(bancor.clone(), bancor.into());
+    let natural = NaturalExponential::with_supply(0.5, 0.01, 100.0).unwrap();
+    check_dispatch(natural.clone(), natural.into());
+    let affine = AffineLinear::with_supply(0.01, 1.0, 100.0).unwrap();
+    check_dispatch(affine.clone(), affine.into());
+    let offset = OffsetPower::with_supply(0.001, 2.0, 0.5, 10.0).unwrap();
+    check_dispatch(offset.clone(), offset.into());
+    let polynomial = Polynomial::with_supply(&[1.0, 0.02, 0.0003], 100.0).unwrap();
+    check_dispatch(polynomial.clone(), polynomial.into());
+    let pool = ConstantProduct::with_supply(10000.0, 100.0, 50.0, 100.0).unwrap();
+    check_dispatch(pool.clone(), pool.into());
+    let launch = LaunchCurve::with_supply(1073.0, 30.0, 793.1, 1000.0, 100.0).unwrap();
+    check_dispatch(launch.clone(), launch.into());
+    let augmented = open_augmented();
+    check_dispatch(augmented.clone(), augmented.into());
+}
+
+#[test]
+fn test_any_curve_kind() {
+    let kinds: Vec<CurveKind> = all_curves().iter().map(AnyCurve::kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            CurveKind::Linear,
+            CurveKind::Exponential,
+            CurveKind::Logarithmic,
+            CurveKind::Sigmoid,
+            CurveKind::Bancor,
+            CurveKind::NaturalExponential,
+            CurveKind::AffineLinear,
+            CurveKind::OffsetPower,
+            CurveKind::Polynomial,
+            CurveKind::ConstantProduct,
+            CurveKind::LaunchCurve,
+            CurveKind::Augmented,
+        ]
+    );
+    assert_eq!(CurveKind::Sigmoid.name(), "sigmoid");
+    assert_eq!(CurveKind::NaturalExponential.name(), "natural_exponential");
+}
+
+#[test]
+fn test_any_curve_parameters() {
+    let curve: AnyCurve = Sigmoid::new(100.0, 0.1, 50.0).unwrap().into();
+    let names: Vec<String> = curve
+        .parameters()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(names, ["max_price", "steepness", "midpoint"]);
+    assert_eq!(curve.parameter("midpoint"), Some(I64F64::from_num(50)));
+    assert_eq!(curve.parameter("slope"), None);
+
+    let curve: AnyCurve = Bancor::new(1000, 10000, 0.5).unwrap().into();
+    assert_eq!(
+        curve.parameters(),
+        [("connector_weight".to_string(), I64F64::from_num(0.5))]
+    );
+
+    // Tribute rates are reported in basis points alongside the price parameters
+    let curve: AnyCurve = open_augmented().into();
+    assert_eq!(curve.parameter("hatch_price"), Some(I64F64::from_num(0.01)));
+    assert_eq!(
+        curve.parameter("hatch_tribute_bps"),
+        Some(I64F64::from_num(2000))
+    );
+    assert_eq!(
+        curve.parameter("entry_tribute_bps"),
+        Some(I64F64::from_num(500))
+    );
+    assert_eq!(
+        curve.parameter("exit_tribute_bps"),
+        Some(I64F64::from_num(1000))
+    );
+
+    // Every curve reports at least one parameter and no state
+    for curve in all_curves() {
+        let parameters = curve.parameters();
+        assert!(!parameters.is_empty(), "{}", curve.kind().name());
+        assert!(curve.parameter("token_supply").is_none());
+    }
+}
+
+#[test]
+fn test_any_curve_clones_independently() {
+    let mut curves = all_curves();
+    let snapshot = curves.clone();
+    for curve in &mut curves {
+        curve.buy_exact_tokens(I64F64::from_num(1)).unwrap();
+    }
+    for (curve, before) in curves.iter().zip(&snapshot) {
+        assert!(
+            curve.get_supply() > before.get_supply(),
+            "{:?}",
+            curve.kind()
+        );
+    }
+}
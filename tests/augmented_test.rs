@@ -0,0 +1,222 @@
+use bonding_curves::{Augmented, AugmentedPhase, Bancor, BondingCurve, BondingCurveError};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn assert_invalid<T: std::fmt::Debug>(result: Result<T, BondingCurveError>, field: &str) {
+    assert!(
+        matches!(
+            &result,
+            Err(BondingCurveError::InvalidParameter { name, .. }) if name == field
+        ),
+        "expected an invalid {field}, got {result:?}"
+    );
+}
+
+fn num(value: f64) -> I64F64 {
+    I64F64::from_num(value)
+}
+
+// Hatch at 0.01 with a 20% hatch tribute, then a 0.5 weight, 5% entry and 10% exit tributes
+fn hatched() -> Augmented {
+    let mut curve = Augmented::new(0.01, 0.5, 2000, 500, 1000).unwrap();
+    curve.buy_with_reserve(num(1000.0)).unwrap();
+    curve
+}
+
+#[test]
+fn test_augmented_new_invalid() {
+    assert_invalid(Augmented::<I64F64>::new(0.0, 0.5, 0, 0, 0), "hatch_price");
+    assert_invalid(
+        Augmented::<I64F64>::new(0.01, 1.5, 0, 0, 0),
+        "connector_weight",
+    );
+    assert_invalid(
+        Augmented::<I64F64>::new(0.01, 0.5, 10000, 0, 0),
+        "hatch_tribute_bps",
+    );
+    assert_invalid(
+        Augmented::<I64F64>::new(0.01, 0.5, 0, 0, 10000),
+        "exit_tribute_bps",
+    );
+}
+
+#[test]
+fn test_augmented_hatch() {
+    let mut curve = hatched();
+    assert_eq!(curve.phase(), AugmentedPhase::Hatch);
+    assert_eq!(curve.get_price().unwrap(), num(0.01));
+
+    // 1000 reserve buys 100000 tokens at the hatch price, 20% of it funds the project
+    assert_approx_eq(curve.get_supply(), num(100_000.0), num(1e-9), "Supply");
+    assert_eq!(curve.reserve_pool(), num(800.0));
+    assert_eq!(curve.funding_pool(), num(200.0));
+    assert_eq!(curve.get_reserve(), Some(num(800.0)));
+
+    let cost = curve.buy_exact_tokens(num(50_000.0)).unwrap();
+    assert_approx_eq(cost, num(500.0), num(1e-12), "Hatch cost");
+    assert_approx_eq(curve.funding_pool(), num(300.0), num(1e-12), "Funding");
+
+    // Nothing can be sold before the curve opens
+    assert_eq!(
+        curve.sell_token(num(1.0)),
+        Err(BondingCurveError::WrongPhase {
+            operation: "sell",
+            phase: "hatch",
+        })
+    );
+    assert!(matches!(
+        curve.quote_sell_for_reserve(num(1.0)),
+        Err(BondingCurveError::WrongPhase { .. })
+    ));
+}
+
+#[test]
+fn test_augmented_open_transition() {
+    let mut curve = hatched();
+    curve.open().unwrap();
+    assert_eq!(curve.phase(), AugmentedPhase::Open);
+
+    // The curve opens at (1 - 0.2) * 0.01 / 0.5
+    assert_approx_eq(curve.get_price().unwrap(), num(0.016), num(1e-12), "Price");
+    assert_eq!(curve.reserve_pool(), num(800.0));
+    assert_eq!(
+        curve.open(),
+        Err(BondingCurveError::WrongPhase {
+            operation: "open",
+            phase: "open",
+        })
+    );
+}
+
+#[test]
+fn test_augmented_open_requires_hatch_contributions() {
+    let mut curve = Augmented::<I64F64>::new(0.01, 0.5, 2000, 500, 1000).unwrap();
+    assert_invalid(curve.open(), "token_supply");
+    assert_eq!(curve.phase(), AugmentedPhase::Hatch);
+
+    curve.buy_exact_tokens(num(100.0)).unwrap();
+    curve.open().unwrap();
+    assert_eq!(curve.phase(), AugmentedPhase::Open);
+}
+
+#[test]
+fn test_augmented_entry_tribute() {
+    let mut curve = hatched();
+    curve.open().unwrap();
+    let mut bancor = Bancor::new(800, 100_000, 0.5).unwrap();
+
+    // 5% of the 100 paid in is funding, the curve receives the other 95
+    let tokens = curve.buy_with_reserve(num(100.0)).unwrap();
+    let expected = bancor.buy_with_reserve(num(95.0)).unwrap();
+    assert_approx_eq(tokens, expected, num(1e-9), "Tokens");
+    assert_eq!(curve.reserve_pool(), num(895.0));
+    assert_eq!(curve.funding_pool(), num(205.0));
+
+    // Exact-token buys pay the curve's cost grossed up by the tribute
+    let cost = bancor.quote_buy_exact_tokens(num(1000.0)).unwrap();
+    let paid = curve.buy_exact_tokens(num(1000.0)).unwrap();
+    assert_approx_eq(paid, cost / num(0.95), num(1e-12), "Grossed-up cost");
+}
+
+#[test]
+fn test_augmented_exit_tribute() {
+    let mut curve = hatched();
+    curve.open().unwrap();
+    let bancor = Bancor::new(800, 100_000, 0.5).unwrap();
+
+    // The reserve pool pays the full refund, 10% of which goes to the funding pool
+    let refund = bancor.quote_sell(num(10_000.0)).unwrap();
+    let received = curve.sell_token(num(10_000.0)).unwrap();
+    assert_approx_eq(received, refund * num(0.9), num(1e-12), "Received");
+    assert_approx_eq(
+        curve.reserve_pool(),
+        num(800.0) - refund,
+        num(1e-9),
+        "Reserve",
+    );
+    assert_eq!(
+        curve.reserve_pool() + curve.funding_pool() + received,
+        num(1000.0)
+    );
+
+    // Selling for a reserve amount delivers that amount after the tribute
+    let (supply, reserve, funding) = (
+        curve.get_supply(),
+        curve.reserve_pool(),
+        curve.funding_pool(),
+    );
+    let tokens = curve.sell_for_reserve(num(9.0)).unwrap();
+    assert_eq!(curve.get_supply(), supply - tokens);
+    assert_approx_eq(
+        reserve - curve.reserve_pool(),
+        num(10.0),
+        num(1e-12),
+        "Gross",
+    );
+    assert_approx_eq(
+        curve.funding_pool() - funding,
+        num(1.0),
+        num(1e-12),
+        "Tribute",
+    );
+}
+
+#[test]
+fn test_augmented_funding_pool_is_never_sold_into() {
+    let mut curve = hatched();
+    curve.open().unwrap();
+
+    // Selling all but one token drains the reserve pool only
+    let supply = curve.get_supply();
+    curve.sell_token(supply - num(1.0)).unwrap();
+    assert_eq!(curve.get_supply(), num(1.0));
+    assert!(curve.funding_pool() >= num(200.0));
+
+    let funding = curve.funding_pool();
+    assert_eq!(curve.withdraw_funding(), funding);
+    assert_eq!(curve.funding_pool(), num(0.0));
+}
+
+#[test]
+fn test_augmented_cannot_sell_out() {
+    // A 10% hatch tribute opens the curve at 0.9 * 1.0 / 0.5 = 1.8
+    let mut curve = Augmented::<I64F64>::new(1.0, 0.5, 1000, 500, 1000).unwrap();
+    curve.buy_with_reserve(num(100.0)).unwrap();
+    curve.open().unwrap();
+    assert_approx_eq(curve.get_price().unwrap(), num(1.8), num(1e-12), "Price");
+
+    // Selling out would leave an empty Bancor pool priced far below the hatch
+    let supply = curve.get_supply();
+    assert!(matches!(
+        curve.sell_token(supply),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+    // 81 after the 10% exit tribute is the whole reserve pool of 90
+    assert!(matches!(
+        curve.sell_for_reserve(num(81.0)),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+    assert_eq!(curve.get_supply(), supply);
+    assert_eq!(curve.reserve_pool(), num(90.0));
+
+    // The next buy still trades on the curve the hatch opened
+    let bancor = Bancor::new(90, 100, 0.5).unwrap();
+    let tokens = curve.buy_with_reserve(num(1.0)).unwrap();
+    assert_approx_eq(
+        tokens,
+        bancor.quote_buy(num(0.95)).unwrap(),
+        num(1e-12),
+        "Tokens",
+    );
+    assert!(tokens < num(0.95 / 1.8));
+}
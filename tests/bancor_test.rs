@@ -22,41 +22,41 @@ fn test_bancor_valid() {
 
 #[test]
 fn test_bancor_zero_supply_nonzero_reserve() {
-    let result = Bancor::new(500, 0, 0.2);
+    let result = Bancor::<I64F64>::new(500, 0, 0.2);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("zero token supply")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "reserve_balance"
     ))
 }
 
 #[test]
 fn test_bancor_zero_reserve_nonzero_supply() {
-    let result = Bancor::new(0, 1000, 0.2);
+    let result = Bancor::<I64F64>::new(0, 1000, 0.2);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("zero reserve with non-zero token supply")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "reserve_balance"
     ));
 }
 
 #[test]
 fn test_bancor_zero_state() {
-    let curve = Bancor::new(0, 0, 0.2).unwrap();
+    let curve = Bancor::<I64F64>::new(0, 0, 0.2).unwrap();
     assert_eq!(curve.get_supply(), I64F64::from_num(0));
     assert_eq!(curve.get_reserve().unwrap(), I64F64::from_num(0));
 }
 
 #[test]
 fn test_bancor_negative_values() {
-    let result = Bancor::new(-100, 10000, 0.2);
+    let result = Bancor::<I64F64>::new(-100, 10000, 0.2);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("non-negative")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "reserve_balance"
     ));
 
-    let result = Bancor::new(1000, -100, 0.2);
+    let result = Bancor::<I64F64>::new(1000, -100, 0.2);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("non-negative")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "token_supply"
     ));
 }
 
@@ -64,16 +64,20 @@ fn test_bancor_negative_values() {
 fn test_bancor_buy_tokens() {
     let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
     let tokens = curve.buy_token(I64F64::from_num(100)).unwrap();
-    let expected_tokens = I64F64::from_num(200);
+    // S * ((1 + ΔR / R)^cw - 1) = 10000 * (1.1^0.2 - 1)
+    let expected_tokens_f64 = 10000.0 * (1.1_f64.powf(0.2) - 1.0);
+    let expected_tokens = I64F64::from_num(expected_tokens_f64);
     assert_approx_eq(
         tokens,
         expected_tokens,
         I64F64::from_num(0.0000001),
         "Tokens issued",
     );
+    // Large purchases pay slippage compared to the spot price of 0.5
+    assert!(tokens < I64F64::from_num(200));
 
     let supply = curve.get_supply();
-    let expected_supply = I64F64::from_num(10200);
+    let expected_supply = I64F64::from_num(10000.0 + expected_tokens_f64);
     assert_approx_eq(
         supply,
         expected_supply,
@@ -83,7 +87,7 @@ fn test_bancor_buy_tokens() {
     assert_eq!(curve.get_reserve().unwrap(), I64F64::from_num(1100));
 
     let new_price = curve.get_price().unwrap();
-    let expected_price = I64F64::from_num(1100) / (I64F64::from_num(10200) * I64F64::from_num(0.2));
+    let expected_price = I64F64::from_num(1100.0 / ((10000.0 + expected_tokens_f64) * 0.2));
     assert_approx_eq(
         new_price,
         expected_price,
@@ -92,17 +96,51 @@ fn test_bancor_buy_tokens() {
     );
 }
 
+#[test]
+fn test_bancor_sell_whole_supply_empties_pool() {
+    let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
+    let refund = curve.sell_token(I64F64::from_num(10000)).unwrap();
+    assert_eq!(refund, I64F64::from_num(1000));
+    assert_eq!(curve.get_supply(), I64F64::ZERO);
+    assert_eq!(curve.get_reserve(), Some(I64F64::ZERO));
+}
+
+#[test]
+fn test_bancor_near_full_sell_for_reserve_keeps_pool() {
+    // A request just under the reserve rounds up to the whole supply, which would strand
+    // the difference in an empty pool
+    let mut curve = Bancor::new(1000, 10000, 0.5).unwrap();
+    let reserve = I64F64::from_num(1000);
+    let result = curve.sell_for_reserve(reserve - reserve * I64F64::DELTA);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+    assert_eq!(curve.get_supply(), I64F64::from_num(10000));
+    assert_eq!(curve.get_reserve(), Some(reserve));
+
+    // Asking for the whole reserve still sells the whole supply
+    let tokens = curve.sell_for_reserve(reserve).unwrap();
+    assert_eq!(tokens, I64F64::from_num(10000));
+    assert_eq!(curve.get_supply(), I64F64::ZERO);
+    assert_eq!(curve.get_reserve(), Some(I64F64::ZERO));
+}
+
 #[test]
 fn test_bancor_sell_tokens() {
     let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
     let tokens = curve.sell_token(I64F64::from_num(200)).unwrap();
-    let expected_tokens = I64F64::from_num(100);
+    // R * (1 - (1 - ΔS / S)^(1 / cw)) = 1000 * (1 - 0.98^5)
+    let expected_reserve_f64 = 1000.0 * (1.0 - 0.98_f64.powf(5.0));
+    let expected_tokens = I64F64::from_num(expected_reserve_f64);
     assert_approx_eq(
         tokens,
         expected_tokens,
         I64F64::from_num(0.0000001),
         "Reserve received",
     );
+    // Large sales pay slippage compared to the spot price of 0.5
+    assert!(tokens < I64F64::from_num(100));
 
     let supply = curve.get_supply();
     let expected_supply = I64F64::from_num(9800);
@@ -114,7 +152,7 @@ fn test_bancor_sell_tokens() {
     );
 
     let reserve = curve.get_reserve().unwrap();
-    let expected_reserve = I64F64::from_num(900);
+    let expected_reserve = I64F64::from_num(1000.0 - expected_reserve_f64);
     assert_approx_eq(
         reserve,
         expected_reserve,
@@ -123,7 +161,7 @@ fn test_bancor_sell_tokens() {
     );
 
     let new_price = curve.get_price().unwrap();
-    let expected_price = I64F64::from_num(900) / (I64F64::from_num(9800) * I64F64::from_num(0.2));
+    let expected_price = I64F64::from_num((1000.0 - expected_reserve_f64) / (9800.0 * 0.2));
     assert_approx_eq(
         new_price,
         expected_price,
@@ -148,43 +186,43 @@ fn test_bancor_buy_and_sell_token() {
 
     // Buy 100 reserve worth of tokens
     let tokens_bought = curve.buy_token(I64F64::from_num(100)).unwrap();
-    let expected_tokens_bought = I64F64::from_num(200); // 100 / 0.5
+    let expected_tokens_f64 = 10000.0 * (1.1_f64.powf(0.2) - 1.0);
     assert_approx_eq(
         tokens_bought,
-        expected_tokens_bought,
+        I64F64::from_num(expected_tokens_f64),
         I64F64::from_num(0.0000001),
         "Tokens bought",
     );
+    let supply_after_buy = 10000.0 + expected_tokens_f64;
     assert_approx_eq(
         curve.get_supply(),
-        I64F64::from_num(10200),
+        I64F64::from_num(supply_after_buy),
         I64F64::from_num(0.0000001),
         "Supply after buy",
     );
     assert_eq!(curve.get_reserve().unwrap(), I64F64::from_num(1100));
 
     let price_after_buy = curve.get_price().unwrap();
-    let expected_price_after_buy =
-        I64F64::from_num(1100) / (I64F64::from_num(10200) * I64F64::from_num(0.2));
     assert_approx_eq(
         price_after_buy,
-        expected_price_after_buy,
+        I64F64::from_num(1100.0 / (supply_after_buy * 0.2)),
         I64F64::from_num(0.0000001),
         "Price after buy",
     );
 
     // Sell 100 tokens
     let reserve_received = curve.sell_token(I64F64::from_num(100)).unwrap();
-    let expected_reserve_received = I64F64::from_num(100) * price_after_buy;
+    let expected_reserve_received =
+        1100.0 * (1.0 - (1.0 - 100.0 / supply_after_buy).powf(1.0 / 0.2));
     assert_approx_eq(
         reserve_received,
-        expected_reserve_received,
-        I64F64::from_num(0.0000001),
+        I64F64::from_num(expected_reserve_received),
+        I64F64::from_num(0.000001),
         "Reserve received",
     );
     assert_approx_eq(
         curve.get_supply(),
-        I64F64::from_num(10100),
+        I64F64::from_num(supply_after_buy - 100.0),
         I64F64::from_num(0.0000001),
         "Supply after sell",
     );
@@ -193,8 +231,8 @@ fn test_bancor_buy_and_sell_token() {
         I64F64::from_num(1100) - reserve_received
     );
     let final_price = curve.get_price().unwrap();
-    let expected_final_price = (I64F64::from_num(1100) - reserve_received)
-        / (I64F64::from_num(10100) * I64F64::from_num(0.2));
+    let expected_final_price =
+        (I64F64::from_num(1100) - reserve_received) / (curve.get_supply() * I64F64::from_num(0.2));
     assert_approx_eq(
         final_price,
         expected_final_price,
@@ -203,6 +241,48 @@ fn test_bancor_buy_and_sell_token() {
     );
 }
 
+#[test]
+fn test_bancor_round_trip_is_path_independent() {
+    let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
+
+    // Buying in one trade or in two trades issues the same number of tokens
+    let mut split = curve.clone();
+    let tokens = curve.buy_token(I64F64::from_num(300)).unwrap();
+    let split_tokens = split.buy_token(I64F64::from_num(100)).unwrap()
+        + split.buy_token(I64F64::from_num(200)).unwrap();
+    assert_approx_eq(
+        tokens,
+        split_tokens,
+        I64F64::from_num(0.000001),
+        "Split purchase",
+    );
+
+    // Selling the tokens back returns the reserve that was paid
+    let reserve_received = curve.sell_token(tokens).unwrap();
+    assert_approx_eq(
+        reserve_received,
+        I64F64::from_num(300),
+        I64F64::from_num(0.000001),
+        "Round trip reserve",
+    );
+    assert_approx_eq(
+        curve.get_supply(),
+        I64F64::from_num(10000),
+        I64F64::from_num(0.000001),
+        "Round trip supply",
+    );
+
+    // Selling the whole supply drains the whole reserve
+    let total = curve.get_supply();
+    let drained = curve.sell_token(total).unwrap();
+    assert_approx_eq(
+        drained,
+        I64F64::from_num(1000),
+        I64F64::from_num(0.000001),
+        "Full sale",
+    );
+}
+
 #[test]
 fn test_bancor_insufficient_reserve() {
     let mut curve = Bancor::new(100, 10000, 0.2).unwrap();
@@ -210,7 +290,7 @@ fn test_bancor_insufficient_reserve() {
     assert!(result.is_err());
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("token amount")
+        Err(BondingCurveError::InsufficientSupply { .. })
     ));
 }
 
@@ -218,36 +298,123 @@ fn test_bancor_insufficient_reserve() {
 fn test_bancor_invalid_buy_inputs() {
     let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
     let result = curve.buy_token(I64F64::from_num(0));
-    assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("amount must be positive")
-    ));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
 
     let result = curve.buy_token(I64F64::from_num(-100));
-    assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Reserve amount must be positive")
-    ));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
+
+    // Dust that rounds down to no tokens is refused instead of being kept by the pool
+    let result = curve.buy_token(I64F64::DELTA * 10);
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(1000)));
+    assert_eq!(curve.get_supply(), I64F64::from_num(10000));
 }
 
 #[test]
 fn test_bancor_invalid_sell_inputs() {
     let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
     let result = curve.sell_token(I64F64::from_num(0));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
+
+    let result = curve.sell_token(I64F64::from_num(-100));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
+
+    let result = curve.sell_token(I64F64::from_num(20000));
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
+        Err(BondingCurveError::InsufficientSupply { .. })
     ));
+}
 
-    let result = curve.sell_token(I64F64::from_num(-100));
+#[test]
+fn test_bancor_quotes() {
+    let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
+
+    // Quoting does not change the curve's state
+    let quoted_tokens = curve.quote_buy(I64F64::from_num(100)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(10000));
+    assert_eq!(curve.get_reserve().unwrap(), I64F64::from_num(1000));
+    assert_eq!(
+        curve.buy_token(I64F64::from_num(100)).unwrap(),
+        quoted_tokens
+    );
+
+    let supply = curve.get_supply();
+    let quoted_reserve = curve.quote_sell(I64F64::from_num(150)).unwrap();
+    assert_eq!(curve.get_supply(), supply);
+    assert_eq!(
+        curve.sell_token(I64F64::from_num(150)).unwrap(),
+        quoted_reserve
+    );
+
+    // Quotes are validated like trades
+    assert!(curve.quote_buy(I64F64::from_num(0)).is_err());
     assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
+        curve.quote_sell(I64F64::from_num(20000)),
+        Err(BondingCurveError::InsufficientSupply { .. })
     ));
+}
+
+#[test]
+fn test_bancor_buy_with_reserve() {
+    let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
+    let mut other = curve.clone();
+
+    // Bancor purchases are already denominated in reserve
+    let quoted = curve.quote_buy_with_reserve(I64F64::from_num(100)).unwrap();
+    let tokens = curve.buy_with_reserve(I64F64::from_num(100)).unwrap();
+    assert_eq!(tokens, quoted);
+    assert_eq!(tokens, other.buy_token(I64F64::from_num(100)).unwrap());
+    assert_eq!(curve.get_reserve().unwrap(), I64F64::from_num(1100));
+}
+
+#[test]
+fn test_bancor_exact_output() {
+    let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
+
+    // R * ((1 + ΔS / S)^(1 / cw) - 1) = 1000 * (1.02^5 - 1)
+    let cost = curve.buy_exact_tokens(I64F64::from_num(200)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(1000.0 * (1.02_f64.powf(5.0) - 1.0)),
+        I64F64::from_num(0.000001),
+        "Cost for 200 tokens",
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(10200));
+    assert_eq!(curve.get_reserve().unwrap(), I64F64::from_num(1000) + cost);
+
+    // Selling for exactly the cost returns the curve to its starting point
+    let tokens = curve.sell_for_reserve(cost).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(200),
+        I64F64::from_num(0.000001),
+        "Tokens sold",
+    );
+    assert_eq!(curve.get_reserve().unwrap(), I64F64::from_num(1000));
+
+    // The exact-output quotes invert the exact-input quotes
+    let tokens = curve.quote_buy(I64F64::from_num(250)).unwrap();
+    assert_approx_eq(
+        curve.quote_buy_exact_tokens(tokens).unwrap(),
+        I64F64::from_num(250),
+        I64F64::from_num(0.000001),
+        "Inverse purchase",
+    );
+    let reserve = curve.quote_sell(I64F64::from_num(500)).unwrap();
+    assert_approx_eq(
+        curve.quote_sell_for_reserve(reserve).unwrap(),
+        I64F64::from_num(500),
+        I64F64::from_num(0.000001),
+        "Inverse sale",
+    );
 
-    let result = curve.sell_token(I64F64::from_num(20000));
     assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
+        curve.sell_for_reserve(I64F64::from_num(1001)),
+        Err(BondingCurveError::InsufficientReserve { .. })
+    ));
+    assert!(matches!(
+        curve.buy_exact_tokens(I64F64::from_num(0)),
+        Err(BondingCurveError::ZeroAmount)
     ));
 }
@@ -0,0 +1,256 @@
+#![cfg(feature = "borsh")]
+
+use bonding_curves::{
+    AffineLinear, AnyCurve, Augmented, AugmentedPhase, Bancor, BondingCurve, ConstantProduct,
+    CurveKind, Exponential, LAYOUT_VERSION, LaunchCurve, Linear, Logarithmic, NaturalExponential,
+    OffsetPower, Polynomial, Sigmoid,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use fixed::types::{I32F32, I64F64, I80F48, U64F64};
+
+// Encodes `curve`, checks the header and size, and checks that the decoded state matches
+fn round_trip<C>(curve: &C, discriminator: u8, size: usize) -> C
+where
+    C: BondingCurve + BorshSerialize + BorshDeserialize,
+{
+    let bytes = borsh::to_vec(curve).unwrap();
+    assert_eq!(bytes.len(), size);
+    assert_eq!(bytes[..2], [discriminator, LAYOUT_VERSION]);
+    let decoded = C::try_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.get_supply(), curve.get_supply());
+    assert_eq!(decoded.get_reserve(), curve.get_reserve());
+    assert_eq!(decoded.get_price(), curve.get_price());
+    decoded
+}
+
+#[test]
+fn test_curves_round_trip() {
+    let mut linear = Linear::with_supply(0.01, 100.0).unwrap();
+    linear.buy_token(I64F64::from_num(3.3)).unwrap();
+    let decoded = round_trip(&linear, Linear::<I64F64>::DISCRIMINATOR, 50);
+    assert_eq!(decoded.slope, linear.slope);
+
+    let mut exponential = Exponential::with_supply(0.001, 2.0, 10.0).unwrap();
+    exponential.buy_with_reserve(I64F64::from_num(0.7)).unwrap();
+    let decoded = round_trip(&exponential, Exponential::<I64F64>::DISCRIMINATOR, 66);
+    assert_eq!(decoded.exponent, exponential.exponent);
+
+    let mut logarithmic = Logarithmic::with_supply(10.0, 1.0, 100.0).unwrap();
+    logarithmic.sell_token(I64F64::from_num(12.5)).unwrap();
+    round_trip(&logarithmic, Logarithmic::<I64F64>::DISCRIMINATOR, 66);
+
+    let mut sigmoid = Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    sigmoid.buy_token(I64F64::from_num(1) / 3).unwrap();
+    let decoded = round_trip(&sigmoid, Sigmoid::<I64F64>::DISCRIMINATOR, 82);
+    assert_eq!(decoded.midpoint, sigmoid.midpoint);
+
+    let mut bancor = Bancor::new(1000, 10000, 0.5).unwrap();
+    bancor.buy_token(I64F64::from_num(210)).unwrap();
+    let decoded = round_trip(&bancor, Bancor::<I64F64>::DISCRIMINATOR, 50);
+    assert_eq!(decoded.connector_weight, bancor.connector_weight);
+
+    let mut natural = NaturalExponential::with_supply(0.5, 0.01, 100.0).unwrap();
+    natural.buy_with_reserve(I64F64::from_num(2.5)).unwrap();
+    let decoded = round_trip(&natural, NaturalExponential::<I64F64>::DISCRIMINATOR, 66);
+    assert_eq!(decoded.growth_rate, natural.growth_rate);
+
+    let affine = AffineLinear::with_supply(0.01, 1.0, 100.0).unwrap();
+    let decoded = round_trip(&affine, AffineLinear::<I64F64>::DISCRIMINATOR, 66);
+    assert_eq!(decoded.base_price, affine.base_price);
+
+    let offset = OffsetPower::with_supply(0.001, 2.0, 0.5, 10.0).unwrap();
+    let decoded = round_trip(&offset, OffsetPower::<I64F64>::DISCRIMINATOR, 82);
+    assert_eq!(decoded.base_price, offset.base_price);
+
+    // Coefficients are padded to MAX_DEGREE + 1 fields and the padding dropped on reading
+    let mut polynomial = Polynomial::with_supply(&[1.0, 0.02, 0.0003], 100.0).unwrap();
+    polynomial.buy_with_reserve(I64F64::from_num(2.5)).unwrap();
+    let decoded = round_trip(&polynomial, Polynomial::<I64F64>::DISCRIMINATOR, 178);
+    assert_eq!(decoded.coefficients, polynomial.coefficients);
+
+    let mut pool = ConstantProduct::with_supply(10000.0, 100.0, 50.0, 100.0).unwrap();
+    pool.buy_with_reserve(I64F64::from_num(2.5)).unwrap();
+    let decoded = round_trip(&pool, ConstantProduct::<I64F64>::DISCRIMINATOR, 66);
+    assert_eq!(decoded.token_balance, pool.token_balance);
+    assert_eq!(decoded.virtual_reserve, pool.virtual_reserve);
+
+    let mut launch = LaunchCurve::with_supply(1073.0, 30.0, 793.1, 1000.0, 100.0).unwrap();
+    launch.buy_with_reserve(I64F64::from_num(2.5)).unwrap();
+    let decoded = round_trip(&launch, LaunchCurve::<I64F64>::DISCRIMINATOR, 98);
+    assert_eq!(decoded.real_token_reserve, launch.real_token_reserve);
+    assert_eq!(decoded.virtual_token_reserve, launch.virtual_token_reserve);
+}
+
+#[test]
+fn test_encoded_size_is_fixed_per_curve_and_type() {
+    assert_eq!(Linear::<I64F64>::ENCODED_SIZE, 2 + 3 * 16);
+    assert_eq!(Sigmoid::<I64F64>::ENCODED_SIZE, 2 + 5 * 16);
+    assert_eq!(Bancor::<I32F32>::ENCODED_SIZE, 2 + 3 * 8);
+
+    // The size does not depend on the values held
+    let empty = Linear::<I64F64>::new(0.5).unwrap();
+    let full = Linear::<I64F64>::with_supply(1e-9, 1e9).unwrap();
+    assert_eq!(
+        borsh::to_vec(&empty).unwrap().len(),
+        Linear::<I64F64>::ENCODED_SIZE
+    );
+    assert_eq!(
+        borsh::to_vec(&full).unwrap().len(),
+        Linear::<I64F64>::ENCODED_SIZE
+    );
+
+    // Every curve type has its own discriminator
+    let mut discriminators = [
+        Linear::<I64F64>::DISCRIMINATOR,
+        Exponential::<I64F64>::DISCRIMINATOR,
+        Logarithmic::<I64F64>::DISCRIMINATOR,
+        Sigmoid::<I64F64>::DISCRIMINATOR,
+        Bancor::<I64F64>::DISCRIMINATOR,
+    ];
+    discriminators.sort();
+    discriminators
+        .windows(2)
+        .for_each(|pair| assert_ne!(pair[0], pair[1]));
+}
+
+#[test]
+fn test_fields_are_little_endian_bits() {
+    let curve = Linear::<I64F64>::with_supply(0.5, 100.0).unwrap();
+    let bytes = borsh::to_vec(&curve).unwrap();
+    assert_eq!(bytes[2..18], curve.slope.to_bits().to_le_bytes());
+    assert_eq!(bytes[18..34], curve.token_supply.to_bits().to_le_bytes());
+    assert_eq!(bytes[34..50], curve.reserve_balance.to_bits().to_le_bytes());
+}
+
+#[test]
+fn test_other_fixed_point_types_round_trip() {
+    let mut unsigned = Linear::<U64F64>::new(0.5).unwrap();
+    unsigned.buy_token(U64F64::from_num(7.25)).unwrap();
+    let bytes = borsh::to_vec(&unsigned).unwrap();
+    assert_eq!(bytes.len(), Linear::<U64F64>::ENCODED_SIZE);
+    let decoded = Linear::<U64F64>::try_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.token_supply, unsigned.token_supply);
+
+    let wide = Sigmoid::<I80F48>::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    let bytes = borsh::to_vec(&wide).unwrap();
+    let decoded = Sigmoid::<I80F48>::try_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.reserve_balance, wide.reserve_balance);
+
+    let narrow = Bancor::<I32F32>::new(1000, 10000, 0.5).unwrap();
+    let bytes = borsh::to_vec(&narrow).unwrap();
+    assert_eq!(bytes.len(), 26);
+    let decoded = Bancor::<I32F32>::try_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.connector_weight, narrow.connector_weight);
+}
+
+#[test]
+fn test_deserialization_checks_header() {
+    let bytes = borsh::to_vec(&Linear::<I64F64>::new(0.5).unwrap()).unwrap();
+
+    // Another curve's account is not mistaken for this one, even with a matching size
+    let error = Bancor::<I64F64>::try_from_slice(&bytes).unwrap_err();
+    assert!(error.to_string().contains("discriminator"), "{error}");
+
+    let mut future = bytes.clone();
+    future[1] = LAYOUT_VERSION + 1;
+    let error = Linear::<I64F64>::try_from_slice(&future).unwrap_err();
+    assert!(error.to_string().contains("version"), "{error}");
+}
+
+#[test]
+fn test_deserialization_rejects_malformed_input() {
+    let bytes = borsh::to_vec(&Linear::<I64F64>::new(0.5).unwrap()).unwrap();
+    assert!(Linear::<I64F64>::try_from_slice(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Linear::<I64F64>::try_from_slice(&[]).is_err());
+
+    let mut padded = bytes.clone();
+    padded.push(0);
+    assert!(Linear::<I64F64>::try_from_slice(&padded).is_err());
+}
+
+#[test]
+fn test_deserialization_validates_state() {
+    // Parameters go through the checks of `new`
+    let mut bytes = borsh::to_vec(&Linear::<I64F64>::new(0.5).unwrap()).unwrap();
+    bytes[2..18].copy_from_slice(&I64F64::from_num(-1).to_bits().to_le_bytes());
+    let error = Linear::<I64F64>::try_from_slice(&bytes).unwrap_err();
+    assert!(error.to_string().contains("slope"), "{error}");
+
+    // So do the supply and the reserve
+    let mut bytes = borsh::to_vec(&Exponential::<I64F64>::new(1.0, 2.0).unwrap()).unwrap();
+    bytes[34..50].copy_from_slice(&I64F64::from_num(1e15).to_bits().to_le_bytes());
+    let error = Exponential::<I64F64>::try_from_slice(&bytes).unwrap_err();
+    assert!(error.to_string().contains("token_supply"), "{error}");
+
+    let mut bytes = borsh::to_vec(&Bancor::<I64F64>::new(1000, 10000, 0.5).unwrap()).unwrap();
+    bytes[34..50].copy_from_slice(&I64F64::from_num(1.5).to_bits().to_le_bytes());
+    let error = Bancor::<I64F64>::try_from_slice(&bytes).unwrap_err();
+    assert!(error.to_string().contains("connector_weight"), "{error}");
+}
+
+#[test]
+fn test_sold_out_pool_round_trips() {
+    // Selling the whole supply leaves a state that decodes like any other
+    let mut bancor = Bancor::new(1000, 10000, 0.2).unwrap();
+    bancor.sell_token(I64F64::from_num(10000)).unwrap();
+    round_trip(&bancor, Bancor::<I64F64>::DISCRIMINATOR, 50);
+}
+
+#[test]
+fn test_any_curve_selects_variant_by_discriminator() {
+    let bancor = Bancor::<I64F64>::new(1000, 10000, 0.5).unwrap();
+    let bytes = borsh::to_vec(&bancor).unwrap();
+    let decoded = AnyCurve::<I64F64>::try_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.kind(), CurveKind::Bancor);
+    assert_eq!(borsh::to_vec(&decoded).unwrap(), bytes);
+
+    let curve: AnyCurve = Logarithmic::with_supply(10.0, 1.0, 100.0).unwrap().into();
+    let decoded = round_trip(&curve, Logarithmic::<I64F64>::DISCRIMINATOR, 66);
+    assert_eq!(decoded.kind(), CurveKind::Logarithmic);
+
+    // An account sized for the largest curve holds any of them, followed by padding
+    let mut account = vec![0u8; AnyCurve::<I64F64>::MAX_ENCODED_SIZE];
+    account[..bytes.len()].copy_from_slice(&bytes);
+    let decoded = AnyCurve::<I64F64>::deserialize(&mut account.as_slice()).unwrap();
+    assert_eq!(
+        decoded.parameter("connector_weight"),
+        Some(bancor.connector_weight)
+    );
+
+    let mut unknown = bytes.clone();
+    unknown[0] = 200;
+    let error = AnyCurve::<I64F64>::try_from_slice(&unknown).unwrap_err();
+    assert!(error.to_string().contains("discriminator"), "{error}");
+}
+
+#[test]
+fn test_augmented_round_trips() {
+    let mut curve = Augmented::new(0.01, 0.5, 2000, 500, 1000).unwrap();
+    curve.buy_with_reserve(I64F64::from_num(1000)).unwrap();
+    curve.open().unwrap();
+    curve.sell_token(I64F64::from_num(10000)).unwrap();
+
+    // Five fixed-point fields, three u16 rates and the phase byte
+    assert_eq!(Augmented::<I64F64>::ENCODED_SIZE, 2 + 5 * 16 + 3 * 2 + 1);
+    let decoded = round_trip(&curve, Augmented::<I64F64>::DISCRIMINATOR, 89);
+    assert_eq!(decoded.phase(), AugmentedPhase::Open);
+    assert_eq!(decoded.funding_pool(), curve.funding_pool());
+    assert_eq!(decoded.entry_tribute_bps(), curve.entry_tribute_bps());
+
+    let bytes = borsh::to_vec(&curve).unwrap();
+    assert_eq!(bytes[34..36], 2000u16.to_le_bytes());
+    assert_eq!(bytes[40], 1);
+    let decoded = AnyCurve::<I64F64>::try_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.kind(), CurveKind::Augmented);
+
+    let mut unknown = bytes.clone();
+    unknown[40] = 2;
+    let error = Augmented::<I64F64>::try_from_slice(&unknown).unwrap_err();
+    assert!(error.to_string().contains("phase"), "{error}");
+
+    // The stored state goes through the checks of `restore`
+    let mut negative = bytes.clone();
+    negative[73..89].copy_from_slice(&I64F64::from_num(-1).to_bits().to_le_bytes());
+    let error = Augmented::<I64F64>::try_from_slice(&negative).unwrap_err();
+    assert!(error.to_string().contains("funding_pool"), "{error}");
+}
@@ -0,0 +1,228 @@
+#![cfg(feature = "config")]
+
+use bonding_curves::{
+    AnyCurve, Bancor, BondingCurve, BondingCurveError, CurveConfig, CurveKind, Sigmoid,
+};
+use fixed::types::I64F64;
+use serde::Deserialize;
+
+// Asserts that `result` failed on `field`, either in the schema or in the constructor
+fn assert_field_error<T: std::fmt::Debug>(result: Result<T, BondingCurveError>, field: &str) {
+    match result {
+        Err(BondingCurveError::InvalidConfig { field: name, .. })
+        | Err(BondingCurveError::InvalidParameter { name, .. }) => {
+            assert_eq!(name, field)
+        }
+        other => panic!("expected an error naming {field}, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_toml_config_builds_curve() {
+    let config = CurveConfig::from_toml(
+        r#"
+        kind = "sigmoid"
+        max_price = 100
+        steepness = 0.1
+        midpoint = 50.0
+        initial_supply = 40.0
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.kind(), CurveKind::Sigmoid);
+
+    let curve: AnyCurve = config.build().unwrap();
+    let expected = Sigmoid::<I64F64>::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    assert_eq!(curve.kind(), CurveKind::Sigmoid);
+    assert_eq!(curve.get_supply(), expected.get_supply());
+    assert_eq!(curve.get_reserve(), expected.get_reserve());
+    assert_eq!(curve.get_price(), expected.get_price());
+}
+
+#[test]
+fn test_json_config_builds_curve() {
+    let config = CurveConfig::from_json(
+        r#"{ "kind": "bancor", "reserve_balance": 1000, "token_supply": 10000, "connector_weight": 0.5 }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        CurveConfig::Bancor {
+            reserve_balance: 1000,
+            token_supply: 10000,
+            connector_weight: 0.5,
+        }
+    );
+    let curve: AnyCurve = config.build().unwrap();
+    let expected = Bancor::<I64F64>::new(1000, 10000, 0.5).unwrap();
+    assert_eq!(curve.get_price(), expected.get_price());
+
+    // The initial supply defaults to zero
+    let config = CurveConfig::from_json(r#"{ "kind": "linear", "slope": 0.01 }"#).unwrap();
+    assert_eq!(
+        config,
+        CurveConfig::Linear {
+            slope: 0.01,
+            initial_supply: 0.0,
+        }
+    );
+}
+
+#[test]
+fn test_every_kind_is_configurable() {
+    let documents = [
+        r#"kind = "linear"
+        slope = 0.01"#,
+        r#"kind = "exponential"
+        coefficient = 0.001
+        exponent = 2"#,
+        r#"kind = "logarithmic"
+        coefficient = 10
+        constant = 1"#,
+        r#"kind = "sigmoid"
+        max_price = 100
+        steepness = 0.1
+        midpoint = 50"#,
+        r#"kind = "bancor"
+        reserve_balance = 1000
+        token_supply = 10000
+        connector_weight = 0.5"#,
+        r#"kind = "natural_exponential"
+        initial_price = 0.5
+        growth_rate = 0.01"#,
+        r#"kind = "affine_linear"
+        slope = 0.01
+        base_price = 1"#,
+        r#"kind = "offset_power"
+        coefficient = 0.001
+        exponent = 2
+        base_price = 0.5"#,
+        r#"kind = "polynomial"
+        coefficients = [1, 0.02, 0.0003]"#,
+        r#"kind = "constant_product"
+        token_balance = 10000
+        reserve_balance = 100
+        virtual_reserve = 50"#,
+        r#"kind = "launch_curve"
+        virtual_token_reserve = 1073000000
+        virtual_reserve = 30
+        real_token_reserve = 793100000
+        total_supply = 1000000000"#,
+        r#"kind = "augmented"
+        hatch_price = 0.01
+        connector_weight = 0.5
+        hatch_tribute_bps = 2000"#,
+    ];
+    for (document, kind) in documents.iter().zip(CurveKind::ALL) {
+        let curve: AnyCurve = CurveConfig::from_toml(document).unwrap().build().unwrap();
+        assert_eq!(curve.kind(), kind);
+    }
+}
+
+#[test]
+fn test_schema_errors_name_the_field() {
+    assert_field_error(
+        CurveConfig::from_toml("kind = \"linear\"\ninitial_supply = 5"),
+        "slope",
+    );
+    assert_field_error(
+        CurveConfig::from_toml("kind = \"linear\"\nslope = 0.5\nslop = 0.5"),
+        "slop",
+    );
+    assert_field_error(
+        CurveConfig::from_toml("kind = \"linear\"\nslope = \"steep\""),
+        "slope",
+    );
+    assert_field_error(CurveConfig::from_toml("slope = 0.5"), "kind");
+    assert_field_error(
+        CurveConfig::from_json(r#"{ "kind": "quadratic", "slope": 0.5 }"#),
+        "kind",
+    );
+    assert_field_error(
+        CurveConfig::from_toml("kind = \"polynomial\"\ncoefficients = [1, \"a\"]"),
+        "coefficients",
+    );
+    // Bancor balances are whole numbers, as in `Bancor::new`
+    assert_field_error(
+        CurveConfig::from_json(
+            r#"{ "kind": "bancor", "reserve_balance": 10.5, "token_supply": 100, "connector_weight": 0.5 }"#,
+        ),
+        "reserve_balance",
+    );
+}
+
+#[test]
+fn test_validation_errors_name_the_field() {
+    let build = |document: &str| CurveConfig::from_toml(document)?.build::<I64F64>();
+
+    assert_field_error(build("kind = \"linear\"\nslope = -1"), "slope");
+    assert_field_error(
+        build("kind = \"sigmoid\"\nmax_price = 100\nsteepness = 0\nmidpoint = 50"),
+        "steepness",
+    );
+    assert_field_error(
+        build("kind = \"exponential\"\ncoefficient = 1\nexponent = 2\ninitial_supply = -3"),
+        "initial_supply",
+    );
+    assert_field_error(
+        build(
+            "kind = \"bancor\"\nreserve_balance = 1000\ntoken_supply = 1000\nconnector_weight = 1.5",
+        ),
+        "connector_weight",
+    );
+    // Tribute rates are whole basis points below 10000
+    assert_field_error(
+        build(
+            "kind = \"augmented\"\nhatch_price = 0.01\nconnector_weight = 0.5\nexit_tribute_bps = 10000",
+        ),
+        "exit_tribute_bps",
+    );
+    assert_field_error(
+        build(
+            "kind = \"augmented\"\nhatch_price = 0.01\nconnector_weight = 0.5\nentry_tribute_bps = 70000",
+        ),
+        "entry_tribute_bps",
+    );
+}
+
+#[test]
+fn test_malformed_documents() {
+    let result = CurveConfig::from_toml("kind = ");
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::MalformedConfig { .. })
+    ));
+    let result = CurveConfig::from_json("[1, 2]");
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::MalformedConfig { .. })
+    ));
+}
+
+#[test]
+fn test_config_embedded_in_larger_document() {
+    #[derive(Deserialize)]
+    struct Deployment {
+        name: String,
+        curve: CurveConfig,
+    }
+
+    let deployment: Deployment = toml::from_str(
+        r#"
+        name = "launch"
+
+        [curve]
+        kind = "logarithmic"
+        coefficient = 10
+        constant = 1
+        "#,
+    )
+    .unwrap();
+    assert_eq!(deployment.name, "launch");
+    assert_eq!(deployment.curve.kind(), CurveKind::Logarithmic);
+
+    let error = toml::from_str::<Deployment>("name = \"launch\"\n[curve]\nkind = \"linear\"")
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("slope"), "{error}");
+}
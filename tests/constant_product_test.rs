@@ -0,0 +1,218 @@
+use bonding_curves::{BondingCurve, BondingCurveError, ConstantProduct};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn num(value: f64) -> I64F64 {
+    I64F64::from_num(value)
+}
+
+#[test]
+fn test_constant_product_new_valid() {
+    let curve = ConstantProduct::new(1000.0, 200.0, 0.0).unwrap();
+    assert_eq!(curve.get_supply(), num(0.0));
+    assert_eq!(curve.get_reserve(), Some(num(200.0)));
+    assert_approx_eq(curve.get_price().unwrap(), num(0.2), num(1e-15), "Price");
+    assert_eq!(curve.invariant().unwrap(), num(200_000.0));
+
+    // A virtual reserve prices the pool without being held by it
+    let curve = ConstantProduct::new(1000.0, 0.0, 30.0).unwrap();
+    assert_eq!(curve.get_reserve(), Some(num(0.0)));
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        num(0.03),
+        num(1e-15),
+        "Virtual price",
+    );
+    assert_eq!(curve.effective_reserve().unwrap(), num(30.0));
+}
+
+#[test]
+fn test_constant_product_new_invalid() {
+    let result = ConstantProduct::<I64F64>::new(0.0, 200.0, 0.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "token_balance"
+    ));
+
+    let result = ConstantProduct::<I64F64>::new(1000.0, -1.0, 0.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "reserve_balance"
+    ));
+
+    // No reserve at all, real or virtual
+    let result = ConstantProduct::<I64F64>::new(1000.0, 0.0, 0.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "virtual_reserve"
+    ));
+
+    // The invariant must fit the fixed-point type
+    let result = ConstantProduct::<I64F64>::new(1e12, 1e12, 0.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "token_balance"
+    ));
+}
+
+#[test]
+fn test_constant_product_swaps() {
+    let mut curve = ConstantProduct::new(1000.0, 200.0, 0.0).unwrap();
+
+    // Taking 100 tokens out costs y * Δx / (x - Δx)
+    let cost = curve.buy_token(num(100.0)).unwrap();
+    assert_approx_eq(cost, num(200.0 * 100.0 / 900.0), num(1e-12), "Buy cost");
+    assert_eq!(curve.token_balance, num(900.0));
+    assert_eq!(curve.get_supply(), num(100.0));
+
+    // Paying Δy in returns x * Δy / (y + Δy) tokens
+    let reserve = curve.get_reserve().unwrap();
+    let tokens = curve.buy_with_reserve(num(50.0)).unwrap();
+    assert_approx_eq(
+        tokens,
+        num(900.0) * num(50.0) / (reserve + num(50.0)),
+        num(1e-12),
+        "Tokens for 50 reserve",
+    );
+
+    // Putting Δx back pays y * Δx / (x + Δx)
+    let (balance, reserve) = (curve.token_balance, curve.get_reserve().unwrap());
+    let refund = curve.sell_token(num(60.0)).unwrap();
+    assert_approx_eq(
+        refund,
+        reserve * num(60.0) / (balance + num(60.0)),
+        num(1e-12),
+        "Sell refund",
+    );
+
+    // Withdrawing Δy costs x * Δy / (y - Δy) tokens
+    let (balance, reserve) = (curve.token_balance, curve.get_reserve().unwrap());
+    let tokens = curve.sell_for_reserve(num(20.0)).unwrap();
+    assert_approx_eq(
+        tokens,
+        balance * num(20.0) / (reserve - num(20.0)),
+        num(1e-12),
+        "Tokens for 20 reserve",
+    );
+}
+
+#[test]
+fn test_constant_product_invariant_never_decreases() {
+    let mut curve = ConstantProduct::new(1000.0, 200.0, 0.0).unwrap();
+    let mut invariant = curve.invariant().unwrap();
+    let mut check = |curve: &ConstantProduct| {
+        let next = curve.invariant().unwrap();
+        assert!(next >= invariant, "{next} < {invariant}");
+        assert_approx_eq(next, invariant, num(1e-9), "Invariant");
+        invariant = next;
+    };
+
+    curve.buy_token(num(1.0) / 3).unwrap();
+    check(&curve);
+    curve.buy_with_reserve(num(7.7)).unwrap();
+    check(&curve);
+    curve.sell_token(num(0.1)).unwrap();
+    check(&curve);
+    curve.sell_for_reserve(num(1.3)).unwrap();
+    check(&curve);
+
+    // A round trip never returns more than it cost
+    let cost = curve.buy_token(num(25.0)).unwrap();
+    assert!(curve.sell_token(num(25.0)).unwrap() <= cost);
+}
+
+#[test]
+fn test_constant_product_virtual_reserve() {
+    let mut curve = ConstantProduct::new(1000.0, 0.0, 30.0).unwrap();
+
+    // Buys price against the virtual reserve: 30 * 250 / 750 = 10
+    let cost = curve.buy_token(num(250.0)).unwrap();
+    assert_approx_eq(cost, num(10.0), num(1e-12), "Cost");
+    assert_eq!(curve.get_reserve(), Some(cost));
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        num(40.0 / 750.0),
+        num(1e-12),
+        "Price",
+    );
+
+    // Selling everything back drains the real reserve and leaves the virtual one
+    curve.sell_token(curve.get_supply()).unwrap();
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        num(0.0),
+        num(1e-12),
+        "Reserve",
+    );
+    assert_eq!(curve.virtual_reserve, num(30.0));
+
+    // Tokens minted elsewhere can only be sold for the real reserve the pool holds
+    let mut curve = ConstantProduct::with_supply(1000.0, 5.0, 30.0, 500.0).unwrap();
+    assert_eq!(curve.sell_token(num(500.0)).unwrap(), num(5.0));
+    assert!(matches!(
+        ConstantProduct::with_supply(1000.0, 5.0, 30.0, 500.0)
+            .unwrap()
+            .sell_for_reserve(num(6.0)),
+        Err(BondingCurveError::InsufficientReserve { .. })
+    ));
+}
+
+#[test]
+fn test_constant_product_cannot_be_drained() {
+    let mut curve = ConstantProduct::new(1000.0, 200.0, 0.0).unwrap();
+
+    // The last token would cost an unbounded reserve
+    let result = curve.buy_token(num(1000.0));
+    assert!(matches!(result, Err(BondingCurveError::Overflow { .. })));
+    assert_eq!(curve.token_balance, num(1000.0));
+
+    // Any reserve, however large, buys less than the whole pool
+    let tokens = curve.buy_with_reserve(num(1e12)).unwrap();
+    assert!(tokens < num(1000.0));
+    assert!(curve.token_balance > num(0.0));
+}
+
+#[test]
+fn test_constant_product_pure_functions() {
+    let curve = ConstantProduct::new(1000.0, 200.0, 0.0).unwrap();
+
+    // P(S) = k / (X - S)^2 and the reserve side is k / (X - S)
+    assert_approx_eq(
+        curve.price_at(num(0.0)).unwrap(),
+        num(0.2),
+        num(1e-15),
+        "Price at 0",
+    );
+    assert_approx_eq(
+        curve.price_at(num(500.0)).unwrap(),
+        num(0.8),
+        num(1e-15),
+        "Price at 500",
+    );
+    assert_approx_eq(
+        curve.cost_between(num(0.0), num(500.0)).unwrap(),
+        num(200.0),
+        num(1e-12),
+        "Cost to halve the pool",
+    );
+    assert_approx_eq(
+        curve.supply_for_reserve(num(200.0)).unwrap(),
+        num(500.0),
+        num(1e-12),
+        "Supply for 200",
+    );
+    assert!(matches!(
+        curve.price_at(num(1000.0)),
+        Err(BondingCurveError::Overflow { .. })
+    ));
+}
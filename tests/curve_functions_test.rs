@@ -0,0 +1,349 @@
+use bonding_curves::{
+    AffineLinear, AnyCurve, Bancor, BondingCurve, BondingCurveError, ConstantProduct, Exponential,
+    FeeConfig, FeeCurve, FeeSide, LaunchCurve, Linear, Logarithmic, NaturalExponential,
+    OffsetPower, Polynomial, Sigmoid,
+};
+use fixed::types::I64F64;
+
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn num(value: f64) -> I64F64 {
+    I64F64::from_num(value)
+}
+
+// Properties every curve's pure functions share, checked against its live state
+fn check_curve_functions<C: BondingCurve + Clone>(curve: &C, name: &str) {
+    let before = (curve.get_supply(), curve.get_reserve());
+    let supply = curve.get_supply();
+    let tolerance = num(1e-6);
+
+    // The price at the current supply is the current price
+    assert_approx_eq(
+        curve.price_at(supply).unwrap(),
+        curve.get_price().unwrap(),
+        tolerance,
+        name,
+    );
+
+    // The price never falls as supply grows
+    let mut previous = curve.price_at(I64F64::ZERO).unwrap();
+    for step in 1..=20 {
+        let price = curve.price_at(num(step as f64 * 10.0)).unwrap();
+        assert!(price >= previous, "{name}: price fell at step {step}");
+        previous = price;
+    }
+
+    // Buying tokens costs the integral over the supply they add
+    let amount = num(7.5);
+    assert_approx_eq(
+        curve.cost_between(supply, supply + amount).unwrap(),
+        curve.quote_buy_exact_tokens(amount).unwrap(),
+        tolerance,
+        name,
+    );
+
+    // The integral is additive up to rounding, and rounding only ever adds to a cost
+    let (a, b, c) = (num(5.0), num(20.0), num(60.0));
+    let split = curve.cost_between(a, b).unwrap() + curve.cost_between(b, c).unwrap();
+    let whole = curve.cost_between(a, c).unwrap();
+    assert!(split >= whole, "{name}: {split} < {whole}");
+    assert_approx_eq(split, whole, tolerance, name);
+    let empty = curve.cost_between(b, b).unwrap();
+    assert!(
+        empty >= I64F64::ZERO && empty < tolerance,
+        "{name}: {empty}"
+    );
+
+    // The supply a reserve buys from zero costs no more than that reserve
+    for reserve in [0.5, 10.0, 250.0] {
+        let reserve = num(reserve);
+        let covered = curve.supply_for_reserve(reserve).unwrap();
+        let cost = curve.cost_between(I64F64::ZERO, covered).unwrap();
+        assert!(cost <= reserve, "{name}: {cost} for a reserve of {reserve}");
+        let inverse = curve.supply_for_reserve(cost).unwrap();
+        assert!(inverse >= covered, "{name}: inverse {inverse} < {covered}");
+    }
+    let cost = curve.cost_between(I64F64::ZERO, num(30.0)).unwrap();
+    assert_approx_eq(
+        curve.supply_for_reserve(cost).unwrap(),
+        num(30.0),
+        num(1e-3),
+        name,
+    );
+
+    // Values outside the curve's domain are rejected
+    for result in [
+        curve.price_at(num(-1.0)),
+        curve.cost_between(num(-1.0), num(1.0)),
+        curve.cost_between(num(2.0), num(1.0)),
+        curve.supply_for_reserve(num(-1.0)),
+    ] {
+        assert!(
+            matches!(result, Err(BondingCurveError::DomainError { .. })),
+            "{name}: {result:?}"
+        );
+    }
+
+    assert_eq!((curve.get_supply(), curve.get_reserve()), before, "{name}");
+}
+
+#[test]
+fn test_linear_curve_functions() {
+    let curve = Linear::with_supply(0.01, 100.0).unwrap();
+    check_curve_functions(&curve, "linear");
+
+    // P = 0.01 * S, cost = 0.005 * S^2
+    assert_approx_eq(
+        curve.price_at(num(300.0)).unwrap(),
+        num(3.0),
+        num(1e-9),
+        "price",
+    );
+    // Its buys are priced by exactly this integral
+    assert_eq!(
+        curve.cost_between(num(100.0), num(107.5)).unwrap(),
+        curve.quote_buy(num(7.5)).unwrap()
+    );
+    let tolerance = num(1e-9);
+    assert_approx_eq(
+        curve.cost_between(num(100.0), num(200.0)).unwrap(),
+        num(150.0),
+        tolerance,
+        "cost",
+    );
+    assert_approx_eq(
+        curve.supply_for_reserve(num(50.0)).unwrap(),
+        num(100.0),
+        tolerance,
+        "supply",
+    );
+    assert_eq!(
+        curve
+            .cost_between(I64F64::ZERO, curve.token_supply)
+            .unwrap(),
+        curve.reserve_balance
+    );
+}
+
+#[test]
+fn test_exponential_curve_functions() {
+    let curve = Exponential::with_supply(0.001, 2.0, 10.0).unwrap();
+    check_curve_functions(&curve, "exponential");
+
+    // P = 0.001 * S^2, cost = 0.001 * S^3 / 3
+    assert_approx_eq(
+        curve.price_at(num(100.0)).unwrap(),
+        num(10.0),
+        num(1e-9),
+        "price",
+    );
+    assert_approx_eq(
+        curve.cost_between(I64F64::ZERO, num(30.0)).unwrap(),
+        num(9.0),
+        num(1e-9),
+        "cost",
+    );
+}
+
+#[test]
+fn test_natural_exponential_curve_functions() {
+    let curve = NaturalExponential::with_supply(0.5, 0.01, 100.0).unwrap();
+    check_curve_functions(&curve, "natural exponential");
+
+    // P = 0.5 * e^(0.01 * S), cost = 50 * (e^(0.01 * S) - 1)
+    assert_eq!(curve.price_at(I64F64::ZERO).unwrap(), num(0.5));
+    assert_approx_eq(
+        curve.cost_between(I64F64::ZERO, num(100.0)).unwrap(),
+        num(50.0 * (std::f64::consts::E - 1.0)),
+        num(1e-9),
+        "cost",
+    );
+    assert_approx_eq(
+        curve
+            .supply_for_reserve(num(50.0 * (std::f64::consts::E - 1.0)))
+            .unwrap(),
+        num(100.0),
+        num(1e-9),
+        "supply",
+    );
+}
+
+#[test]
+fn test_affine_linear_curve_functions() {
+    let curve = AffineLinear::with_supply(0.01, 1.0, 100.0).unwrap();
+    check_curve_functions(&curve, "affine linear");
+
+    // P = 0.01 * S + 1, cost = 0.005 * S^2 + S
+    assert_eq!(curve.price_at(I64F64::ZERO).unwrap(), num(1.0));
+    assert_approx_eq(
+        curve.supply_for_reserve(num(150.0)).unwrap(),
+        num(100.0),
+        num(1e-9),
+        "supply",
+    );
+}
+
+#[test]
+fn test_offset_power_curve_functions() {
+    let curve = OffsetPower::with_supply(0.001, 2.0, 0.5, 10.0).unwrap();
+    check_curve_functions(&curve, "offset power");
+
+    // P = 0.001 * S^2 + 0.5, cost = 0.001 * S^3 / 3 + 0.5 * S
+    assert_approx_eq(
+        curve.cost_between(I64F64::ZERO, num(30.0)).unwrap(),
+        num(24.0),
+        num(1e-9),
+        "cost",
+    );
+}
+
+#[test]
+fn test_polynomial_curve_functions() {
+    let curve = Polynomial::with_supply(&[1.0, 0.02, 0.0003], 100.0).unwrap();
+    check_curve_functions(&curve, "polynomial");
+
+    // P = 1 + 0.02 * S + 0.0003 * S^2, cost = S + 0.01 * S^2 + 0.0001 * S^3
+    assert_approx_eq(
+        curve.price_at(num(10.0)).unwrap(),
+        num(1.23),
+        num(1e-12),
+        "price",
+    );
+    assert_approx_eq(
+        curve.supply_for_reserve(num(300.0)).unwrap(),
+        num(100.0),
+        num(1e-9),
+        "supply",
+    );
+}
+
+#[test]
+fn test_constant_product_curve_functions() {
+    let curve = ConstantProduct::with_supply(10000.0, 100.0, 50.0, 100.0).unwrap();
+    check_curve_functions(&curve, "constant product");
+
+    // k = 10000 * 150 over X = 10100 tokens: the reserve side at S is k / (X - S)
+    assert_approx_eq(
+        curve.cost_between(num(100.0), num(2100.0)).unwrap(),
+        num(1_500_000.0 / 8000.0 - 150.0),
+        num(1e-9),
+        "cost",
+    );
+}
+
+#[test]
+fn test_launch_curve_functions() {
+    let curve = LaunchCurve::with_supply(1073.0, 30.0, 793.1, 1000.0, 100.0).unwrap();
+    check_curve_functions(&curve, "launch curve");
+
+    // k = 1073 * 30: the virtual reserve side at S is k / (1073 - S)
+    assert_approx_eq(
+        curve.cost_between(num(0.0), num(73.0)).unwrap(),
+        num(32190.0 / 1000.0 - 30.0),
+        num(1e-9),
+        "cost",
+    );
+}
+
+#[test]
+fn test_logarithmic_curve_functions() {
+    let curve = Logarithmic::with_supply(10.0, 1.0, 100.0).unwrap();
+    check_curve_functions(&curve, "logarithmic");
+
+    // P = 10 * ln(S + 1)
+    assert_approx_eq(
+        curve.price_at(num(std::f64::consts::E - 1.0)).unwrap(),
+        num(10.0),
+        num(1e-9),
+        "price",
+    );
+}
+
+#[test]
+fn test_sigmoid_curve_functions() {
+    let curve = Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    check_curve_functions(&curve, "sigmoid");
+
+    // Half the maximum price at the midpoint
+    assert_approx_eq(
+        curve.price_at(num(50.0)).unwrap(),
+        num(50.0),
+        num(1e-9),
+        "price",
+    );
+}
+
+#[test]
+fn test_bancor_curve_functions() {
+    let curve = Bancor::new(1000, 10000, 0.5).unwrap();
+    check_curve_functions(&curve, "bancor");
+
+    // The curve through the current state backs the current supply with the current reserve
+    assert_approx_eq(
+        curve.cost_between(I64F64::ZERO, num(10000.0)).unwrap(),
+        num(1000.0),
+        num(1e-6),
+        "reserve",
+    );
+    assert_approx_eq(
+        curve.supply_for_reserve(num(1000.0)).unwrap(),
+        num(10000.0),
+        num(1e-6),
+        "supply",
+    );
+    // With cw = 0.5, R grows with S^2 and the price with S: P(2 * S0) = 2 * P(S0)
+    assert_approx_eq(
+        curve.price_at(num(20000.0)).unwrap(),
+        curve.get_price().unwrap() * 2,
+        num(1e-9),
+        "price",
+    );
+
+    // After a trade the curve still passes through the new state
+    let mut traded = curve.clone();
+    traded.buy_token(num(210.0)).unwrap();
+    assert_approx_eq(
+        curve
+            .cost_between(I64F64::ZERO, traded.token_supply)
+            .unwrap(),
+        traded.reserve_balance,
+        num(1e-6),
+        "reserve after trade",
+    );
+
+    // An empty pool is priced flat until its first purchase sets the reserve ratio
+    let empty = Bancor::<I64F64>::new(0, 0, 0.5).unwrap();
+    let tokens = empty.quote_buy(num(1.0)).unwrap();
+    assert_eq!(empty.supply_for_reserve(num(1.0)).unwrap(), tokens);
+    assert!(empty.cost_between(I64F64::ZERO, tokens).unwrap() >= num(1.0));
+}
+
+#[test]
+fn test_wrappers_forward_curve_functions() {
+    let linear = Linear::with_supply(0.01, 100.0).unwrap();
+    let config = FeeConfig::new(100, 200, 2500, FeeSide::Input).unwrap();
+    let with_fees = FeeCurve::new(linear.clone(), config);
+    let any: AnyCurve = linear.clone().into();
+
+    // Fees apply to trades, not to the curve itself
+    let (from, to) = (num(10.0), num(90.0));
+    let cost = linear.cost_between(from, to).unwrap();
+    assert_eq!(with_fees.cost_between(from, to).unwrap(), cost);
+    assert_eq!(any.cost_between(from, to).unwrap(), cost);
+    assert_eq!(
+        with_fees.price_at(to).unwrap(),
+        linear.price_at(to).unwrap()
+    );
+    assert_eq!(
+        any.supply_for_reserve(cost).unwrap(),
+        linear.supply_for_reserve(cost).unwrap()
+    );
+}
@@ -17,51 +17,51 @@ fn test_exponential_new_valid() {
     let curve = Exponential::new(2.0, 1.5).unwrap();
     assert_eq!(curve.get_supply(), I64F64::from_num(0));
     assert_eq!(curve.get_price().unwrap(), I64F64::from_num(0));
-    assert_eq!(curve.get_reserve(), None);
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
 }
 
 #[test]
 fn test_exponential_new_invalid() {
     // Zero coefficient
-    let result = Exponential::new(0.0, 1.5);
+    let result = Exponential::<I64F64>::new(0.0, 1.5);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and exponent must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "coefficient"
     ));
 
     // Negative coefficient
-    let result = Exponential::new(-1.0, 1.5);
+    let result = Exponential::<I64F64>::new(-1.0, 1.5);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and exponent must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "coefficient"
     ));
 
     // Zero exponent
-    let result = Exponential::new(2.0, 0.0);
+    let result = Exponential::<I64F64>::new(2.0, 0.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and exponent must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "exponent"
     ));
 
     // Negative exponent
-    let result = Exponential::new(2.0, -1.0);
+    let result = Exponential::<I64F64>::new(2.0, -1.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and exponent must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "exponent"
     ));
 
     // NaN coefficient
-    let result = Exponential::new(f64::NAN, 1.5);
+    let result = Exponential::<I64F64>::new(f64::NAN, 1.5);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and exponent must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "coefficient"
     ));
 
     // Infinity exponent
-    let result = Exponential::new(2.0, f64::INFINITY);
+    let result = Exponential::<I64F64>::new(2.0, f64::INFINITY);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and exponent must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "exponent"
     ));
 }
 
@@ -192,37 +192,25 @@ fn test_exponential_invalid_inputs() {
 
     // Buy zero tokens
     let result = curve.buy_token(I64F64::from_num(0));
-    assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Token amount must be positive")
-    ));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
 
     // Buy negative tokens
     let result = curve.buy_token(I64F64::from_num(-10));
-    assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Token amount must be positive")
-    ));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
 
     // Sell zero tokens
     let result = curve.sell_token(I64F64::from_num(0));
-    assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
-    ));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
 
     // Sell negative tokens
     let result = curve.sell_token(I64F64::from_num(-10));
-    assert!(matches!(
-        result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
-    ));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
 
     // Sell more than supply
     let result = curve.sell_token(I64F64::from_num(1));
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
+        Err(BondingCurveError::InsufficientSupply { .. })
     ));
 }
 
@@ -269,3 +257,151 @@ fn test_exponential_edge_cases() {
         "Price with large exponent",
     );
 }
+
+#[test]
+fn test_exponential_quotes() {
+    let mut curve = Exponential::new(0.001, 2.0).unwrap();
+
+    // Quoting does not change the curve's state
+    let quoted_cost = curve.quote_buy(I64F64::from_num(50)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    assert_eq!(curve.buy_token(I64F64::from_num(50)).unwrap(), quoted_cost);
+
+    let quoted_refund = curve.quote_sell(I64F64::from_num(20)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(50));
+    assert_eq!(
+        curve.sell_token(I64F64::from_num(20)).unwrap(),
+        quoted_refund
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(30));
+
+    // Quotes are validated like trades
+    assert!(curve.quote_buy(I64F64::from_num(-1)).is_err());
+    assert!(curve.quote_sell(I64F64::from_num(31)).is_err());
+}
+
+#[test]
+fn test_exponential_buy_with_reserve() {
+    let mut curve = Exponential::new(0.001, 2.0).unwrap();
+    curve.buy_token(I64F64::from_num(20)).unwrap();
+
+    // S' = (S^3 + 3R / c)^(1/3)
+    let tokens = curve.buy_with_reserve(I64F64::from_num(40)).unwrap();
+    let expected_supply = (20.0_f64.powi(3) + 3.0 * 40.0 / 0.001).cbrt();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(expected_supply - 20.0),
+        I64F64::from_num(0.000001),
+        "Tokens for 40 reserve",
+    );
+    assert_approx_eq(
+        curve.get_supply(),
+        I64F64::from_num(expected_supply),
+        I64F64::from_num(0.000001),
+        "Supply after buy",
+    );
+
+    // Spending the quoted cost of a token amount buys back that amount
+    let cost = curve.quote_buy(I64F64::from_num(15)).unwrap();
+    let tokens = curve.quote_buy_with_reserve(cost).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(15),
+        I64F64::from_num(0.000001),
+        "Inverse of quote_buy",
+    );
+
+    assert!(matches!(
+        curve.buy_with_reserve(I64F64::from_num(-1)),
+        Err(BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_exponential_exact_output() {
+    let mut curve = Exponential::new(0.001, 2.0).unwrap();
+
+    let quoted = curve.quote_buy_exact_tokens(I64F64::from_num(50)).unwrap();
+    assert_eq!(quoted, curve.quote_buy(I64F64::from_num(50)).unwrap());
+    assert_eq!(
+        curve.buy_exact_tokens(I64F64::from_num(50)).unwrap(),
+        quoted
+    );
+
+    // Selling for the refund of 20 tokens sells 20 tokens
+    let refund = curve.quote_sell(I64F64::from_num(20)).unwrap();
+    let tokens = curve.sell_for_reserve(refund).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(20),
+        I64F64::from_num(0.000001),
+        "Tokens sold",
+    );
+    assert_approx_eq(
+        curve.get_supply(),
+        I64F64::from_num(30),
+        I64F64::from_num(0.000001),
+        "Supply after sell",
+    );
+
+    assert!(matches!(
+        curve.sell_for_reserve(I64F64::from_num(10)),
+        Err(BondingCurveError::InsufficientReserve { .. })
+    ));
+}
+
+#[test]
+fn test_exponential_reserve_accounting() {
+    let mut curve = Exponential::new(0.001, 2.0).unwrap();
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+
+    // The reserve collects buy costs and pays out sell refunds
+    let cost = curve.buy_token(I64F64::from_num(60)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost));
+    let refund = curve.sell_token(I64F64::from_num(25)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost - refund));
+    let tokens = curve.sell_for_reserve(I64F64::from_num(1)).unwrap();
+    assert!(tokens > I64F64::from_num(0));
+    assert_eq!(
+        curve.get_reserve(),
+        Some(cost - refund - I64F64::from_num(1))
+    );
+
+    // Selling the whole supply drains the reserve
+    curve.sell_token(curve.get_supply()).unwrap();
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        I64F64::from_num(0),
+        I64F64::from_num(0.000001),
+        "Reserve after selling everything",
+    );
+}
+
+#[test]
+fn test_exponential_with_supply() {
+    let mut seeded = Exponential::with_supply(0.001, 2.0, 40.0).unwrap();
+    let mut bought = Exponential::new(0.001, 2.0).unwrap();
+    let cost = bought.buy_token(I64F64::from_num(40)).unwrap();
+
+    // The seeded reserve equals the integral of the price up to the initial supply
+    assert_eq!(seeded.get_supply(), I64F64::from_num(40));
+    assert_approx_eq(
+        seeded.get_reserve().unwrap(),
+        cost,
+        I64F64::from_num(0.000001),
+        "Seeded reserve",
+    );
+    assert_approx_eq(
+        seeded.get_price().unwrap(),
+        bought.get_price().unwrap(),
+        I64F64::from_num(0.000001),
+        "Seeded price",
+    );
+    seeded.sell_token(I64F64::from_num(40)).unwrap();
+
+    let result = Exponential::<I64F64>::with_supply(0.001, 2.0, f64::NAN);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+}
@@ -0,0 +1,208 @@
+use bonding_curves::{
+    Bancor, BondingCurve, BondingCurveError, FeeBalance, FeeConfig, FeeCurve, FeeRecipient,
+    FeeSide, Linear,
+};
+use fixed::types::{I16F16, I64F64};
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn linear_with_fees(fee_side: FeeSide) -> FeeCurve<Linear> {
+    // 1% on buys, 2% on sells, protocol keeps 25% of every fee
+    let config = FeeConfig::new(100, 200, 2500, fee_side).unwrap();
+    FeeCurve::new(Linear::new(0.01).unwrap(), config)
+}
+
+#[test]
+fn test_fee_config_validation() {
+    assert!(FeeConfig::new(0, 0, 0, FeeSide::Input).is_ok());
+    assert!(FeeConfig::new(9999, 9999, 10000, FeeSide::Output).is_ok());
+
+    let result = FeeConfig::new(10000, 0, 0, FeeSide::Input);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "buy_fee_bps"
+    ));
+    let result = FeeConfig::new(0, 10001, 0, FeeSide::Input);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "sell_fee_bps"
+    ));
+    let result = FeeConfig::new(0, 0, 10001, FeeSide::Input);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "protocol_share_bps"
+    ));
+}
+
+#[test]
+fn test_fee_overflow_leaves_inner_curve_untouched() {
+    // Nearly all of each buy goes to the creator, whose balance eventually overflows
+    let config = FeeConfig::new(9999, 0, 0, FeeSide::Input).unwrap();
+    let mut curve = FeeCurve::new(Linear::<I16F16>::new(0.01).unwrap(), config);
+    let reserve = I16F16::from_num(3);
+    loop {
+        let supply = curve.get_supply();
+        let reserve_balance = curve.get_reserve();
+        let fees = curve.fee_balance(FeeRecipient::Creator);
+        let Err(error) = curve.buy_with_reserve(reserve) else {
+            continue;
+        };
+        assert!(matches!(error, BondingCurveError::Overflow { .. }));
+        assert_eq!(curve.get_supply(), supply);
+        assert_eq!(curve.get_reserve(), reserve_balance);
+        assert_eq!(curve.fee_balance(FeeRecipient::Creator), fees);
+        break;
+    }
+}
+
+#[test]
+fn test_fee_on_input_buy() {
+    let mut curve = linear_with_fees(FeeSide::Input);
+    let mut plain = Linear::new(0.01).unwrap();
+
+    // 1% of the 100 reserve paid is kept as a fee, the curve receives the other 99
+    let tokens = curve.buy_with_reserve(I64F64::from_num(100)).unwrap();
+    assert_eq!(
+        tokens,
+        plain.buy_with_reserve(I64F64::from_num(99)).unwrap()
+    );
+    assert_eq!(curve.get_reserve(), plain.get_reserve());
+
+    // The fee is split 25% / 75% between protocol and creator
+    let protocol = curve.fee_balance(FeeRecipient::Protocol);
+    let creator = curve.fee_balance(FeeRecipient::Creator);
+    assert_eq!(protocol.reserve, I64F64::from_num(0.25));
+    assert_eq!(creator.reserve, I64F64::from_num(0.75));
+    assert_eq!(protocol.tokens, I64F64::from_num(0));
+
+    // Fees that are not exactly representable are rounded up
+    let fee_before = protocol.reserve + creator.reserve;
+    curve.buy_with_reserve(I64F64::from_num(0.3)).unwrap();
+    let protocol = curve.fee_balance(FeeRecipient::Protocol);
+    let creator = curve.fee_balance(FeeRecipient::Creator);
+    assert!(protocol.reserve + creator.reserve - fee_before >= I64F64::from_num(0.3) / 100);
+}
+
+#[test]
+fn test_fee_exact_output_matches_exact_input() {
+    for fee_side in [FeeSide::Input, FeeSide::Output] {
+        let mut curve = linear_with_fees(fee_side);
+        curve.buy_exact_tokens(I64F64::from_num(100)).unwrap();
+
+        // Spending the quoted cost of N tokens buys N tokens
+        let cost = curve.quote_buy_exact_tokens(I64F64::from_num(40)).unwrap();
+        let tokens = curve.quote_buy_with_reserve(cost).unwrap();
+        assert_approx_eq(
+            tokens,
+            I64F64::from_num(40),
+            I64F64::from_num(0.000001),
+            "Tokens for the exact-output cost",
+        );
+
+        // Selling the quoted tokens for R returns R
+        let tokens = curve.quote_sell_for_reserve(I64F64::from_num(20)).unwrap();
+        let refund = curve.quote_sell(tokens).unwrap();
+        assert_approx_eq(
+            refund,
+            I64F64::from_num(20),
+            I64F64::from_num(0.000001),
+            "Refund for the exact-output tokens",
+        );
+    }
+}
+
+#[test]
+fn test_fee_on_output_sell() {
+    let mut curve = linear_with_fees(FeeSide::Output);
+    curve.buy_exact_tokens(I64F64::from_num(200)).unwrap();
+    let buy_fees = curve.fee_balance(FeeRecipient::Creator);
+    assert!(buy_fees.tokens > I64F64::from_num(0));
+    assert_eq!(buy_fees.reserve, I64F64::from_num(0));
+
+    // 2% of the 87.5 refund for the top 50 tokens is kept as a fee
+    let supply = curve.get_supply();
+    let gross = curve.curve.quote_sell(I64F64::from_num(50)).unwrap();
+    let refund = curve.sell_token(I64F64::from_num(50)).unwrap();
+    assert_approx_eq(
+        refund,
+        gross * I64F64::from_num(0.98),
+        I64F64::from_num(0.000001),
+        "Refund after fee",
+    );
+    assert_eq!(curve.get_supply(), supply - I64F64::from_num(50));
+    let protocol = curve.fee_balance(FeeRecipient::Protocol);
+    let creator = curve.fee_balance(FeeRecipient::Creator);
+    assert_eq!(protocol.reserve + creator.reserve, gross - refund);
+}
+
+#[test]
+fn test_fee_on_input_sell_withholds_tokens() {
+    let mut curve = linear_with_fees(FeeSide::Input);
+    curve.buy_exact_tokens(I64F64::from_num(200)).unwrap();
+
+    // 2% of the 100 tokens sold stay with the fee recipients instead of being sold
+    curve.sell_token(I64F64::from_num(100)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(102));
+    let protocol = curve.fee_balance(FeeRecipient::Protocol);
+    let creator = curve.fee_balance(FeeRecipient::Creator);
+    assert_eq!(protocol.tokens, I64F64::from_num(0.5));
+    assert_eq!(creator.tokens, I64F64::from_num(1.5));
+}
+
+#[test]
+fn test_fee_withdrawal() {
+    let mut curve = linear_with_fees(FeeSide::Input);
+    curve.buy_with_reserve(I64F64::from_num(100)).unwrap();
+
+    let creator = curve.withdraw_fees(FeeRecipient::Creator);
+    assert!(creator.reserve > I64F64::from_num(0));
+    assert_eq!(
+        curve.fee_balance(FeeRecipient::Creator),
+        FeeBalance::default()
+    );
+    assert!(curve.fee_balance(FeeRecipient::Protocol).reserve > I64F64::from_num(0));
+    assert_eq!(
+        curve.withdraw_fees(FeeRecipient::Creator),
+        FeeBalance::default()
+    );
+}
+
+#[test]
+fn test_fee_wrapping_bancor() {
+    let config = FeeConfig::new(100, 100, 10000, FeeSide::Input).unwrap();
+    let mut curve = FeeCurve::new(Bancor::new(1000, 10000, 0.2).unwrap(), config);
+    let mut plain = Bancor::new(1000, 10000, 0.2).unwrap();
+
+    // Bancor's buy_token takes reserve, so the fee comes off the reserve paid
+    assert!(curve.buys_with_reserve());
+    let tokens = curve.buy_token(I64F64::from_num(100)).unwrap();
+    assert_eq!(tokens, plain.buy_token(I64F64::from_num(99)).unwrap());
+    assert_eq!(
+        curve.fee_balance(FeeRecipient::Protocol).reserve,
+        I64F64::from_num(1)
+    );
+    assert_eq!(curve.get_reserve(), plain.get_reserve());
+
+    // Slippage limits apply to the amounts after fees
+    let quoted = curve.quote_sell(I64F64::from_num(50)).unwrap();
+    let result = curve.sell_token_min_out(I64F64::from_num(50), quoted + I64F64::DELTA);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::SlippageExceeded { .. })
+    ));
+    assert_eq!(
+        curve
+            .sell_token_min_out(I64F64::from_num(50), quoted)
+            .unwrap(),
+        quoted
+    );
+}
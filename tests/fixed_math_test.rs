@@ -0,0 +1,187 @@
+use bonding_curves::{BondingCurveError, exp_fixed, ln_fixed, pow_fixed, sqrt_fixed};
+use fixed::consts;
+use fixed::types::{I64F64, I80F48};
+
+// Checks |actual - expected| against a bound expressed in units of I64F64::DELTA
+fn assert_within_delta(actual: I64F64, expected: I64F64, deltas: i128, message: &str) {
+    let error = (actual - expected).abs();
+    assert!(
+        error <= I64F64::DELTA * deltas,
+        "{}: {} != {} (error {})",
+        message,
+        actual,
+        expected,
+        error
+    );
+}
+
+#[test]
+fn test_exp_fixed_accuracy() {
+    assert_eq!(exp_fixed(I64F64::from_num(0)).unwrap(), I64F64::from_num(1));
+    assert_within_delta(
+        exp_fixed(I64F64::from_num(1)).unwrap(),
+        I64F64::from_num(consts::E),
+        2,
+        "e^1",
+    );
+    // e^(ln 2) = 2 and e^(-ln 2) = 0.5
+    assert_within_delta(
+        exp_fixed(I64F64::from_num(consts::LN_2)).unwrap(),
+        I64F64::from_num(2),
+        2,
+        "e^ln2",
+    );
+    assert_within_delta(
+        exp_fixed(-I64F64::from_num(consts::LN_2)).unwrap(),
+        I64F64::from_num(0.5),
+        2,
+        "e^-ln2",
+    );
+
+    // Large results keep a relative error of one DELTA
+    let large = exp_fixed(I64F64::from_num(30)).unwrap();
+    let expected = I64F64::from_num(30.0_f64.exp());
+    assert!(((large - expected) / expected).abs() < I64F64::from_num(1e-15));
+
+    // Results beyond the type's range are reported instead of wrapping
+    assert!(exp_fixed(I64F64::from_num(44)).is_err());
+    assert_eq!(
+        exp_fixed(I64F64::from_num(-100)).unwrap(),
+        I64F64::from_num(0)
+    );
+}
+
+#[test]
+fn test_ln_fixed_accuracy() {
+    assert_eq!(ln_fixed(I64F64::from_num(1)).unwrap(), I64F64::from_num(0));
+    assert_within_delta(
+        ln_fixed(I64F64::from_num(2)).unwrap(),
+        I64F64::from_num(consts::LN_2),
+        2,
+        "ln 2",
+    );
+    assert_within_delta(
+        ln_fixed(I64F64::from_num(10)).unwrap(),
+        I64F64::from_num(consts::LN_10),
+        2,
+        "ln 10",
+    );
+    assert_within_delta(
+        ln_fixed(I64F64::from_num(consts::E)).unwrap(),
+        I64F64::from_num(1),
+        2,
+        "ln e",
+    );
+    assert_within_delta(
+        ln_fixed(I64F64::from_num(0.5)).unwrap(),
+        -I64F64::from_num(consts::LN_2),
+        2,
+        "ln 0.5",
+    );
+    // ln(2^40) = 40 ln 2
+    assert_within_delta(
+        ln_fixed(I64F64::from_num(1u64 << 40)).unwrap(),
+        I64F64::from_num(consts::LN_2) * 40,
+        64,
+        "ln 2^40",
+    );
+
+    assert!(ln_fixed(I64F64::from_num(0)).is_err());
+    assert!(ln_fixed(I64F64::from_num(-1)).is_err());
+}
+
+#[test]
+fn test_pow_fixed_accuracy() {
+    assert_within_delta(
+        pow_fixed(I64F64::from_num(2), I64F64::from_num(10)).unwrap(),
+        I64F64::from_num(1024),
+        1024 * 22 + 1,
+        "2^10",
+    );
+    assert_within_delta(
+        pow_fixed(I64F64::from_num(2), I64F64::from_num(0.5)).unwrap(),
+        I64F64::from_num(consts::SQRT_2),
+        4,
+        "2^0.5",
+    );
+    assert_eq!(
+        pow_fixed(I64F64::from_num(7), I64F64::from_num(1)).unwrap(),
+        I64F64::from_num(7)
+    );
+    assert_eq!(
+        pow_fixed(I64F64::from_num(0), I64F64::from_num(3)).unwrap(),
+        I64F64::from_num(0)
+    );
+    assert_eq!(
+        pow_fixed(I64F64::from_num(0), I64F64::from_num(0)).unwrap(),
+        I64F64::from_num(1)
+    );
+    assert!(pow_fixed(I64F64::from_num(-2), I64F64::from_num(0.5)).is_err());
+    assert!(pow_fixed(I64F64::from_num(0), I64F64::from_num(-1)).is_err());
+    assert!(pow_fixed(I64F64::from_num(1000), I64F64::from_num(10)).is_err());
+}
+
+#[test]
+fn test_sqrt_fixed_accuracy() {
+    // Perfect squares are exact
+    assert_eq!(
+        sqrt_fixed(I64F64::from_num(4)).unwrap(),
+        I64F64::from_num(2)
+    );
+    assert_eq!(
+        sqrt_fixed(I64F64::from_num(2.25)).unwrap(),
+        I64F64::from_num(1.5)
+    );
+    assert_eq!(
+        sqrt_fixed(I64F64::from_num(1_000_000)).unwrap(),
+        I64F64::from_num(1000)
+    );
+    assert_eq!(
+        sqrt_fixed(I64F64::from_num(0.0625)).unwrap(),
+        I64F64::from_num(0.25)
+    );
+    assert_within_delta(
+        sqrt_fixed(I64F64::from_num(2)).unwrap(),
+        I64F64::from_num(consts::SQRT_2),
+        1,
+        "sqrt 2",
+    );
+    assert_eq!(
+        sqrt_fixed(I64F64::from_num(0)).unwrap(),
+        I64F64::from_num(0)
+    );
+    assert!(sqrt_fixed(I64F64::from_num(-1)).is_err());
+}
+
+#[test]
+fn test_fixed_math_is_generic() {
+    let value = exp_fixed(I80F48::from_num(1)).unwrap();
+    assert!((value - I80F48::from_num(consts::E)).abs() <= I80F48::DELTA * 2);
+    let value = sqrt_fixed(I80F48::from_num(1u64 << 60)).unwrap();
+    assert_eq!(value, I80F48::from_num(1u64 << 30));
+}
+
+#[test]
+fn test_fixed_math_errors() {
+    assert_eq!(
+        ln_fixed(I64F64::from_num(0)),
+        Err(BondingCurveError::DomainError { operation: "ln" })
+    );
+    assert_eq!(
+        sqrt_fixed(I64F64::from_num(-4)),
+        Err(BondingCurveError::DomainError { operation: "sqrt" })
+    );
+    assert_eq!(
+        pow_fixed(I64F64::from_num(-2), I64F64::from_num(0.5)),
+        Err(BondingCurveError::DomainError { operation: "pow" })
+    );
+    // e^50 is beyond the 64 integer bits of I64F64
+    assert_eq!(
+        exp_fixed(I64F64::from_num(50)),
+        Err(BondingCurveError::Overflow { operation: "exp" })
+    );
+    assert_eq!(
+        pow_fixed(I64F64::from_num(1000), I64F64::from_num(10)),
+        Err(BondingCurveError::Overflow { operation: "pow" })
+    );
+}
@@ -0,0 +1,110 @@
+use bonding_curves::{
+    Bancor, BondingCurve, Exponential, FeeConfig, FeeCurve, FeeSide, Linear, Logarithmic, Sigmoid,
+};
+use fixed::traits::Fixed;
+use fixed::types::{I64F64, I80F48, U64F64};
+
+// Buys `amount` and sells everything bought, returning (cost, refund)
+fn round_trip<T: Fixed, C: BondingCurve<T>>(curve: &mut C, amount: T) -> (T, T) {
+    let supply_before = curve.get_supply();
+    let cost = curve.buy_exact_tokens(amount).unwrap();
+    assert_eq!(curve.get_supply(), supply_before + amount);
+    let refund = curve.sell_token(amount).unwrap();
+    assert_eq!(curve.get_supply(), supply_before);
+    (cost, refund)
+}
+
+// Quotes round in the pool's favor by a few ulps: `actual` lies in [expected, expected + tolerance]
+fn assert_rounded_up<T: Fixed>(actual: T, expected: T, tolerance: T) {
+    assert!(
+        actual >= expected && actual - expected <= tolerance,
+        "{actual} is not within {tolerance} above {expected}"
+    );
+}
+
+#[test]
+fn test_unsigned_linear() {
+    let mut curve = Linear::<U64F64>::new(0.5).unwrap();
+    let tolerance = U64F64::DELTA * 8;
+    let cost = curve.buy_token(U64F64::from_num(100)).unwrap();
+    assert_rounded_up(cost, U64F64::from_num(2500), tolerance);
+    assert_eq!(curve.get_price().unwrap(), U64F64::from_num(50));
+
+    let tokens = curve
+        .quote_buy_with_reserve(U64F64::from_num(7500))
+        .unwrap();
+    assert_rounded_up(U64F64::from_num(100), tokens, tolerance);
+
+    let (cost, refund) = round_trip(&mut curve, U64F64::from_num(25));
+    assert_rounded_up(cost, refund, tolerance * 2);
+    assert!(curve.get_reserve().unwrap() >= U64F64::from_num(2500));
+}
+
+#[test]
+fn test_unsigned_bancor() {
+    let mut curve = Bancor::<U64F64>::new(1000, 10000, 0.5).unwrap();
+    let tokens = curve.buy_token(U64F64::from_num(210)).unwrap();
+    assert!((tokens.to_num::<f64>() - 1000.0).abs() < 1e-9);
+
+    let refund = curve.sell_token(tokens).unwrap();
+    assert!((refund.to_num::<f64>() - 210.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_unsigned_exponential() {
+    let mut curve = Exponential::<U64F64>::new(0.001, 2.0).unwrap();
+    let (cost, refund) = round_trip(&mut curve, U64F64::from_num(30));
+    assert!((cost.to_num::<f64>() - 9.0).abs() < 1e-9);
+    assert_rounded_up(cost, refund, U64F64::from_num(1e-12));
+}
+
+#[test]
+fn test_wide_integer_range() {
+    // 10^10 tokens on a unit slope cost 5 * 10^19, beyond the range of I64F64
+    let mut curve = Linear::<I80F48>::new(1.0).unwrap();
+    let cost = curve
+        .buy_token(I80F48::from_num(10_000_000_000u64))
+        .unwrap();
+    assert_rounded_up(
+        cost,
+        I80F48::from_num(50_000_000_000_000_000_000u128),
+        I80F48::DELTA * 16,
+    );
+    assert!(cost > I64F64::MAX);
+}
+
+#[test]
+fn test_signed_curves_over_other_types() {
+    let mut log = Logarithmic::<I80F48>::new(10.0, 1.0).unwrap();
+    let (cost, refund) = round_trip(&mut log, I80F48::from_num(50));
+    assert!(cost > 0);
+    assert_rounded_up(cost, refund, I80F48::from_num(1e-10));
+
+    let mut sigmoid = Sigmoid::<I80F48>::new(100.0, 0.1, 50.0).unwrap();
+    let (cost, refund) = round_trip(&mut sigmoid, I80F48::from_num(50));
+    assert!(cost > 0);
+    assert_rounded_up(cost, refund, I80F48::from_num(1e-10));
+}
+
+#[test]
+fn test_precision_matches_type() {
+    let wide = Exponential::<I64F64>::with_supply(0.001, 2.0, 10.0).unwrap();
+    let narrow = Exponential::<I80F48>::with_supply(0.001, 2.0, 10.0).unwrap();
+    let wide_price: f64 = wide.get_price().unwrap().to_num();
+    let narrow_price: f64 = narrow.get_price().unwrap().to_num();
+    assert!((wide_price - 0.1).abs() < 1e-15);
+    assert!((narrow_price - 0.1).abs() < 1e-12);
+}
+
+#[test]
+fn test_fee_curve_over_unsigned_curve() {
+    let config = FeeConfig::new(100, 100, 5000, FeeSide::Input).unwrap();
+    let mut curve = FeeCurve::new(Linear::<U64F64>::new(0.5).unwrap(), config);
+    let paid = curve.buy_exact_tokens(U64F64::from_num(100)).unwrap();
+    assert!(paid > U64F64::from_num(2500));
+    assert_rounded_up(
+        curve.curve.get_reserve().unwrap(),
+        U64F64::from_num(2500),
+        U64F64::DELTA * 8,
+    );
+}
@@ -0,0 +1,252 @@
+use bonding_curves::{
+    BondingCurve, BondingCurveError, FeeConfig, FeeCurve, FeeSide, GraduationCurve,
+    GraduationTarget, LaunchCurve, Linear,
+};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn assert_invalid<T: std::fmt::Debug>(result: Result<T, BondingCurveError>, field: &str) {
+    assert!(
+        matches!(
+            &result,
+            Err(BondingCurveError::InvalidParameter { name, .. }) if name == field
+        ),
+        "expected an invalid {field}, got {result:?}"
+    );
+}
+
+fn num(value: f64) -> I64F64 {
+    I64F64::from_num(value)
+}
+
+fn linear_until(target: GraduationTarget) -> GraduationCurve<Linear> {
+    GraduationCurve::new(Linear::new(0.01).unwrap(), target).unwrap()
+}
+
+#[test]
+fn test_graduation_target_validation() {
+    let curve = || Linear::<I64F64>::new(0.01).unwrap();
+    assert_invalid(
+        GraduationCurve::new(curve(), GraduationTarget::Reserve(num(0.0))),
+        "target_reserve",
+    );
+    assert_invalid(
+        GraduationCurve::new(curve(), GraduationTarget::Supply(num(-1.0))),
+        "target_supply",
+    );
+    assert_invalid(
+        GraduationCurve::new(
+            curve(),
+            GraduationTarget::MarketCap {
+                market_cap: num(100.0),
+                total_supply: num(0.0),
+            },
+        ),
+        "total_supply",
+    );
+
+    // A launch curve sells at most its 793.1 real tokens, at a final market cap near 411
+    let launch = || LaunchCurve::<I64F64>::new(1073.0, 30.0, 793.1, 1000.0).unwrap();
+    assert_invalid(
+        GraduationCurve::new(launch(), GraduationTarget::Supply(num(800.0))),
+        "target_supply",
+    );
+    assert_invalid(
+        GraduationCurve::new(launch(), GraduationTarget::Reserve(num(100.0))),
+        "target_reserve",
+    );
+    assert_invalid(
+        GraduationCurve::new(
+            launch(),
+            GraduationTarget::MarketCap {
+                market_cap: num(500.0),
+                total_supply: num(1000.0),
+            },
+        ),
+        "target_market_cap",
+    );
+}
+
+#[test]
+fn test_graduation_cuts_crossing_token_order() {
+    let mut curve = linear_until(GraduationTarget::Supply(num(100.0)));
+    curve.buy_exact_tokens(num(60.0)).unwrap();
+    assert!(!curve.is_completed());
+    assert_eq!(curve.tokens_to_graduation().unwrap(), num(40.0));
+
+    // Asking for 60 more buys the 40 left and pays only for them
+    let quote = curve.quote_buy_exact_tokens(num(40.0)).unwrap();
+    let fill = curve.fill_exact_tokens(num(60.0)).unwrap();
+    assert_eq!(fill.tokens, num(40.0));
+    assert_eq!(fill.reserve, quote);
+    assert!(fill.graduated);
+    assert!(curve.is_completed());
+    assert_eq!(curve.get_supply(), num(100.0));
+}
+
+#[test]
+fn test_graduation_refunds_excess_reserve() {
+    // P = 0.01 * S holds a reserve of 0.005 * S^2
+    let mut curve = linear_until(GraduationTarget::Reserve(num(50.0)));
+    curve.buy_with_reserve(num(30.0)).unwrap();
+    assert!(!curve.is_completed());
+
+    let fill = curve.fill_with_reserve(num(40.0)).unwrap();
+    assert_eq!(fill.reserve, num(20.0));
+    assert_eq!(fill.refund, num(20.0));
+    assert!(fill.graduated);
+    assert_eq!(curve.get_reserve(), Some(num(50.0)));
+    assert_approx_eq(curve.get_supply(), num(100.0), num(1e-9), "Supply");
+}
+
+#[test]
+fn test_graduation_at_market_cap() {
+    let launch = LaunchCurve::new(1073.0, 30.0, 793.1, 1000.0).unwrap();
+    let target = GraduationTarget::MarketCap {
+        market_cap: num(100.0),
+        total_supply: num(1000.0),
+    };
+    let mut curve = GraduationCurve::new(launch, target).unwrap();
+
+    // A market cap of 100 is a price of 0.1: 32190 / (1073 - S)^2 = 0.1
+    let pool_tokens = (32190.0f64 / 0.1).sqrt();
+    let fill = curve.fill_with_reserve(num(1000.0)).unwrap();
+    assert_approx_eq(fill.tokens, num(1073.0 - pool_tokens), num(1e-9), "Tokens");
+    assert_approx_eq(
+        fill.reserve,
+        num(32190.0 / pool_tokens - 30.0),
+        num(1e-9),
+        "Reserve",
+    );
+    assert_eq!(fill.reserve + fill.refund, num(1000.0));
+    assert!(curve.is_completed());
+    assert!(curve.curve.market_cap().unwrap() >= num(100.0));
+    assert_approx_eq(
+        curve.curve.market_cap().unwrap(),
+        num(100.0),
+        num(1e-9),
+        "Market cap",
+    );
+}
+
+#[test]
+fn test_completed_curve_rejects_trades() {
+    let mut curve = linear_until(GraduationTarget::Supply(num(100.0)));
+
+    // Landing exactly on the target completes the curve too
+    let fill = curve.fill_exact_tokens(num(100.0)).unwrap();
+    assert!(fill.graduated);
+
+    let amount = num(1.0);
+    assert_eq!(
+        curve.buy_exact_tokens(amount),
+        Err(BondingCurveError::CurveCompleted)
+    );
+    assert_eq!(
+        curve.buy_with_reserve(amount),
+        Err(BondingCurveError::CurveCompleted)
+    );
+    assert_eq!(
+        curve.sell_token(amount),
+        Err(BondingCurveError::CurveCompleted)
+    );
+    assert_eq!(
+        curve.quote_sell_for_reserve(amount),
+        Err(BondingCurveError::CurveCompleted)
+    );
+    assert_eq!(curve.get_supply(), num(100.0));
+    assert!(curve.get_price().is_ok());
+
+    // A curve already past its target starts out completed
+    let past = GraduationCurve::new(
+        Linear::with_supply(0.01, 200.0).unwrap(),
+        GraduationTarget::Supply(num(100.0)),
+    )
+    .unwrap();
+    assert!(past.is_completed());
+}
+
+#[test]
+fn test_graduation_quotes_and_sells_before_target() {
+    let mut curve = linear_until(GraduationTarget::Supply(num(100.0)));
+    curve.buy_exact_tokens(num(80.0)).unwrap();
+
+    // Quotes below the target are those of the wrapped curve
+    let expected = curve.curve.quote_buy_with_reserve(num(1.0)).unwrap();
+    assert_eq!(curve.quote_buy_with_reserve(num(1.0)).unwrap(), expected);
+
+    // Selling moves the curve away from its target
+    curve.sell_token(num(30.0)).unwrap();
+    assert_eq!(curve.tokens_to_graduation().unwrap(), num(50.0));
+    assert!(matches!(
+        curve.buy_exact_tokens(num(0.0)),
+        Err(BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_graduation_reserve_target_counts_fees() {
+    // A 5% input fee keeps 95% of each reserve amount in the curve
+    let config = FeeConfig::new(500, 0, 0, FeeSide::Input).unwrap();
+    let fee_curve = FeeCurve::new(Linear::new(0.01).unwrap(), config);
+    let mut curve = GraduationCurve::new(fee_curve, GraduationTarget::Reserve(num(100.0))).unwrap();
+
+    let fill = curve.fill_with_reserve(num(1000.0)).unwrap();
+    assert!(fill.graduated);
+    assert!(curve.is_completed());
+    assert!(curve.get_reserve().unwrap() >= num(100.0));
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        num(100.0),
+        num(1e-9),
+        "Reserve",
+    );
+    assert_approx_eq(fill.reserve, num(100.0 / 0.95), num(1e-9), "Paid");
+    assert_eq!(fill.reserve + fill.refund, num(1000.0));
+    assert_approx_eq(
+        curve.get_supply(),
+        num(200.0_f64.sqrt() * 10.0),
+        num(1e-9),
+        "Supply",
+    );
+}
+
+#[test]
+fn test_graduation_trait_buys_reject_cut_orders() {
+    let mut curve = linear_until(GraduationTarget::Supply(num(100.0)));
+    curve.buy_exact_tokens(num(90.0)).unwrap();
+    let cost = curve.quote_buy_exact_tokens(num(10.0)).unwrap();
+
+    // An order the target would cut fails, limits or not, and leaves the curve untouched
+    assert_eq!(
+        curve.buy_exact_tokens_max_in(num(50.0), cost * num(10.0)),
+        Err(BondingCurveError::TargetExceeded {
+            requested: 50.0,
+            available: 10.0,
+        })
+    );
+    assert!(matches!(
+        curve.buy_with_reserve_min_out(num(1000.0), num(0.0)),
+        Err(BondingCurveError::TargetExceeded { .. })
+    ));
+    assert!(matches!(
+        curve.quote_buy_with_reserve(num(1000.0)),
+        Err(BondingCurveError::TargetExceeded { .. })
+    ));
+    assert_eq!(curve.get_supply(), num(90.0));
+    assert!(!curve.is_completed());
+
+    // An order that ends on the target fills in full and completes the curve
+    assert_eq!(curve.buy_exact_tokens_max_in(num(10.0), cost), Ok(cost));
+    assert!(curve.is_completed());
+}
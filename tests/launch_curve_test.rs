@@ -0,0 +1,171 @@
+use bonding_curves::{BondingCurve, BondingCurveError, ConstantProduct, LaunchCurve};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn assert_invalid<T: std::fmt::Debug>(result: Result<T, BondingCurveError>, field: &str) {
+    assert!(
+        matches!(
+            &result,
+            Err(BondingCurveError::InvalidParameter { name, .. }) if name == field
+        ),
+        "expected an invalid {field}, got {result:?}"
+    );
+}
+
+fn num(value: f64) -> I64F64 {
+    I64F64::from_num(value)
+}
+
+// A launch scaled down from 1.073B virtual tokens, 30 virtual reserve and 793.1M for sale
+fn launch() -> LaunchCurve {
+    LaunchCurve::new(1073.0, 30.0, 793.1, 1000.0).unwrap()
+}
+
+#[test]
+fn test_launch_curve_new_valid() {
+    let curve = launch();
+    assert_eq!(curve.get_supply(), num(0.0));
+    assert_eq!(curve.get_reserve(), Some(num(0.0)));
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        num(30.0 / 1073.0),
+        num(1e-15),
+        "Price",
+    );
+    assert_eq!(curve.virtual_reserves().unwrap(), (num(1073.0), num(30.0)));
+    assert_eq!(curve.real_reserves(), (num(793.1), num(0.0)));
+    assert_approx_eq(
+        curve.market_cap().unwrap(),
+        num(30.0 / 1073.0 * 1000.0),
+        num(1e-12),
+        "Market cap",
+    );
+}
+
+#[test]
+fn test_launch_curve_new_invalid() {
+    assert_invalid(
+        LaunchCurve::<I64F64>::new(0.0, 30.0, 0.0, 1000.0),
+        "virtual_token_reserve",
+    );
+    assert_invalid(
+        LaunchCurve::<I64F64>::new(1073.0, 0.0, 793.1, 1000.0),
+        "virtual_reserve",
+    );
+    assert_invalid(
+        LaunchCurve::<I64F64>::new(1073.0, 30.0, -1.0, 1000.0),
+        "real_token_reserve",
+    );
+
+    // The real inventory cannot exceed the virtual one or the total supply
+    assert_invalid(
+        LaunchCurve::<I64F64>::new(1073.0, 30.0, 1100.0, 2000.0),
+        "real_token_reserve",
+    );
+    assert_invalid(
+        LaunchCurve::<I64F64>::new(1073.0, 30.0, 793.1, 700.0),
+        "total_supply",
+    );
+}
+
+#[test]
+fn test_launch_curve_moves_virtual_and_real_balances() {
+    let mut curve = launch();
+
+    // 100 tokens cost 30 * 100 / 973, against the virtual reserve alone
+    let cost = curve.buy_token(num(100.0)).unwrap();
+    assert_approx_eq(cost, num(30.0 * 100.0 / 973.0), num(1e-12), "Cost");
+    assert_eq!(curve.real_reserves(), (num(693.1), cost));
+    assert_eq!(
+        curve.virtual_reserves().unwrap(),
+        (num(973.0), num(30.0) + cost)
+    );
+    assert_approx_eq(
+        curve.market_cap().unwrap(),
+        (num(30.0) + cost) / num(973.0) * num(1000.0),
+        num(1e-12),
+        "Market cap",
+    );
+
+    // Selling returns tokens to the inventory and pays out of the real reserve only
+    let refund = curve.sell_token(num(100.0)).unwrap();
+    assert!(refund <= cost);
+    assert_eq!(curve.real_reserves().0, num(793.1));
+    assert_eq!(curve.virtual_reserves().unwrap().0, num(1073.0));
+    assert_eq!(curve.virtual_reserve, num(30.0));
+}
+
+#[test]
+fn test_launch_curve_matches_constant_product() {
+    let mut curve = launch();
+    let mut pool = ConstantProduct::new(1073.0, 0.0, 30.0).unwrap();
+
+    for amount in [num(12.5), num(200.0), num(1.0) / 3] {
+        assert_eq!(curve.buy_token(amount), pool.buy_token(amount));
+    }
+    assert_eq!(
+        curve.buy_with_reserve(num(4.0)),
+        pool.buy_with_reserve(num(4.0))
+    );
+    assert_eq!(
+        curve.sell_for_reserve(num(1.5)),
+        pool.sell_for_reserve(num(1.5))
+    );
+    assert_eq!(curve.get_price(), pool.get_price());
+    assert_eq!(curve.get_reserve(), pool.get_reserve());
+}
+
+#[test]
+fn test_launch_curve_sells_only_real_inventory() {
+    let mut curve = launch();
+    assert_eq!(curve.max_supply(), num(793.1));
+
+    assert!(matches!(
+        curve.buy_token(num(800.0)),
+        Err(BondingCurveError::InsufficientSupply { available, .. }) if available == num(793.1)
+    ));
+    assert!(matches!(
+        curve.buy_with_reserve(num(1000.0)),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+    assert_eq!(curve.get_supply(), num(0.0));
+
+    // Selling out leaves the virtual tokens that were never for sale
+    let cost = curve.buy_token(num(793.1)).unwrap();
+    assert_eq!(curve.real_reserves(), (num(0.0), cost));
+    assert_approx_eq(
+        curve.virtual_reserves().unwrap().0,
+        num(1073.0 - 793.1),
+        num(1e-12),
+        "Virtual tokens",
+    );
+    assert!(matches!(
+        curve.quote_buy(I64F64::DELTA),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+}
+
+#[test]
+fn test_launch_curve_with_supply() {
+    let seeded = LaunchCurve::<I64F64>::with_supply(1073.0, 30.0, 793.1, 1000.0, 250.0).unwrap();
+    let mut bought = launch();
+    let cost = bought.buy_token(num(250.0)).unwrap();
+
+    assert_eq!(seeded.get_supply(), num(250.0));
+    assert_eq!(seeded.get_reserve(), Some(cost));
+    assert_eq!(seeded.real_reserves(), bought.real_reserves());
+    assert_invalid(
+        LaunchCurve::<I64F64>::with_supply(1073.0, 30.0, 793.1, 1000.0, 800.0),
+        "initial_supply",
+    );
+}
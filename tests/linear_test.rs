@@ -1,4 +1,4 @@
-use bonding_curves::{BondingCurve, Linear};
+use bonding_curves::{BondingCurve, BondingCurveError, Linear};
 use fixed::types::I64F64;
 
 // helper function
@@ -15,22 +15,22 @@ fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message
 #[test]
 fn test_linear_new() {
     // Valid slope
-    let curve = Linear::new(0.01).unwrap();
+    let curve = Linear::<I64F64>::new(0.01).unwrap();
     assert_eq!(curve.get_supply(), I64F64::from_num(0));
     assert_eq!(curve.get_price().unwrap(), I64F64::from_num(0));
 
     // Invalid slope (zero)
-    let result = Linear::new(0.0);
+    let result = Linear::<I64F64>::new(0.0);
     assert!(matches!(
         result,
-        Err(bonding_curves::BondingCurveError::InvalidInput(msg)) if msg.contains("Slope must be positive")
+        Err(bonding_curves::BondingCurveError::InvalidParameter { name, .. }) if name == "slope"
     ));
 
     // Invalid slope (negative)
-    let result = Linear::new(-0.01);
+    let result = Linear::<I64F64>::new(-0.01);
     assert!(matches!(
         result,
-        Err(bonding_curves::BondingCurveError::InvalidInput(msg)) if msg.contains("Slope must be positive")
+        Err(bonding_curves::BondingCurveError::InvalidParameter { name, .. }) if name == "slope"
     ));
 }
 
@@ -172,28 +172,28 @@ fn test_linear_invalid_inputs() {
     let result = curve.buy_token(I64F64::from_num(0));
     assert!(matches!(
         result,
-        Err(bonding_curves::BondingCurveError::InvalidInput(msg)) if msg.contains("Token amount must be positive")
+        Err(bonding_curves::BondingCurveError::ZeroAmount)
     ));
 
     // Buy negative tokens
     let result = curve.buy_token(I64F64::from_num(-10));
     assert!(matches!(
         result,
-        Err(bonding_curves::BondingCurveError::InvalidInput(msg)) if msg.contains("Token amount must be positive")
+        Err(bonding_curves::BondingCurveError::ZeroAmount)
     ));
 
     // Sell zero tokens
     let result = curve.sell_token(I64F64::from_num(0));
     assert!(matches!(
         result,
-        Err(bonding_curves::BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
+        Err(bonding_curves::BondingCurveError::ZeroAmount)
     ));
 
     // Sell negative tokens
     let result = curve.sell_token(I64F64::from_num(-10));
     assert!(matches!(
         result,
-        Err(bonding_curves::BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
+        Err(bonding_curves::BondingCurveError::ZeroAmount)
     ));
 
     // Sell more tokens than supply
@@ -201,7 +201,7 @@ fn test_linear_invalid_inputs() {
     let result = curve.sell_token(I64F64::from_num(101));
     assert!(matches!(
         result,
-        Err(bonding_curves::BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid token amount")
+        Err(bonding_curves::BondingCurveError::InsufficientSupply { .. })
     ));
 }
 
@@ -269,3 +269,197 @@ fn test_linear_precision() {
         "Price with large supply",
     );
 }
+
+#[test]
+fn test_linear_quotes() {
+    let mut curve = Linear::new(0.01).unwrap();
+
+    // Quoting does not change the curve's state
+    let quoted_cost = curve.quote_buy(I64F64::from_num(100)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    assert_eq!(curve.buy_token(I64F64::from_num(100)).unwrap(), quoted_cost);
+
+    let quoted_refund = curve.quote_sell(I64F64::from_num(40)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(100));
+    assert_eq!(
+        curve.sell_token(I64F64::from_num(40)).unwrap(),
+        quoted_refund
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(60));
+
+    // Quotes are validated like trades
+    assert!(curve.quote_buy(I64F64::from_num(0)).is_err());
+    assert!(curve.quote_sell(I64F64::from_num(61)).is_err());
+}
+
+#[test]
+fn test_linear_buy_with_reserve() {
+    let mut curve = Linear::new(0.01).unwrap();
+
+    // k * S^2 / 2 = 50  =>  S = sqrt(2 * 50 / 0.01) = 100
+    let tokens = curve.buy_with_reserve(I64F64::from_num(50)).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(100),
+        I64F64::from_num(0.0000001),
+        "Tokens for 50 reserve",
+    );
+    assert_eq!(curve.get_supply(), tokens);
+
+    // S' = sqrt(100^2 + 2 * 62.5 / 0.01) = 150
+    let quoted = curve
+        .quote_buy_with_reserve(I64F64::from_num(62.5))
+        .unwrap();
+    let tokens = curve.buy_with_reserve(I64F64::from_num(62.5)).unwrap();
+    assert_eq!(tokens, quoted);
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(50),
+        I64F64::from_num(0.0000001),
+        "Tokens for 62.5 reserve",
+    );
+
+    // Invalid reserve amounts
+    let result = curve.buy_with_reserve(I64F64::from_num(0));
+    assert!(matches!(
+        result,
+        Err(bonding_curves::BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_linear_exact_output() {
+    let mut curve = Linear::new(0.01).unwrap();
+
+    // Buying exact tokens costs the integral under the curve
+    let cost = curve.buy_exact_tokens(I64F64::from_num(200)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(200),
+        I64F64::from_num(0.0000001),
+        "Cost for 200 tokens",
+    );
+
+    // k * (200^2 - 150^2) / 2 = 87.5  =>  selling 50 tokens returns 87.5
+    let quoted = curve
+        .quote_sell_for_reserve(I64F64::from_num(87.5))
+        .unwrap();
+    let tokens = curve.sell_for_reserve(I64F64::from_num(87.5)).unwrap();
+    assert_eq!(tokens, quoted);
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(50),
+        I64F64::from_num(0.0000001),
+        "Tokens sold for 87.5 reserve",
+    );
+    assert_approx_eq(
+        curve.get_supply(),
+        I64F64::from_num(150),
+        I64F64::from_num(0.0000001),
+        "Supply after sell",
+    );
+
+    // Cannot receive more than selling the entire supply returns
+    let result = curve.sell_for_reserve(I64F64::from_num(113));
+    assert!(matches!(
+        result,
+        Err(bonding_curves::BondingCurveError::InsufficientReserve { .. })
+    ));
+    let result = curve.sell_for_reserve(I64F64::from_num(0));
+    assert!(matches!(
+        result,
+        Err(bonding_curves::BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_linear_reserve_accounting() {
+    let mut curve = Linear::new(0.01).unwrap();
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+
+    // The reserve collects buy costs and pays out sell refunds
+    let cost = curve.buy_token(I64F64::from_num(200)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost));
+    let refund = curve.sell_token(I64F64::from_num(50)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost - refund));
+    curve.buy_with_reserve(I64F64::from_num(10)).unwrap();
+    assert_eq!(
+        curve.get_reserve(),
+        Some(cost - refund + I64F64::from_num(10))
+    );
+
+    // Selling the whole supply drains the reserve
+    curve.sell_token(curve.get_supply()).unwrap();
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        I64F64::from_num(0),
+        I64F64::from_num(0.0000001),
+        "Reserve after selling everything",
+    );
+}
+
+#[test]
+fn test_linear_with_supply() {
+    // Reserve = k * S^2 / 2 = 0.01 * 100^2 / 2 = 50
+    let mut curve = Linear::with_supply(0.01, 100.0).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(100));
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        I64F64::from_num(50),
+        I64F64::from_num(0.0000001),
+        "Seeded reserve",
+    );
+    let refund = curve.sell_token(I64F64::from_num(100)).unwrap();
+    assert_approx_eq(
+        refund,
+        I64F64::from_num(50),
+        I64F64::from_num(0.0000001),
+        "Refund for the seeded supply",
+    );
+
+    let result = Linear::<I64F64>::with_supply(0.01, -1.0);
+    assert!(matches!(
+        result,
+        Err(bonding_curves::BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+}
+
+#[test]
+fn test_linear_error_fields() {
+    let mut curve = Linear::with_supply(0.5, 100.0).unwrap();
+
+    let result = curve.sell_token(I64F64::from_num(101));
+    assert_eq!(
+        result,
+        Err(BondingCurveError::InsufficientSupply {
+            requested: 101.0,
+            available: 100.0,
+        })
+    );
+
+    // The entire supply refunds 0.5 * 100^2 / 2 = 2500
+    let result = curve.sell_for_reserve(I64F64::from_num(3000));
+    assert_eq!(
+        result,
+        Err(BondingCurveError::InsufficientReserve {
+            requested: 3000.0,
+            available: 2500.0,
+        })
+    );
+
+    let result = Linear::<I64F64>::new(-2.0);
+    assert_eq!(
+        result.unwrap_err(),
+        BondingCurveError::InvalidParameter {
+            name: "slope".into(),
+            value: -2.0,
+        }
+    );
+    assert_eq!(
+        curve
+            .buy_token(I64F64::from_num(0))
+            .unwrap_err()
+            .to_string(),
+        "Amount must be positive"
+    );
+}
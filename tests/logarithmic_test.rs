@@ -22,51 +22,51 @@ fn test_logarithmic_new_valid() {
         I64F64::from_num(0.001),
         "Initial price",
     );
-    assert_eq!(curve.get_reserve(), None);
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
 }
 
 #[test]
 fn test_logarithmic_new_invalid() {
     // Zero coefficient
-    let result = Logarithmic::new(0.0, 1.0);
+    let result = Logarithmic::<I64F64>::new(0.0, 1.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and constant must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "coefficient"
     ));
 
     // Negative coefficient
-    let result = Logarithmic::new(-1.0, 1.0);
+    let result = Logarithmic::<I64F64>::new(-1.0, 1.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and constant must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "coefficient"
     ));
 
     // Zero constant
-    let result = Logarithmic::new(2.0, 0.0);
+    let result = Logarithmic::<I64F64>::new(2.0, 0.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and constant must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "constant"
     ));
 
     // Negative constant
-    let result = Logarithmic::new(2.0, -1.0);
+    let result = Logarithmic::<I64F64>::new(2.0, -1.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and constant must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "constant"
     ));
 
     // NaN coefficient
-    let result = Logarithmic::new(f64::NAN, 1.0);
+    let result = Logarithmic::<I64F64>::new(f64::NAN, 1.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and constant must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "coefficient"
     ));
 
     // Infinity constant
-    let result = Logarithmic::new(2.0, f64::INFINITY);
+    let result = Logarithmic::<I64F64>::new(2.0, f64::INFINITY);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Coefficient and constant must be positive and finite")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "constant"
     ));
 }
 
@@ -233,3 +233,140 @@ fn test_logarithmic_edge_cases() {
         "Price with large supply",
     );
 }
+
+#[test]
+fn test_logarithmic_quotes() {
+    let mut curve = Logarithmic::new(10.0, 1.0).unwrap();
+
+    // Quoting does not change the curve's state
+    let quoted_cost = curve.quote_buy(I64F64::from_num(25)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    assert_eq!(curve.buy_token(I64F64::from_num(25)).unwrap(), quoted_cost);
+
+    let quoted_refund = curve.quote_sell(I64F64::from_num(10)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(25));
+    assert_eq!(
+        curve.sell_token(I64F64::from_num(10)).unwrap(),
+        quoted_refund
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(15));
+
+    // Quotes are validated like trades
+    assert!(curve.quote_buy(I64F64::from_num(0)).is_err());
+    assert!(curve.quote_sell(I64F64::from_num(16)).is_err());
+}
+
+#[test]
+fn test_logarithmic_buy_with_reserve() {
+    let mut curve = Logarithmic::new(10.0, 1.0).unwrap();
+
+    // The numeric solver inverts the cost integral
+    let cost = curve.quote_buy(I64F64::from_num(25)).unwrap();
+    let tokens = curve.buy_with_reserve(cost).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(25),
+        I64F64::from_num(0.000001),
+        "Tokens for the cost of 25",
+    );
+    assert_eq!(curve.get_supply(), tokens);
+
+    // The tokens received never cost more than the reserve spent
+    let quoted = curve.quote_buy_with_reserve(I64F64::from_num(500)).unwrap();
+    assert!(curve.quote_buy(quoted).unwrap() <= I64F64::from_num(500));
+    assert_eq!(
+        curve.buy_with_reserve(I64F64::from_num(500)).unwrap(),
+        quoted
+    );
+
+    assert!(matches!(
+        curve.buy_with_reserve(I64F64::from_num(0)),
+        Err(BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_logarithmic_exact_output() {
+    let mut curve = Logarithmic::new(10.0, 1.0).unwrap();
+
+    let quoted = curve.quote_buy_exact_tokens(I64F64::from_num(40)).unwrap();
+    assert_eq!(quoted, curve.quote_buy(I64F64::from_num(40)).unwrap());
+    assert_eq!(
+        curve.buy_exact_tokens(I64F64::from_num(40)).unwrap(),
+        quoted
+    );
+
+    // Selling for the refund of 15 tokens sells 15 tokens
+    let refund = curve.quote_sell(I64F64::from_num(15)).unwrap();
+    let tokens = curve.sell_for_reserve(refund).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(15),
+        I64F64::from_num(0.000001),
+        "Tokens sold",
+    );
+    // The tokens sold always pay for at least the reserve received
+    assert!(curve.quote_buy(tokens).unwrap() >= refund);
+
+    let all = curve.quote_sell(curve.get_supply()).unwrap();
+    assert!(matches!(
+        curve.sell_for_reserve(all + I64F64::from_num(1)),
+        Err(BondingCurveError::InsufficientReserve { .. })
+    ));
+}
+
+#[test]
+fn test_logarithmic_reserve_accounting() {
+    let mut curve = Logarithmic::new(10.0, 1.0).unwrap();
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+
+    // The reserve collects buy costs and pays out sell refunds
+    let cost = curve.buy_token(I64F64::from_num(60)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost));
+    let refund = curve.sell_token(I64F64::from_num(25)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost - refund));
+    let tokens = curve.sell_for_reserve(I64F64::from_num(1)).unwrap();
+    assert!(tokens > I64F64::from_num(0));
+    assert_eq!(
+        curve.get_reserve(),
+        Some(cost - refund - I64F64::from_num(1))
+    );
+
+    // Selling the whole supply drains the reserve
+    curve.sell_token(curve.get_supply()).unwrap();
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        I64F64::from_num(0),
+        I64F64::from_num(0.000001),
+        "Reserve after selling everything",
+    );
+}
+
+#[test]
+fn test_logarithmic_with_supply() {
+    let mut seeded = Logarithmic::with_supply(10.0, 1.0, 40.0).unwrap();
+    let mut bought = Logarithmic::new(10.0, 1.0).unwrap();
+    let cost = bought.buy_token(I64F64::from_num(40)).unwrap();
+
+    // The seeded reserve equals the integral of the price up to the initial supply
+    assert_eq!(seeded.get_supply(), I64F64::from_num(40));
+    assert_approx_eq(
+        seeded.get_reserve().unwrap(),
+        cost,
+        I64F64::from_num(0.000001),
+        "Seeded reserve",
+    );
+    assert_approx_eq(
+        seeded.get_price().unwrap(),
+        bought.get_price().unwrap(),
+        I64F64::from_num(0.000001),
+        "Seeded price",
+    );
+    seeded.sell_token(I64F64::from_num(40)).unwrap();
+
+    let result = Logarithmic::<I64F64>::with_supply(10.0, 1.0, f64::NAN);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+}
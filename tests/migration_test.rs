@@ -0,0 +1,112 @@
+use bonding_curves::{
+    BondingCurve, BondingCurveError, GraduationCurve, GraduationTarget, LaunchCurve,
+    LeftoverTokens, Linear, MigrationConfig,
+};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn num(value: f64) -> I64F64 {
+    I64F64::from_num(value)
+}
+
+#[test]
+fn test_migration_config_validation() {
+    assert!(MigrationConfig::new(0, LeftoverTokens::Burn).is_ok());
+    assert!(MigrationConfig::new(9999, LeftoverTokens::Return).is_ok());
+    let result = MigrationConfig::new(10000, LeftoverTokens::Burn);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "fee_bps"
+    ));
+}
+
+#[test]
+fn test_migration_of_sold_out_launch_curve() {
+    let mut curve = GraduationCurve::new(
+        LaunchCurve::new(1073.0, 30.0, 793.1, 1000.0).unwrap(),
+        GraduationTarget::Supply(num(793.1)),
+    )
+    .unwrap();
+    curve.fill_with_reserve(num(1000.0)).unwrap();
+    assert!(curve.is_completed());
+
+    // 85.0 reserve at a final price of 32190 / 279.9^2, less a 1% fee, pairs with about
+    // 204.8 of the 206.9 tokens that were never for sale
+    let config = MigrationConfig::new(100, LeftoverTokens::Burn).unwrap();
+    let inventory = num(1000.0) - curve.get_supply();
+    let migration = config.migrate(&curve, inventory).unwrap();
+    let reserve = 32190.0 / 279.9 - 30.0;
+    let price = 32190.0 / (279.9f64 * 279.9);
+    assert_approx_eq(migration.fee, num(reserve * 0.01), num(1e-9), "Fee");
+    assert_eq!(
+        migration.reserve + migration.fee,
+        curve.get_reserve().unwrap()
+    );
+    assert_approx_eq(
+        migration.tokens,
+        num(reserve * 0.99 / price),
+        num(1e-9),
+        "Tokens",
+    );
+    assert_eq!(migration.tokens + migration.leftover_tokens, inventory);
+    assert_eq!(migration.burned(), migration.leftover_tokens);
+    assert_eq!(migration.returned(), num(0.0));
+
+    // The pool opens at the curve's last price, never below it
+    let pool = migration.pool().unwrap();
+    let final_price = curve.get_price().unwrap();
+    assert!(pool.get_price().unwrap() >= final_price);
+    assert_approx_eq(pool.get_price().unwrap(), final_price, num(1e-12), "Price");
+}
+
+#[test]
+fn test_migration_of_any_curve() {
+    // P = 0.01 * S at 100 tokens: a reserve of 50 at a price of 1
+    let curve = Linear::with_supply(0.01, 100.0).unwrap();
+    let config = MigrationConfig::new(0, LeftoverTokens::Return).unwrap();
+    let migration = config.migrate(&curve, num(80.0)).unwrap();
+    assert_eq!(migration.reserve, curve.get_reserve().unwrap());
+    assert_eq!(migration.fee, num(0.0));
+    assert_approx_eq(migration.tokens, num(50.0), num(1e-12), "Tokens");
+    assert_approx_eq(migration.returned(), num(30.0), num(1e-12), "Returned");
+    assert_eq!(migration.burned(), num(0.0));
+
+    // Holders can sell the curve's tokens into the pool, which pays out only its reserve
+    let mut pool = migration.pool().unwrap();
+    assert_eq!(pool.get_supply(), num(100.0));
+    let refund = pool.sell_token(num(100.0)).unwrap();
+    assert!(refund < num(50.0));
+}
+
+#[test]
+fn test_migration_errors() {
+    let config = MigrationConfig::new(100, LeftoverTokens::Burn).unwrap();
+
+    // 50 reserve less 1% at a price of 1 needs 49.5 tokens
+    let curve = Linear::with_supply(0.01, 100.0).unwrap();
+    assert!(matches!(
+        config.migrate(&curve, num(40.0)),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+    assert!(matches!(
+        config.migrate(&curve, num(-1.0)),
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "token_inventory"
+    ));
+
+    // A curve that never sold anything has no reserve to seed a pool with
+    let empty = Linear::new(0.01).unwrap();
+    assert_eq!(
+        config.migrate(&empty, num(100.0)),
+        Err(BondingCurveError::ZeroAmount)
+    );
+}
@@ -0,0 +1,256 @@
+use bonding_curves::{BondingCurve, BondingCurveError, NaturalExponential};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+// Integral of 0.5 * e^(0.01 * S) from 0 to `supply`
+fn integral(supply: f64) -> f64 {
+    0.5 / 0.01 * ((0.01 * supply).exp() - 1.0)
+}
+
+#[test]
+fn test_natural_exponential_new_valid() {
+    let curve = NaturalExponential::new(0.5, 0.01).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    // Unlike the power law, the first token is priced at the initial price
+    assert_eq!(curve.get_price().unwrap(), I64F64::from_num(0.5));
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+}
+
+#[test]
+fn test_natural_exponential_new_invalid() {
+    // Zero initial price
+    let result = NaturalExponential::<I64F64>::new(0.0, 0.01);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_price"
+    ));
+
+    // Negative growth rate
+    let result = NaturalExponential::<I64F64>::new(0.5, -0.01);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "growth_rate"
+    ));
+
+    // NaN initial price
+    let result = NaturalExponential::<I64F64>::new(f64::NAN, 0.01);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_price"
+    ));
+
+    // A growth rate so small that a / b does not fit
+    let result = NaturalExponential::<I64F64>::new(1e9, 1e-12);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "growth_rate"
+    ));
+}
+
+#[test]
+fn test_natural_exponential_price() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+
+    // The price grows by a factor e every 1 / b = 100 tokens
+    curve.buy_token(I64F64::from_num(100)).unwrap();
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        I64F64::from_num(0.5 * std::f64::consts::E),
+        I64F64::from_num(0.000001),
+        "Price after 100 tokens",
+    );
+
+    curve.buy_token(I64F64::from_num(100)).unwrap();
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        I64F64::from_num(0.5 * 2.0_f64.exp()),
+        I64F64::from_num(0.000001),
+        "Price after 200 tokens",
+    );
+}
+
+#[test]
+fn test_natural_exponential_buy_and_sell() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+
+    let cost = curve.buy_token(I64F64::from_num(100)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(integral(100.0)),
+        I64F64::from_num(0.000001),
+        "Cost for 100 tokens",
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(100));
+
+    let cost = curve.buy_token(I64F64::from_num(50)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(integral(150.0) - integral(100.0)),
+        I64F64::from_num(0.000001),
+        "Cost for additional 50 tokens",
+    );
+
+    let refund = curve.sell_token(I64F64::from_num(100)).unwrap();
+    assert_approx_eq(
+        refund,
+        I64F64::from_num(integral(150.0) - integral(50.0)),
+        I64F64::from_num(0.000001),
+        "Refund for 100 tokens",
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(50));
+}
+
+#[test]
+fn test_natural_exponential_invalid_inputs() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+
+    let result = curve.buy_token(I64F64::from_num(0));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
+
+    let result = curve.sell_token(I64F64::from_num(-10));
+    assert!(matches!(result, Err(BondingCurveError::ZeroAmount)));
+
+    // Sell more than supply
+    let result = curve.sell_token(I64F64::from_num(1));
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+}
+
+#[test]
+fn test_natural_exponential_domain_limits() {
+    let curve = NaturalExponential::<I64F64>::new(0.5, 0.01).unwrap();
+
+    // e^(bS) * max(1, a, a / b) stays within a quarter of the type's range
+    let max_supply = curve.max_supply();
+    let expected = (I64F64::MAX.to_num::<f64>() / 4.0 / 50.0).ln() / 0.01;
+    assert_approx_eq(
+        max_supply,
+        I64F64::from_num(expected),
+        I64F64::from_num(0.001),
+        "Max supply",
+    );
+    assert!(curve.price_at(max_supply).is_ok());
+    assert!(curve.cost_between(I64F64::ZERO, max_supply).is_ok());
+
+    // Far beyond it the price no longer fits, which is reported rather than saturated
+    assert!(matches!(
+        curve.price_at(max_supply * 2),
+        Err(BondingCurveError::Overflow { .. })
+    ));
+    let result = NaturalExponential::<I64F64>::with_supply(0.5, 0.01, expected * 2.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+}
+
+#[test]
+fn test_natural_exponential_quotes() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+
+    // Quoting does not change the curve's state
+    let quoted_cost = curve.quote_buy(I64F64::from_num(50)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    assert_eq!(curve.buy_token(I64F64::from_num(50)).unwrap(), quoted_cost);
+
+    let quoted_refund = curve.quote_sell(I64F64::from_num(20)).unwrap();
+    assert_eq!(
+        curve.sell_token(I64F64::from_num(20)).unwrap(),
+        quoted_refund
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(30));
+}
+
+#[test]
+fn test_natural_exponential_buy_with_reserve() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+    curve.buy_token(I64F64::from_num(20)).unwrap();
+
+    // S' = ln(1 + (I(S) + R) * b / a) / b
+    let tokens = curve.buy_with_reserve(I64F64::from_num(40)).unwrap();
+    let expected_supply = (1.0 + (integral(20.0) + 40.0) * 0.01 / 0.5).ln() / 0.01;
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(expected_supply - 20.0),
+        I64F64::from_num(0.000001),
+        "Tokens for 40 reserve",
+    );
+
+    // Spending the quoted cost of a token amount buys back that amount
+    let cost = curve.quote_buy(I64F64::from_num(15)).unwrap();
+    let tokens = curve.quote_buy_with_reserve(cost).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(15),
+        I64F64::from_num(0.000001),
+        "Inverse of quote_buy",
+    );
+}
+
+#[test]
+fn test_natural_exponential_exact_output() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+    curve.buy_exact_tokens(I64F64::from_num(50)).unwrap();
+
+    // Selling for the refund of 20 tokens sells 20 tokens
+    let refund = curve.quote_sell(I64F64::from_num(20)).unwrap();
+    let tokens = curve.sell_for_reserve(refund).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(20),
+        I64F64::from_num(0.000001),
+        "Tokens sold",
+    );
+
+    assert!(matches!(
+        curve.sell_for_reserve(I64F64::from_num(100)),
+        Err(BondingCurveError::InsufficientReserve { .. })
+    ));
+}
+
+#[test]
+fn test_natural_exponential_reserve_accounting() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+
+    let cost = curve.buy_token(I64F64::from_num(60)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost));
+    let refund = curve.sell_token(I64F64::from_num(25)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost - refund));
+
+    // Selling the whole supply drains the reserve
+    curve.sell_token(curve.get_supply()).unwrap();
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        I64F64::from_num(0),
+        I64F64::from_num(0.000001),
+        "Reserve after selling everything",
+    );
+}
+
+#[test]
+fn test_natural_exponential_with_supply() {
+    let seeded = NaturalExponential::<I64F64>::with_supply(0.5, 0.01, 40.0).unwrap();
+    let mut bought = NaturalExponential::new(0.5, 0.01).unwrap();
+    let cost = bought.buy_token(I64F64::from_num(40)).unwrap();
+
+    assert_eq!(seeded.get_supply(), I64F64::from_num(40));
+    assert_approx_eq(
+        seeded.get_reserve().unwrap(),
+        cost,
+        I64F64::from_num(0.000001),
+        "Seeded reserve",
+    );
+    assert_eq!(seeded.get_price().unwrap(), bought.get_price().unwrap());
+}
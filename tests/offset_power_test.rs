@@ -0,0 +1,151 @@
+use bonding_curves::{BondingCurve, BondingCurveError, Exponential, OffsetPower};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+// Integral of 0.001 * S^2 + 0.5 from 0 to `supply`
+fn integral(supply: f64) -> f64 {
+    0.001 / 3.0 * supply.powi(3) + 0.5 * supply
+}
+
+#[test]
+fn test_offset_power_new_valid() {
+    let curve = OffsetPower::new(0.001, 2.0, 0.5).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    assert_eq!(curve.get_price().unwrap(), I64F64::from_num(0.5));
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+}
+
+#[test]
+fn test_offset_power_new_invalid() {
+    let result = OffsetPower::<I64F64>::new(0.0, 2.0, 0.5);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "coefficient"
+    ));
+
+    let result = OffsetPower::<I64F64>::new(0.001, -2.0, 0.5);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "exponent"
+    ));
+
+    let result = OffsetPower::<I64F64>::new(0.001, 2.0, -0.5);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "base_price"
+    ));
+
+    let result = OffsetPower::<I64F64>::new(0.001, 2.0, f64::NAN);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "base_price"
+    ));
+}
+
+#[test]
+fn test_offset_power_buy_and_sell() {
+    let mut curve = OffsetPower::new(0.001, 2.0, 0.5).unwrap();
+
+    let cost = curve.buy_token(I64F64::from_num(30)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(integral(30.0)),
+        I64F64::from_num(0.000001),
+        "Cost for 30 tokens",
+    );
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        I64F64::from_num(0.001 * 900.0 + 0.5),
+        I64F64::from_num(0.000001),
+        "Price after 30 tokens",
+    );
+
+    let refund = curve.sell_token(I64F64::from_num(10)).unwrap();
+    assert_approx_eq(
+        refund,
+        I64F64::from_num(integral(30.0) - integral(20.0)),
+        I64F64::from_num(0.000001),
+        "Refund for 10 tokens",
+    );
+
+    assert!(matches!(
+        curve.sell_token(I64F64::from_num(21)),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+    assert!(matches!(
+        curve.buy_token(I64F64::from_num(-1)),
+        Err(BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_offset_power_buy_with_reserve() {
+    let mut curve = OffsetPower::new(0.001, 2.0, 0.5).unwrap();
+    curve.buy_token(I64F64::from_num(20)).unwrap();
+
+    // Spending the quoted cost of a token amount buys back that amount, whichever term
+    // dominates the price
+    for amount in [0.01, 15.0, 500.0] {
+        let cost = curve.quote_buy(I64F64::from_num(amount)).unwrap();
+        assert_approx_eq(
+            curve.quote_buy_with_reserve(cost).unwrap(),
+            I64F64::from_num(amount),
+            I64F64::from_num(0.000001),
+            "Inverse of quote_buy",
+        );
+    }
+
+    let tokens = curve.buy_with_reserve(I64F64::from_num(40)).unwrap();
+    let cost = integral(20.0 + tokens.to_num::<f64>()) - integral(20.0);
+    assert!((cost - 40.0).abs() < 1e-6, "cost {cost}");
+
+    let refund = curve.quote_sell(I64F64::from_num(10)).unwrap();
+    assert_approx_eq(
+        curve.sell_for_reserve(refund).unwrap(),
+        I64F64::from_num(10),
+        I64F64::from_num(0.000001),
+        "Tokens sold",
+    );
+}
+
+#[test]
+fn test_offset_power_without_base_price_matches_exponential() {
+    let mut offset = OffsetPower::new(0.001, 2.0, 0.0).unwrap();
+    let mut power = Exponential::new(0.001, 2.0).unwrap();
+    let amount = I64F64::from_num(40);
+    assert_approx_eq(
+        offset.buy_token(amount).unwrap(),
+        power.buy_token(amount).unwrap(),
+        I64F64::from_num(0.000001),
+        "Cost",
+    );
+    assert_eq!(offset.get_price().unwrap(), power.get_price().unwrap());
+}
+
+#[test]
+fn test_offset_power_with_supply() {
+    let seeded = OffsetPower::<I64F64>::with_supply(0.001, 2.0, 0.5, 30.0).unwrap();
+    assert_eq!(seeded.get_supply(), I64F64::from_num(30));
+    assert_approx_eq(
+        seeded.get_reserve().unwrap(),
+        I64F64::from_num(integral(30.0)),
+        I64F64::from_num(0.000001),
+        "Seeded reserve",
+    );
+
+    let result = OffsetPower::<I64F64>::with_supply(0.001, 2.0, 0.5, 1e18);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+}
@@ -0,0 +1,140 @@
+use bonding_curves::{
+    AffineLinear, Bancor, BondingCurve, BondingCurveError, ConstantProduct, Exponential,
+    LaunchCurve, Linear, Logarithmic, NaturalExponential, OffsetPower, Polynomial, Sigmoid,
+};
+use fixed::types::I64F64;
+
+// Trades far beyond the representable range must fail with `Overflow` and leave the
+// curve untouched, and every supply up to `max_supply` must be priced
+fn check_overflow_safety<C: BondingCurve + Clone>(curve: &mut C, name: &str) {
+    let before = curve.clone();
+    let huge = I64F64::MAX;
+
+    let result = curve.buy_exact_tokens(huge);
+    assert!(
+        matches!(result, Err(BondingCurveError::Overflow { .. })),
+        "{name}: buy_exact_tokens(MAX) returned {result:?}"
+    );
+    assert_eq!(curve.get_supply(), before.get_supply(), "{name}: supply");
+    assert_eq!(curve.get_reserve(), before.get_reserve(), "{name}: reserve");
+
+    // A curve whose integral grows slowly can absorb the largest reserve amount; either
+    // way the trade must not panic
+    let result = curve.clone().buy_with_reserve(huge);
+    assert!(
+        matches!(result, Ok(_) | Err(BondingCurveError::Overflow { .. })),
+        "{name}: buy_with_reserve(MAX) returned {result:?}"
+    );
+
+    // Buying up to the documented maximum succeeds
+    let max_supply = curve.max_supply();
+    assert!(max_supply > I64F64::from_num(1000), "{name}: max supply");
+    let remaining = max_supply - curve.get_supply();
+    curve.buy_exact_tokens(remaining).unwrap();
+    assert_eq!(curve.get_supply(), max_supply, "{name}: supply at max");
+    assert!(curve.get_price().unwrap() > I64F64::ZERO, "{name}: price");
+    assert!(curve.quote_sell(remaining).is_ok(), "{name}: sell back");
+}
+
+#[test]
+fn test_linear_overflow_safety() {
+    let mut curve = Linear::new(0.5).unwrap();
+    check_overflow_safety(&mut curve, "linear");
+
+    let mut steep = Linear::new(1e6).unwrap();
+    check_overflow_safety(&mut steep, "steep linear");
+}
+
+#[test]
+fn test_exponential_overflow_safety() {
+    let mut curve = Exponential::new(0.1, 2.0).unwrap();
+    check_overflow_safety(&mut curve, "exponential");
+}
+
+#[test]
+fn test_logarithmic_overflow_safety() {
+    let mut curve = Logarithmic::new(10.0, 1.0).unwrap();
+    check_overflow_safety(&mut curve, "logarithmic");
+}
+
+#[test]
+fn test_sigmoid_overflow_safety() {
+    let mut curve = Sigmoid::new(100.0, 0.01, 1000.0).unwrap();
+    check_overflow_safety(&mut curve, "sigmoid");
+    // Far past the midpoint the price saturates at the maximum
+    assert_eq!(curve.get_price().unwrap(), I64F64::from_num(100));
+}
+
+#[test]
+fn test_natural_exponential_overflow_safety() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+    check_overflow_safety(&mut curve, "natural exponential");
+}
+
+#[test]
+fn test_affine_linear_overflow_safety() {
+    let mut curve = AffineLinear::new(0.5, 10.0).unwrap();
+    check_overflow_safety(&mut curve, "affine linear");
+}
+
+#[test]
+fn test_offset_power_overflow_safety() {
+    let mut curve = OffsetPower::new(0.1, 2.0, 10.0).unwrap();
+    check_overflow_safety(&mut curve, "offset power");
+}
+
+#[test]
+fn test_polynomial_overflow_safety() {
+    let mut curve = Polynomial::new(&[10.0, 0.5, 0.01]).unwrap();
+    check_overflow_safety(&mut curve, "polynomial");
+}
+
+#[test]
+fn test_constant_product_overflow_safety() {
+    let mut curve = ConstantProduct::new(1_000_000.0, 0.0, 30.0).unwrap();
+    check_overflow_safety(&mut curve, "constant product");
+}
+
+#[test]
+fn test_launch_curve_overflow_safety() {
+    let mut curve = LaunchCurve::new(1_073_000_000.0, 30.0, 793_100_000.0, 1e9).unwrap();
+    check_overflow_safety(&mut curve, "launch curve");
+}
+
+#[test]
+fn test_bancor_overflow_safety() {
+    let mut curve = Bancor::new(1000, 1000, 0.5).unwrap();
+    let before = curve.clone();
+    let result = curve.buy_with_reserve(I64F64::MAX);
+    assert!(
+        matches!(result, Err(BondingCurveError::Overflow { .. })),
+        "buy_with_reserve(MAX) returned {result:?}"
+    );
+    let result = curve.buy_exact_tokens(I64F64::MAX);
+    assert!(
+        matches!(result, Err(BondingCurveError::Overflow { .. })),
+        "buy_exact_tokens(MAX) returned {result:?}"
+    );
+    assert_eq!(curve.get_supply(), before.get_supply());
+    assert_eq!(curve.get_reserve(), before.get_reserve());
+}
+
+#[test]
+fn test_initial_supply_above_max_supply() {
+    let max_supply = Linear::<I64F64>::new(2.0).unwrap().max_supply();
+    let result = Linear::<I64F64>::with_supply(2.0, max_supply.to_num::<f64>() * 2.0);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+    let result = Exponential::<I64F64>::with_supply(1.0, 2.0, 1e18);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+    // Values that do not fit the fixed-point type are rejected rather than saturated
+    assert!(matches!(
+        Linear::<I64F64>::new(1e30),
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "slope"
+    ));
+}
@@ -0,0 +1,208 @@
+use bonding_curves::{AffineLinear, BondingCurve, BondingCurveError, Polynomial};
+use fixed::types::I64F64;
+
+// Helper function for approximate equality
+fn assert_approx_eq(actual: I64F64, expected: I64F64, tolerance: I64F64, message: &str) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "{}: {} != {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+fn assert_invalid<T: std::fmt::Debug>(result: Result<T, BondingCurveError>, field: &str) {
+    assert!(
+        matches!(
+            &result,
+            Err(BondingCurveError::InvalidParameter { name, .. }) if name == field
+        ),
+        "expected an invalid {field}, got {result:?}"
+    );
+}
+
+// Integral of 2 + 0.1 * S + 0.003 * S^2 from 0 to `supply`
+fn integral(supply: f64) -> f64 {
+    2.0 * supply + 0.05 * supply.powi(2) + 0.001 * supply.powi(3)
+}
+
+#[test]
+fn test_polynomial_new_valid() {
+    let curve = Polynomial::new(&[2.0, 0.1, 0.003]).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    assert_eq!(curve.get_price().unwrap(), I64F64::from_num(2));
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+    assert_eq!(curve.degree(), 2);
+
+    // Trailing zeros do not raise the degree
+    let curve = Polynomial::<I64F64>::new(&[2.0, 0.1, 0.0, 0.0]).unwrap();
+    assert_eq!(curve.degree(), 1);
+
+    // A flat price is a fixed-price sale
+    let curve = Polynomial::<I64F64>::new(&[0.5]).unwrap();
+    assert_eq!(curve.degree(), 0);
+}
+
+#[test]
+fn test_polynomial_new_invalid() {
+    assert_invalid(Polynomial::<I64F64>::new(&[]), "coefficients");
+    assert_invalid(Polynomial::<I64F64>::new(&[0.0, 0.0]), "coefficients");
+    assert_invalid(Polynomial::<I64F64>::new(&[1.0; 10]), "coefficients");
+    assert_invalid(
+        Polynomial::<I64F64>::new(&[1.0, f64::NAN]),
+        "coefficients[1]",
+    );
+
+    // The first token cannot have a negative price
+    assert_invalid(Polynomial::<I64F64>::new(&[-1.0, 1.0]), "coefficients[0]");
+
+    // P = 10 - S falls from the start, P = 1 + S - S^2 once S > 0.5
+    assert_invalid(Polynomial::<I64F64>::new(&[10.0, -1.0]), "coefficients");
+    assert_invalid(Polynomial::<I64F64>::new(&[1.0, 1.0, -1.0]), "coefficients");
+}
+
+#[test]
+fn test_polynomial_negative_terms() {
+    // P = 1 + S - 1e-7 * S^2 still rises at max_supply, about 8e5
+    let curve = Polynomial::<I64F64>::new(&[1.0, 1.0, -1e-7]).unwrap();
+    assert!(curve.max_supply() < I64F64::from_num(5e6));
+
+    // P = 1 + 3S - 3S^2 + S^3 = (S - 1)^3 + 2 levels off at S = 1 without falling
+    let mut curve = Polynomial::<I64F64>::new(&[1.0, 3.0, -3.0, 1.0]).unwrap();
+    let before = curve.price_at(I64F64::from_num(0.999)).unwrap();
+    let at = curve.price_at(I64F64::from_num(1)).unwrap();
+    let after = curve.price_at(I64F64::from_num(1.001)).unwrap();
+    assert!(before <= at && at <= after);
+    assert_eq!(at, I64F64::from_num(2));
+
+    // Buying across the flat point costs the integral, S + 1.5S^2 - S^3 + S^4 / 4
+    let cost = curve.buy_token(I64F64::from_num(2)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(4),
+        I64F64::from_num(0.000001),
+        "Cost for 2 tokens",
+    );
+}
+
+#[test]
+fn test_polynomial_buy_and_sell() {
+    let mut curve = Polynomial::new(&[2.0, 0.1, 0.003]).unwrap();
+
+    let cost = curve.buy_token(I64F64::from_num(100)).unwrap();
+    assert_approx_eq(
+        cost,
+        I64F64::from_num(integral(100.0)),
+        I64F64::from_num(0.000001),
+        "Cost for 100 tokens",
+    );
+    assert_approx_eq(
+        curve.get_price().unwrap(),
+        I64F64::from_num(2.0 + 10.0 + 30.0),
+        I64F64::from_num(0.000001),
+        "Price after 100 tokens",
+    );
+
+    let refund = curve.sell_token(I64F64::from_num(40)).unwrap();
+    assert_approx_eq(
+        refund,
+        I64F64::from_num(integral(100.0) - integral(60.0)),
+        I64F64::from_num(0.000001),
+        "Refund for 40 tokens",
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(60));
+    assert_eq!(curve.get_reserve(), Some(cost - refund));
+
+    assert!(matches!(
+        curve.buy_token(I64F64::from_num(0)),
+        Err(BondingCurveError::ZeroAmount)
+    ));
+    assert!(matches!(
+        curve.sell_token(I64F64::from_num(61)),
+        Err(BondingCurveError::InsufficientSupply { .. })
+    ));
+}
+
+#[test]
+fn test_polynomial_buy_with_reserve() {
+    let mut curve = Polynomial::new(&[2.0, 0.1, 0.003]).unwrap();
+    curve.buy_token(I64F64::from_num(20)).unwrap();
+
+    // The supply reached costs exactly the reserve spent, found by the Newton solver
+    let tokens = curve.buy_with_reserve(I64F64::from_num(500)).unwrap();
+    assert_approx_eq(
+        I64F64::from_num(integral(20.0 + tokens.to_num::<f64>())),
+        I64F64::from_num(integral(20.0) + 500.0),
+        I64F64::from_num(0.000001),
+        "Integral at the new supply",
+    );
+
+    // Spending the quoted cost of a token amount buys back that amount
+    let cost = curve.quote_buy(I64F64::from_num(15)).unwrap();
+    assert_approx_eq(
+        curve.quote_buy_with_reserve(cost).unwrap(),
+        I64F64::from_num(15),
+        I64F64::from_num(0.000001),
+        "Inverse of quote_buy",
+    );
+
+    // Selling for the refund of 10 tokens sells 10 tokens
+    let refund = curve.quote_sell(I64F64::from_num(10)).unwrap();
+    assert_approx_eq(
+        curve.sell_for_reserve(refund).unwrap(),
+        I64F64::from_num(10),
+        I64F64::from_num(0.000001),
+        "Tokens sold",
+    );
+}
+
+#[test]
+fn test_polynomial_matches_affine_linear() {
+    let mut polynomial = Polynomial::new(&[1.0, 0.01]).unwrap();
+    let mut affine = AffineLinear::new(0.01, 1.0).unwrap();
+
+    let amount = I64F64::from_num(250);
+    assert_approx_eq(
+        polynomial.buy_token(amount).unwrap(),
+        affine.buy_token(amount).unwrap(),
+        I64F64::from_num(0.000001),
+        "Cost",
+    );
+    assert_eq!(polynomial.get_price(), affine.get_price());
+}
+
+#[test]
+fn test_polynomial_domain_limits() {
+    let curve = Polynomial::<I64F64>::new(&[2.0, 0.1, 0.003]).unwrap();
+
+    // S^3 * (2 + 0.1 + 0.003) stays within an eighth of the type's range
+    let max_supply = curve.max_supply();
+    let expected = (I64F64::MAX.to_num::<f64>() / 8.0 / 2.103).cbrt();
+    assert_approx_eq(
+        max_supply,
+        I64F64::from_num(expected),
+        I64F64::from_num(0.01),
+        "Max supply",
+    );
+    assert!(curve.cost_between(I64F64::ZERO, max_supply).is_ok());
+    assert!(matches!(
+        curve.price_at(max_supply * 100_000),
+        Err(BondingCurveError::Overflow { .. })
+    ));
+    assert_invalid(
+        Polynomial::<I64F64>::with_supply(&[2.0, 0.1, 0.003], expected * 2.0),
+        "initial_supply",
+    );
+}
+
+#[test]
+fn test_polynomial_with_supply() {
+    let seeded = Polynomial::<I64F64>::with_supply(&[2.0, 0.1, 0.003], 40.0).unwrap();
+    let mut bought = Polynomial::new(&[2.0, 0.1, 0.003]).unwrap();
+    let cost = bought.buy_token(I64F64::from_num(40)).unwrap();
+
+    assert_eq!(seeded.get_supply(), I64F64::from_num(40));
+    assert_eq!(seeded.get_reserve(), Some(cost));
+    assert_eq!(seeded.get_price().unwrap(), bought.get_price().unwrap());
+}
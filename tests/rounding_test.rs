@@ -0,0 +1,248 @@
+use bonding_curves::{
+    AffineLinear, Augmented, Bancor, BondingCurve, ConstantProduct, Exponential, LaunchCurve,
+    Linear, Logarithmic, NaturalExponential, OffsetPower, Polynomial, Rounding, Sigmoid,
+};
+use fixed::types::I64F64;
+
+// Deterministic xorshift generator so every run replays the same trade sequences
+struct Sequence(u64);
+
+impl Sequence {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // Alternates between dust of a few ulps and ordinary amounts up to `scale`
+    fn amount(&mut self, scale: f64) -> I64F64 {
+        let roll = self.next();
+        if roll.is_multiple_of(3) {
+            I64F64::DELTA * (1 + (roll >> 8) % 1000) as i128
+        } else {
+            let fraction = (roll >> 11) as f64 / (1u64 << 53) as f64;
+            I64F64::from_num(fraction * scale) + I64F64::DELTA
+        }
+    }
+}
+
+// Plays random buys and sells as a single trader, then sells back every token bought.
+// Whatever the sequence, the trader never ends with more reserve than they put in.
+fn check_no_extraction<C: BondingCurve + Clone>(curve: &C, name: &str, scale: f64, seed: u64) {
+    let mut curve = curve.clone();
+    let reserve_before = curve.get_reserve().unwrap();
+    let mut sequence = Sequence(seed);
+    let mut held = I64F64::ZERO;
+    let mut paid = I64F64::ZERO;
+    let mut received = I64F64::ZERO;
+
+    for _ in 0..400 {
+        let amount = sequence.amount(scale);
+        match sequence.next() % 4 {
+            0 => {
+                if let Ok(cost) = curve.buy_exact_tokens(amount) {
+                    held += amount;
+                    paid += cost;
+                }
+            }
+            1 => {
+                if let Ok(tokens) = curve.buy_with_reserve(amount) {
+                    held += tokens;
+                    paid += amount;
+                }
+            }
+            2 => {
+                let amount = amount.min(held);
+                if let Ok(refund) = curve.sell_token(amount) {
+                    held -= amount;
+                    received += refund;
+                }
+            }
+            _ => {
+                if let Ok(tokens) = curve.quote_sell_for_reserve(amount)
+                    && tokens <= held
+                {
+                    assert_eq!(curve.sell_for_reserve(amount).unwrap(), tokens);
+                    held -= tokens;
+                    received += amount;
+                }
+            }
+        }
+    }
+    if held > I64F64::ZERO {
+        received += curve.sell_token(held).unwrap();
+    }
+
+    assert!(
+        received <= paid,
+        "{name} (seed {seed}): received {received} for {paid}"
+    );
+    assert!(
+        curve.get_reserve().unwrap() >= reserve_before,
+        "{name} (seed {seed}): reserve fell to {:?}",
+        curve.get_reserve()
+    );
+}
+
+// Undoes single trades from the same state across dust and ordinary amounts, where
+// independently truncated quotes would otherwise hand the difference to the trader
+fn check_round_trips<C: BondingCurve + Clone>(curve: &C, name: &str, scale: f64) {
+    let mut sequence = Sequence(0x5EED);
+    for _ in 0..500 {
+        let amount = sequence.amount(scale);
+
+        // Reserve in, then every token bought sold back
+        let mut trip = curve.clone();
+        if let Ok(tokens) = trip.buy_with_reserve(amount)
+            && tokens > I64F64::ZERO
+        {
+            let refund = trip.sell_token(tokens).unwrap();
+            assert!(refund <= amount, "{name}: {refund} back for {amount}");
+        }
+
+        // Exact tokens in, then the same tokens sold back
+        let mut trip = curve.clone();
+        if let Ok(cost) = trip.buy_exact_tokens(amount) {
+            let refund = trip.sell_token(amount).unwrap();
+            assert!(refund <= cost, "{name}: {refund} back for {cost}");
+        }
+
+        // Reserve out, then the tokens sold for it bought back
+        let mut trip = curve.clone();
+        if let Ok(tokens) = trip.sell_for_reserve(amount) {
+            let cost = trip.buy_exact_tokens(tokens).unwrap();
+            assert!(
+                cost >= amount,
+                "{name}: {tokens} tokens bought back for {cost}"
+            );
+        }
+    }
+}
+
+fn check_rounding_policy<C: BondingCurve + Clone>(curve: &C, name: &str, scale: f64) {
+    for seed in 1..=8 {
+        check_no_extraction(curve, name, scale, seed * 0x9E37_79B9);
+    }
+    check_round_trips(curve, name, scale);
+}
+
+#[test]
+fn test_rounding_apply() {
+    let value = I64F64::from_num(10);
+    let error = I64F64::DELTA * 3;
+    assert_eq!(Rounding::Up.apply(value, error).unwrap(), value + error);
+    assert_eq!(Rounding::Down.apply(value, error).unwrap(), value - error);
+    assert!(Rounding::Up.apply(I64F64::MAX, error).is_err());
+    assert_eq!(
+        Rounding::Down.apply(I64F64::MIN, error).unwrap(),
+        I64F64::MIN
+    );
+}
+
+#[test]
+fn test_linear_rounding_favors_pool() {
+    let curve = Linear::with_supply(0.5, 100.0).unwrap();
+    check_rounding_policy(&curve, "linear", 10.0);
+    check_rounding_policy(&Linear::new(0.01).unwrap(), "empty linear", 10.0);
+}
+
+#[test]
+fn test_exponential_rounding_favors_pool() {
+    let curve = Exponential::with_supply(0.001, 2.0, 50.0).unwrap();
+    check_rounding_policy(&curve, "exponential", 10.0);
+    check_rounding_policy(
+        &Exponential::new(0.5, 1.5).unwrap(),
+        "empty exponential",
+        10.0,
+    );
+}
+
+#[test]
+fn test_logarithmic_rounding_favors_pool() {
+    let curve = Logarithmic::with_supply(10.0, 1.0, 100.0).unwrap();
+    check_rounding_policy(&curve, "logarithmic", 10.0);
+}
+
+#[test]
+fn test_sigmoid_rounding_favors_pool() {
+    let curve = Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    check_rounding_policy(&curve, "sigmoid", 10.0);
+    check_rounding_policy(
+        &Sigmoid::new(1.0, 0.01, 500.0).unwrap(),
+        "empty sigmoid",
+        10.0,
+    );
+}
+
+#[test]
+fn test_bancor_rounding_favors_pool() {
+    let curve = Bancor::new(1000, 10000, 0.5).unwrap();
+    check_rounding_policy(&curve, "bancor", 10.0);
+    check_rounding_policy(&Bancor::new(50, 100, 0.2).unwrap(), "steep bancor", 5.0);
+}
+
+#[test]
+fn test_natural_exponential_rounding_favors_pool() {
+    let curve = NaturalExponential::with_supply(0.5, 0.01, 100.0).unwrap();
+    check_rounding_policy(&curve, "natural exponential", 10.0);
+    check_rounding_policy(
+        &NaturalExponential::new(0.001, 0.5).unwrap(),
+        "steep natural exponential",
+        1.0,
+    );
+}
+
+#[test]
+fn test_affine_linear_rounding_favors_pool() {
+    let curve = AffineLinear::with_supply(0.01, 1.0, 100.0).unwrap();
+    check_rounding_policy(&curve, "affine linear", 10.0);
+}
+
+#[test]
+fn test_offset_power_rounding_favors_pool() {
+    let curve = OffsetPower::with_supply(0.001, 2.0, 0.5, 50.0).unwrap();
+    check_rounding_policy(&curve, "offset power", 10.0);
+}
+
+#[test]
+fn test_polynomial_rounding_favors_pool() {
+    let curve = Polynomial::with_supply(&[1.0, 0.02, 0.0003], 50.0).unwrap();
+    check_rounding_policy(&curve, "polynomial", 10.0);
+}
+
+#[test]
+fn test_constant_product_rounding_favors_pool() {
+    let curve = ConstantProduct::with_supply(10000.0, 100.0, 50.0, 100.0).unwrap();
+    check_rounding_policy(&curve, "constant product", 10.0);
+}
+
+#[test]
+fn test_launch_curve_rounding_favors_pool() {
+    let curve = LaunchCurve::with_supply(1073.0, 30.0, 793.1, 1000.0, 100.0).unwrap();
+    check_rounding_policy(&curve, "launch curve", 10.0);
+}
+
+#[test]
+fn test_augmented_rounding_favors_pool() {
+    let mut curve = Augmented::new(0.01, 0.5, 2000, 500, 1000).unwrap();
+    curve.buy_with_reserve(I64F64::from_num(1000)).unwrap();
+    curve.open().unwrap();
+    check_rounding_policy(&curve, "augmented", 10.0);
+}
+
+#[test]
+fn test_quotes_round_toward_pool() {
+    // Buying and immediately selling the same amount never returns more than it cost
+    let curve = Linear::with_supply(0.5, 100.0).unwrap();
+    let amount = I64F64::from_num(3);
+    let cost = curve.quote_buy_exact_tokens(amount).unwrap();
+    let mut bought = curve.clone();
+    bought.buy_exact_tokens(amount).unwrap();
+    assert!(bought.quote_sell(amount).unwrap() <= cost);
+
+    // The tokens a reserve buys cost no more than that reserve
+    let reserve = I64F64::from_num(7);
+    let tokens = curve.quote_buy_with_reserve(reserve).unwrap();
+    assert!(curve.quote_buy_exact_tokens(tokens).unwrap() <= reserve);
+}
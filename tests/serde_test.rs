@@ -0,0 +1,331 @@
+#![cfg(feature = "serde")]
+
+use bonding_curves::{
+    AffineLinear, AnyCurve, Augmented, AugmentedPhase, Bancor, BondingCurve, ConstantProduct,
+    CurveKind, Exponential, LaunchCurve, Linear, Logarithmic, NaturalExponential, OffsetPower,
+    Polynomial, Sigmoid,
+};
+use fixed::types::{I64F64, I80F48, U64F64};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+// Serializes `curve`, reads it back and checks that the state survived bit for bit
+fn round_trip<C>(curve: &C) -> C
+where
+    C: BondingCurve + Serialize + DeserializeOwned,
+{
+    let encoded = serde_json::to_string(curve).unwrap();
+    let decoded: C = serde_json::from_str(&encoded).unwrap();
+    assert_eq!(decoded.get_supply(), curve.get_supply(), "{encoded}");
+    assert_eq!(decoded.get_reserve(), curve.get_reserve(), "{encoded}");
+    assert_eq!(decoded.get_price(), curve.get_price(), "{encoded}");
+    decoded
+}
+
+// Deserializes `value` as `C` and returns the error message
+fn rejection<C: DeserializeOwned>(value: Value) -> String {
+    match serde_json::from_value::<C>(value) {
+        Ok(_) => panic!("state should have been rejected"),
+        Err(error) => error.to_string(),
+    }
+}
+
+#[test]
+fn test_curves_round_trip() {
+    let mut linear = Linear::with_supply(0.01, 100.0).unwrap();
+    linear.buy_token(I64F64::from_num(3.3)).unwrap();
+    let decoded = round_trip(&linear);
+    assert_eq!(decoded.slope, linear.slope);
+
+    let mut exponential = Exponential::with_supply(0.001, 2.0, 10.0).unwrap();
+    exponential.buy_with_reserve(I64F64::from_num(0.7)).unwrap();
+    let decoded = round_trip(&exponential);
+    assert_eq!(decoded.exponent, exponential.exponent);
+
+    let mut logarithmic = Logarithmic::with_supply(10.0, 1.0, 100.0).unwrap();
+    logarithmic.sell_token(I64F64::from_num(12.5)).unwrap();
+    round_trip(&logarithmic);
+
+    let mut sigmoid = Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    sigmoid.buy_token(I64F64::from_num(1) / 3).unwrap();
+    let decoded = round_trip(&sigmoid);
+    assert_eq!(decoded.midpoint, sigmoid.midpoint);
+
+    let mut bancor = Bancor::new(1000, 10000, 0.5).unwrap();
+    bancor.buy_token(I64F64::from_num(210)).unwrap();
+    let decoded = round_trip(&bancor);
+    assert_eq!(decoded.connector_weight, bancor.connector_weight);
+
+    let mut natural = NaturalExponential::with_supply(0.5, 0.01, 100.0).unwrap();
+    natural.buy_with_reserve(I64F64::from_num(2.5)).unwrap();
+    let decoded = round_trip(&natural);
+    assert_eq!(decoded.growth_rate, natural.growth_rate);
+
+    let affine = AffineLinear::with_supply(0.01, 1.0, 100.0).unwrap();
+    let decoded = round_trip(&affine);
+    assert_eq!(decoded.base_price, affine.base_price);
+
+    let offset = OffsetPower::with_supply(0.001, 2.0, 0.5, 10.0).unwrap();
+    let decoded = round_trip(&offset);
+    assert_eq!(decoded.base_price, offset.base_price);
+
+    let polynomial = Polynomial::with_supply(&[1.0, 0.02, 0.0003], 100.0).unwrap();
+    let decoded = round_trip(&polynomial);
+    assert_eq!(decoded.coefficients, polynomial.coefficients);
+
+    let pool = ConstantProduct::with_supply(10000.0, 100.0, 50.0, 100.0).unwrap();
+    let decoded = round_trip(&pool);
+    assert_eq!(decoded.token_balance, pool.token_balance);
+
+    let launch = LaunchCurve::with_supply(1073.0, 30.0, 793.1, 1000.0, 100.0).unwrap();
+    let decoded = round_trip(&launch);
+    assert_eq!(decoded.real_token_reserve, launch.real_token_reserve);
+}
+
+#[test]
+fn test_sold_out_pool_round_trips() {
+    // Selling the whole supply leaves a state that deserializes like any other
+    let mut bancor = Bancor::new(1000, 10000, 0.2).unwrap();
+    bancor.sell_token(I64F64::from_num(10000)).unwrap();
+    round_trip(&bancor);
+}
+
+#[test]
+fn test_near_full_sell_for_reserve_round_trips() {
+    // A request just under the reserve must not take the whole supply and leave dust
+    for weight in [0.2, 0.5, 1.0] {
+        let mut bancor = Bancor::new(1000, 10000, weight).unwrap();
+        let reserve = I64F64::from_num(1000);
+        let _ = bancor.sell_for_reserve(reserve - reserve * I64F64::DELTA);
+        round_trip(&bancor);
+        bancor
+            .sell_for_reserve(bancor.get_reserve().unwrap())
+            .unwrap();
+        round_trip(&bancor);
+    }
+}
+
+#[test]
+fn test_fixed_point_values_encode_as_decimal_strings() {
+    let curve = Linear::<I64F64>::with_supply(0.5, 100.0).unwrap();
+    let encoded = serde_json::to_value(&curve).unwrap();
+    assert_eq!(encoded["slope"], json!("0.5"));
+    assert_eq!(encoded["token_supply"], json!("100"));
+
+    // A third has no finite decimal or binary expansion; the string still restores every bit
+    let mut curve = Linear::<I64F64>::new(1.0).unwrap();
+    curve.token_supply = I64F64::from_num(1) / 3;
+    let decoded: Linear = serde_json::from_value(serde_json::to_value(&curve).unwrap()).unwrap();
+    assert_eq!(decoded.token_supply.to_bits(), curve.token_supply.to_bits());
+}
+
+#[test]
+fn test_other_fixed_point_types_round_trip() {
+    let mut unsigned = Linear::<U64F64>::new(0.5).unwrap();
+    unsigned.buy_token(U64F64::from_num(7.25)).unwrap();
+    round_trip_typed(&unsigned);
+
+    let wide = Sigmoid::<I80F48>::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    round_trip_typed(&wide);
+}
+
+fn round_trip_typed<C: Serialize + DeserializeOwned>(curve: &C) {
+    let encoded = serde_json::to_string(curve).unwrap();
+    let decoded: C = serde_json::from_str(&encoded).unwrap();
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), encoded);
+}
+
+#[test]
+fn test_deserialization_validates_parameters() {
+    let error = rejection::<Linear>(json!({
+        "slope": "-1",
+        "token_supply": "0",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("slope"), "{error}");
+
+    let error = rejection::<Exponential>(json!({
+        "coefficient": "0.001",
+        "exponent": "0",
+        "token_supply": "0",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("exponent"), "{error}");
+
+    let error = rejection::<Logarithmic>(json!({
+        "coefficient": "10",
+        "constant": "0",
+        "token_supply": "0",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("constant"), "{error}");
+
+    let error = rejection::<Sigmoid>(json!({
+        "max_price": "100",
+        "steepness": "0.1",
+        "midpoint": "-5",
+        "token_supply": "0",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("midpoint"), "{error}");
+
+    let error = rejection::<Bancor>(json!({
+        "reserve_balance": "1000",
+        "token_supply": "10000",
+        "connector_weight": "1.5",
+    }));
+    assert!(error.contains("connector_weight"), "{error}");
+    let error = rejection::<Bancor>(json!({
+        "reserve_balance": "500",
+        "token_supply": "0",
+        "connector_weight": "0.5",
+    }));
+    assert!(error.contains("reserve_balance"), "{error}");
+}
+
+#[test]
+fn test_deserialization_validates_state() {
+    let error = rejection::<Linear>(json!({
+        "slope": "0.5",
+        "token_supply": "-10",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("token_supply"), "{error}");
+
+    // A supply beyond `max_supply` is rejected just like an oversized initial supply
+    let error = rejection::<Exponential>(json!({
+        "coefficient": "1",
+        "exponent": "2",
+        "token_supply": "1000000000000000",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("token_supply"), "{error}");
+
+    let error = rejection::<Logarithmic>(json!({
+        "coefficient": "10",
+        "constant": "1",
+        "token_supply": "5",
+        "reserve_balance": "-1",
+    }));
+    assert!(error.contains("reserve_balance"), "{error}");
+}
+
+#[test]
+fn test_deserialization_rejects_malformed_input() {
+    // Numbers are not accepted in place of decimal strings, so no value passes through a float
+    let error = rejection::<Linear>(json!({
+        "slope": 0.5,
+        "token_supply": "0",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("string"), "{error}");
+
+    let error = rejection::<Linear>(json!({
+        "slope": "half",
+        "token_supply": "0",
+        "reserve_balance": "0",
+    }));
+    assert!(!error.is_empty());
+
+    let error = rejection::<Linear>(json!({
+        "slope": "0.5",
+        "token_supply": "0",
+        "reserve_balance": "0",
+        "midpoint": "1",
+    }));
+    assert!(error.contains("midpoint"), "{error}");
+
+    let error = rejection::<Linear>(json!({ "slope": "0.5" }));
+    assert!(error.contains("token_supply"), "{error}");
+}
+
+#[test]
+fn test_any_curve_round_trip_with_kind_tag() {
+    let mut curve: AnyCurve = Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap().into();
+    curve.buy_token(I64F64::from_num(2)).unwrap();
+    let encoded = serde_json::to_value(&curve).unwrap();
+    assert_eq!(encoded["kind"], json!("sigmoid"));
+    assert_eq!(encoded["midpoint"], json!("50"));
+    let decoded = round_trip(&curve);
+    assert_eq!(decoded.kind(), CurveKind::Sigmoid);
+
+    let decoded: AnyCurve = serde_json::from_value(json!({
+        "kind": "bancor",
+        "reserve_balance": "1000",
+        "token_supply": "10000",
+        "connector_weight": "0.5",
+    }))
+    .unwrap();
+    assert_eq!(decoded.kind(), CurveKind::Bancor);
+
+    // The wrapped curve's validation still applies
+    let error = rejection::<AnyCurve>(json!({
+        "kind": "linear",
+        "slope": "0",
+        "token_supply": "0",
+        "reserve_balance": "0",
+    }));
+    assert!(error.contains("slope"), "{error}");
+    let error = rejection::<AnyCurve>(json!({ "kind": "quadratic", "slope": "1" }));
+    assert!(error.contains("quadratic"), "{error}");
+}
+
+#[test]
+fn test_augmented_round_trips_in_each_phase() {
+    let mut curve = Augmented::new(0.01, 0.5, 2000, 500, 1000).unwrap();
+    curve.buy_with_reserve(I64F64::from_num(1000)).unwrap();
+    let encoded = serde_json::to_value(&curve).unwrap();
+    assert_eq!(encoded["phase"], json!("hatch"));
+    assert_eq!(encoded["hatch_tribute_bps"], json!(2000));
+    let decoded = round_trip(&curve);
+    assert_eq!(decoded.phase(), AugmentedPhase::Hatch);
+    assert_eq!(decoded.funding_pool(), curve.funding_pool());
+
+    // A restored hatch opens and trades like the original
+    let mut restored = decoded;
+    curve.open().unwrap();
+    restored.open().unwrap();
+    let amount = I64F64::from_num(10000);
+    assert_eq!(restored.sell_token(amount), curve.sell_token(amount));
+    assert_eq!(restored.reserve_pool(), curve.reserve_pool());
+    let decoded = round_trip(&curve);
+    assert_eq!(decoded.phase(), AugmentedPhase::Open);
+    assert_eq!(decoded.funding_pool(), curve.funding_pool());
+    assert_eq!(decoded.exit_tribute_bps(), 1000);
+
+    let any: AnyCurve = curve.into();
+    let encoded = serde_json::to_value(&any).unwrap();
+    assert_eq!(encoded["kind"], json!("augmented"));
+    assert_eq!(round_trip(&any).kind(), CurveKind::Augmented);
+}
+
+#[test]
+fn test_augmented_deserialization_validates_state() {
+    let state = |phase: &str, supply: &str, reserve: &str, funding: &str| {
+        json!({
+            "hatch_price": "0.01",
+            "connector_weight": "0.5",
+            "hatch_tribute_bps": 2000,
+            "entry_tribute_bps": 500,
+            "exit_tribute_bps": 1000,
+            "phase": phase,
+            "token_supply": supply,
+            "reserve_pool": reserve,
+            "funding_pool": funding,
+        })
+    };
+    let error = rejection::<Augmented>(state("open", "100", "10", "-1"));
+    assert!(error.contains("funding_pool"), "{error}");
+
+    // An open curve without tokens cannot hold a reserve
+    let error = rejection::<Augmented>(state("open", "0", "10", "0"));
+    assert!(error.contains("reserve_pool"), "{error}");
+    let error = rejection::<Augmented>(state("closed", "100", "10", "0"));
+    assert!(error.contains("closed"), "{error}");
+
+    let mut invalid = state("hatch", "0", "0", "0");
+    invalid["exit_tribute_bps"] = json!(10000);
+    let error = rejection::<Augmented>(invalid);
+    assert!(error.contains("exit_tribute_bps"), "{error}");
+}
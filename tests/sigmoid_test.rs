@@ -24,58 +24,58 @@ fn test_sigmoid_new_valid() {
         I64F64::from_num(0.001),
         "Initial price",
     );
-    assert_eq!(curve.get_reserve(), None);
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
 }
 
 #[test]
 fn test_sigmoid_new_invalid() {
     // Zero max_price
-    let result = Sigmoid::new(0.0, 0.1, 50.0);
+    let result = Sigmoid::<I64F64>::new(0.0, 0.1, 50.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid parameters")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "max_price"
     ));
 
     // Negative max_price
-    let result = Sigmoid::new(-1.0, 0.1, 50.0);
+    let result = Sigmoid::<I64F64>::new(-1.0, 0.1, 50.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid parameters")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "max_price"
     ));
 
     // Zero steepness
-    let result = Sigmoid::new(100.0, 0.0, 50.0);
+    let result = Sigmoid::<I64F64>::new(100.0, 0.0, 50.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid parameters")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "steepness"
     ));
 
     // Negative steepness
-    let result = Sigmoid::new(100.0, -0.1, 50.0);
+    let result = Sigmoid::<I64F64>::new(100.0, -0.1, 50.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid parameters")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "steepness"
     ));
 
     // Negative midpoint
-    let result = Sigmoid::new(100.0, 0.1, -1.0);
+    let result = Sigmoid::<I64F64>::new(100.0, 0.1, -1.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid parameters")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "midpoint"
     ));
 
     // NaN max_price
-    let result = Sigmoid::new(f64::NAN, 0.1, 50.0);
+    let result = Sigmoid::<I64F64>::new(f64::NAN, 0.1, 50.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid parameters")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "max_price"
     ));
 
     // Infinity steepness
-    let result = Sigmoid::new(100.0, f64::INFINITY, 50.0);
+    let result = Sigmoid::<I64F64>::new(100.0, f64::INFINITY, 50.0);
     assert!(matches!(
         result,
-        Err(BondingCurveError::InvalidInput(msg)) if msg.contains("Invalid parameters")
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "steepness"
     ));
 }
 
@@ -263,3 +263,146 @@ fn test_sigmoid_edge_cases() {
         "Cost for large steepness",
     );
 }
+
+#[test]
+fn test_sigmoid_quotes() {
+    let mut curve = Sigmoid::new(100.0, 0.1, 50.0).unwrap();
+
+    // Quoting does not change the curve's state
+    let quoted_cost = curve.quote_buy(I64F64::from_num(75)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+    assert_eq!(curve.buy_token(I64F64::from_num(75)).unwrap(), quoted_cost);
+
+    let quoted_refund = curve.quote_sell(I64F64::from_num(30)).unwrap();
+    assert_eq!(curve.get_supply(), I64F64::from_num(75));
+    assert_eq!(
+        curve.sell_token(I64F64::from_num(30)).unwrap(),
+        quoted_refund
+    );
+    assert_eq!(curve.get_supply(), I64F64::from_num(45));
+
+    // Quotes are validated like trades
+    assert!(curve.quote_buy(I64F64::from_num(0)).is_err());
+    assert!(curve.quote_sell(I64F64::from_num(46)).is_err());
+}
+
+#[test]
+fn test_sigmoid_buy_with_reserve() {
+    let mut curve = Sigmoid::new(100.0, 0.1, 50.0).unwrap();
+
+    // The softplus inverse recovers the supply for a given cost
+    let cost = curve.quote_buy(I64F64::from_num(50)).unwrap();
+    let tokens = curve.buy_with_reserve(cost).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(50),
+        I64F64::from_num(0.0001),
+        "Tokens for the cost of 50",
+    );
+
+    // Buying past the midpoint
+    let cost = curve.quote_buy(I64F64::from_num(30)).unwrap();
+    let quoted = curve.quote_buy_with_reserve(cost).unwrap();
+    assert_eq!(curve.buy_with_reserve(cost).unwrap(), quoted);
+    assert_approx_eq(
+        curve.get_supply(),
+        I64F64::from_num(80),
+        I64F64::from_num(0.0001),
+        "Supply after second buy",
+    );
+
+    assert!(matches!(
+        curve.buy_with_reserve(I64F64::from_num(0)),
+        Err(BondingCurveError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn test_sigmoid_exact_output() {
+    let mut curve = Sigmoid::new(100.0, 0.1, 50.0).unwrap();
+
+    let quoted = curve.quote_buy_exact_tokens(I64F64::from_num(80)).unwrap();
+    assert_eq!(quoted, curve.quote_buy(I64F64::from_num(80)).unwrap());
+    assert_eq!(
+        curve.buy_exact_tokens(I64F64::from_num(80)).unwrap(),
+        quoted
+    );
+
+    // Selling for the refund of 30 tokens sells 30 tokens
+    let refund = curve.quote_sell(I64F64::from_num(30)).unwrap();
+    let tokens = curve.sell_for_reserve(refund).unwrap();
+    assert_approx_eq(
+        tokens,
+        I64F64::from_num(30),
+        I64F64::from_num(0.0001),
+        "Tokens sold",
+    );
+    assert_approx_eq(
+        curve.get_supply(),
+        I64F64::from_num(50),
+        I64F64::from_num(0.0001),
+        "Supply after sell",
+    );
+
+    let all = curve.quote_sell(curve.get_supply()).unwrap();
+    assert!(matches!(
+        curve.sell_for_reserve(all + I64F64::from_num(1)),
+        Err(BondingCurveError::InsufficientReserve { .. })
+    ));
+}
+
+#[test]
+fn test_sigmoid_reserve_accounting() {
+    let mut curve = Sigmoid::new(100.0, 0.1, 50.0).unwrap();
+    assert_eq!(curve.get_reserve(), Some(I64F64::from_num(0)));
+
+    // The reserve collects buy costs and pays out sell refunds
+    let cost = curve.buy_token(I64F64::from_num(60)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost));
+    let refund = curve.sell_token(I64F64::from_num(25)).unwrap();
+    assert_eq!(curve.get_reserve(), Some(cost - refund));
+    let tokens = curve.sell_for_reserve(I64F64::from_num(1)).unwrap();
+    assert!(tokens > I64F64::from_num(0));
+    assert_eq!(
+        curve.get_reserve(),
+        Some(cost - refund - I64F64::from_num(1))
+    );
+
+    // Selling the whole supply drains the reserve
+    curve.sell_token(curve.get_supply()).unwrap();
+    assert_approx_eq(
+        curve.get_reserve().unwrap(),
+        I64F64::from_num(0),
+        I64F64::from_num(0.000001),
+        "Reserve after selling everything",
+    );
+}
+
+#[test]
+fn test_sigmoid_with_supply() {
+    let mut seeded = Sigmoid::with_supply(100.0, 0.1, 50.0, 40.0).unwrap();
+    let mut bought = Sigmoid::new(100.0, 0.1, 50.0).unwrap();
+    let cost = bought.buy_token(I64F64::from_num(40)).unwrap();
+
+    // The seeded reserve equals the integral of the price up to the initial supply
+    assert_eq!(seeded.get_supply(), I64F64::from_num(40));
+    assert_approx_eq(
+        seeded.get_reserve().unwrap(),
+        cost,
+        I64F64::from_num(0.000001),
+        "Seeded reserve",
+    );
+    assert_approx_eq(
+        seeded.get_price().unwrap(),
+        bought.get_price().unwrap(),
+        I64F64::from_num(0.000001),
+        "Seeded price",
+    );
+    seeded.sell_token(I64F64::from_num(40)).unwrap();
+
+    let result = Sigmoid::<I64F64>::with_supply(100.0, 0.1, 50.0, f64::NAN);
+    assert!(matches!(
+        result,
+        Err(BondingCurveError::InvalidParameter { name, .. }) if name == "initial_supply"
+    ));
+}
@@ -0,0 +1,131 @@
+use bonding_curves::{
+    Bancor, BondingCurve, BondingCurveError, Exponential, Linear, Logarithmic, NaturalExponential,
+    Sigmoid,
+};
+use fixed::types::I64F64;
+
+// Runs the same slippage checks against any curve that already has some supply
+fn check_slippage_limits<C: BondingCurve + Clone>(curve: &mut C, name: &str) {
+    let reserve_amount = I64F64::from_num(10);
+    let token_amount = I64F64::from_num(5);
+
+    // Buying with a reserve amount
+    let tokens = curve.quote_buy_with_reserve(reserve_amount).unwrap();
+    let before = curve.clone();
+    let result = curve.buy_with_reserve_min_out(reserve_amount, tokens + I64F64::from_num(1));
+    assert!(
+        matches!(result, Err(BondingCurveError::SlippageExceeded { .. })),
+        "{name}: buy_with_reserve_min_out should fail"
+    );
+    assert_eq!(curve.get_supply(), before.get_supply(), "{name}: supply");
+    assert_eq!(curve.get_reserve(), before.get_reserve(), "{name}: reserve");
+    assert_eq!(
+        curve
+            .buy_with_reserve_min_out(reserve_amount, tokens)
+            .unwrap(),
+        tokens,
+        "{name}: buy_with_reserve_min_out at the limit"
+    );
+
+    // Buying exact tokens
+    let cost = curve.quote_buy_exact_tokens(token_amount).unwrap();
+    let before = curve.clone();
+    let result = curve.buy_exact_tokens_max_in(token_amount, cost - I64F64::from_num(0.001));
+    assert!(
+        matches!(result, Err(BondingCurveError::SlippageExceeded { .. })),
+        "{name}: buy_exact_tokens_max_in should fail"
+    );
+    assert_eq!(curve.get_supply(), before.get_supply(), "{name}: supply");
+    assert_eq!(
+        curve.buy_exact_tokens_max_in(token_amount, cost).unwrap(),
+        cost,
+        "{name}: buy_exact_tokens_max_in at the limit"
+    );
+
+    // Selling tokens
+    let refund = curve.quote_sell(token_amount).unwrap();
+    let before = curve.clone();
+    let result = curve.sell_token_min_out(token_amount, refund + I64F64::from_num(0.001));
+    assert!(
+        matches!(result, Err(BondingCurveError::SlippageExceeded { .. })),
+        "{name}: sell_token_min_out should fail"
+    );
+    assert_eq!(curve.get_supply(), before.get_supply(), "{name}: supply");
+    assert_eq!(
+        curve.sell_token_min_out(token_amount, refund).unwrap(),
+        refund,
+        "{name}: sell_token_min_out at the limit"
+    );
+
+    // Selling for an exact reserve amount
+    let tokens = curve.quote_sell_for_reserve(reserve_amount).unwrap();
+    let before = curve.clone();
+    let result = curve.sell_for_reserve_max_in(reserve_amount, tokens - I64F64::from_num(0.001));
+    assert!(
+        matches!(result, Err(BondingCurveError::SlippageExceeded { .. })),
+        "{name}: sell_for_reserve_max_in should fail"
+    );
+    assert_eq!(curve.get_supply(), before.get_supply(), "{name}: supply");
+    assert_eq!(
+        curve
+            .sell_for_reserve_max_in(reserve_amount, tokens)
+            .unwrap(),
+        tokens,
+        "{name}: sell_for_reserve_max_in at the limit"
+    );
+}
+
+#[test]
+fn test_linear_slippage_limits() {
+    let mut curve = Linear::new(0.01).unwrap();
+    curve.buy_token(I64F64::from_num(100)).unwrap();
+    check_slippage_limits(&mut curve, "Linear");
+}
+
+#[test]
+fn test_exponential_slippage_limits() {
+    let mut curve = Exponential::new(0.001, 2.0).unwrap();
+    curve.buy_token(I64F64::from_num(100)).unwrap();
+    check_slippage_limits(&mut curve, "Exponential");
+}
+
+#[test]
+fn test_logarithmic_slippage_limits() {
+    let mut curve = Logarithmic::new(10.0, 1.0).unwrap();
+    curve.buy_token(I64F64::from_num(100)).unwrap();
+    check_slippage_limits(&mut curve, "Logarithmic");
+}
+
+#[test]
+fn test_sigmoid_slippage_limits() {
+    let mut curve = Sigmoid::new(100.0, 0.1, 50.0).unwrap();
+    curve.buy_token(I64F64::from_num(100)).unwrap();
+    check_slippage_limits(&mut curve, "Sigmoid");
+}
+
+#[test]
+fn test_natural_exponential_slippage_limits() {
+    let mut curve = NaturalExponential::new(0.5, 0.01).unwrap();
+    curve.buy_token(I64F64::from_num(100)).unwrap();
+    check_slippage_limits(&mut curve, "NaturalExponential");
+}
+
+#[test]
+fn test_bancor_slippage_limits() {
+    let mut curve = Bancor::new(1000, 10000, 0.2).unwrap();
+    check_slippage_limits(&mut curve, "Bancor");
+}
+
+#[test]
+fn test_slippage_error_reports_amounts() {
+    let mut curve = Linear::new(0.01).unwrap();
+    let result = curve.buy_exact_tokens_max_in(I64F64::from_num(100), I64F64::from_num(40));
+    match result {
+        Err(BondingCurveError::SlippageExceeded { amount, limit }) => {
+            assert!((amount - 50.0).abs() < 1e-9);
+            assert!((limit - 40.0).abs() < 1e-9);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert_eq!(curve.get_supply(), I64F64::from_num(0));
+}